@@ -0,0 +1,681 @@
+//! Many conversions from one input: `batch` request lines or JSON arrays,
+//! and the `convert --watch` loop
+
+use crate::convert::{convert_recognized, note_assumed_gallon};
+use crate::errors::Warnings;
+use crate::format::{format_result, format_value, OutputOptions};
+use crate::output::{self, write_line, write_lines, OutputFormat};
+use crate::report::{BatchAnswer, ConversionRequest, ConversionResult, ErrorObject, ErrorOutput};
+use crate::units::{parse_positional, require_unit, RecognizedUnit};
+use anyhow::{anyhow, Context, Result};
+use std::io::{BufRead, Write};
+use std::num::NonZeroUsize;
+
+/// The answer to one `--watch` request line, as text, JSON or CSV. A failed
+/// request gets an error line rather than ending the loop, so the driving
+/// program receives one line per request; under CSV the error goes to
+/// stderr instead (`None`) to keep the stream parseable.
+pub fn answer_request(
+    line: &str,
+    output: OutputOptions,
+    warnings: &mut Warnings,
+) -> Result<Option<String>> {
+    let tokens: Vec<String> = line.split_whitespace().map(str::to_string).collect();
+    let answer = convert_request(&tokens, warnings);
+    render_answer(answer, warnings, output)
+}
+
+/// Runs one `<VALUE> <FROM> <TO>` request, in any token order
+pub fn convert_request(
+    tokens: &[String],
+    warnings: &mut Warnings,
+) -> Result<(f64, RecognizedUnit, f64, RecognizedUnit)> {
+    let (from, to, value) = parse_positional(tokens)?;
+    note_assumed_gallon(&[&from, &to], warnings);
+    let from = require_unit(&from)?;
+    let to = require_unit(&to)?;
+    let result = convert_recognized(value, &from, &to, warnings)?;
+    Ok((value, from, result, to))
+}
+
+/// The line printed for a request's outcome; see `answer_request`. A JSON
+/// answer takes the request's `warnings` along, leaving none for stderr.
+pub fn render_answer(
+    answer: Result<(f64, RecognizedUnit, f64, RecognizedUnit)>,
+    warnings: &mut Warnings,
+    output: OutputOptions,
+) -> Result<Option<String>> {
+    match answer {
+        Ok((value, from, result, to)) => {
+            let output = output.in_category(from.category());
+            let warnings = if output.format == OutputFormat::Json {
+                std::mem::take(warnings)
+            } else {
+                Warnings::new()
+            };
+            let machine =
+                output::machine_line(output.format, value, &from, result, &to, &warnings)?;
+            Ok(Some(machine.unwrap_or_else(|| {
+                let printed = format_result(result, output);
+                if output.raw {
+                    return printed;
+                }
+                format!(
+                    "{} {} = {} {}",
+                    format_value(value, output),
+                    from,
+                    printed,
+                    to
+                )
+            })))
+        }
+        Err(e) if output.format == OutputFormat::Json => {
+            Ok(Some(serde_json::to_string(&ErrorOutput {
+                error: format!("{:#}", e),
+            })?))
+        }
+        Err(e) => {
+            // The first line names the problem; unit lists would drown the stream
+            let message = format!(
+                "error: {}",
+                format!("{:#}", e).lines().next().unwrap_or_default()
+            );
+            if output.format == OutputFormat::Csv {
+                eprintln!("{}", message);
+                return Ok(None);
+            }
+            Ok(Some(message))
+        }
+    }
+}
+
+/// `convert --watch`: answers each request line from `input` as soon as it
+/// is read, flushing after every answer so a program driving uniconv over a
+/// pipe never waits on a buffer. Blank lines are skipped. Stops at end of
+/// input, or quietly when the reader of `out` goes away.
+pub fn watch_stdin(
+    input: impl BufRead,
+    out: &mut (impl Write + ?Sized),
+    output: OutputOptions,
+) -> Result<()> {
+    if output.format == OutputFormat::Csv
+        && output.csv_header
+        && !write_lines(out, [output::CSV_HEADER])?
+    {
+        return Ok(());
+    }
+
+    for line in input.lines() {
+        let line = line.context("Failed to read a request from stdin")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut warnings = Warnings::new();
+        let answer = answer_request(&line, output, &mut warnings)?;
+        if !write_lines(out, answer)? {
+            return Ok(());
+        }
+        warnings.write_text(&mut std::io::stderr())?;
+    }
+
+    Ok(())
+}
+
+/// The tally `batch` prints as its closing summary
+pub struct BatchReport {
+    pub converted: usize,
+    pub failed: usize,
+    /// The reader of the answers went away before the input ran out
+    pub closed: bool,
+    /// `--max-errors` stopped the run early
+    pub aborted: bool,
+    /// Requests left unconverted after the abort
+    pub skipped: usize,
+}
+
+/// How `batch` splits request lines into fields, decided by the first one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BatchSyntax {
+    /// `cm,inch,188`
+    Csv,
+    /// `188 cm inch`
+    Whitespace,
+}
+
+impl BatchSyntax {
+    pub fn detect(line: &str) -> Self {
+        if line.contains(',') {
+            BatchSyntax::Csv
+        } else {
+            BatchSyntax::Whitespace
+        }
+    }
+
+    pub fn fields(self, line: &str) -> Vec<String> {
+        match self {
+            BatchSyntax::Csv => line
+                .split(',')
+                .map(|field| field.trim().to_string())
+                .collect(),
+            BatchSyntax::Whitespace => line.split_whitespace().map(str::to_string).collect(),
+        }
+    }
+}
+
+/// `batch --from-column N --to UNIT`: rows that name their own source unit
+/// in one field, like a log mixing `cm` and `in` readings, all converted to
+/// one target unit
+#[derive(Debug, Clone)]
+pub struct UnitColumn {
+    /// Index of the field holding the source unit, counted from 0
+    pub from: usize,
+    pub to: String,
+}
+
+impl UnitColumn {
+    /// The row as a `<VALUE> <FROM> <TO>` request: the unit in its column,
+    /// the one numeric field among the others, and the shared target unit
+    pub fn request(&self, fields: &[String]) -> Result<Vec<String>> {
+        let from = fields.get(self.from).ok_or_else(|| {
+            anyhow!(
+                "No unit column {}: the row has {} field(s).",
+                self.from + 1,
+                fields.len()
+            )
+        })?;
+        let numbers: Vec<&String> = fields
+            .iter()
+            .enumerate()
+            .filter(|(index, field)| *index != self.from && field.parse::<f64>().is_ok())
+            .map(|(_, field)| field)
+            .collect();
+
+        match numbers.as_slice() {
+            [value] => Ok(vec![value.to_string(), from.clone(), self.to.clone()]),
+            [] => Err(anyhow!("No numeric value found outside the unit column.")),
+            _ => Err(anyhow!(
+                "Ambiguous row: expected exactly one numeric value outside the unit column but found {}.",
+                numbers.len()
+            )),
+        }
+    }
+}
+
+/// `batch`: converts every request line from `input`, writing each answer
+/// to `out` as it goes so large inputs are never held in memory. The first
+/// request line picks comma- or whitespace-separated fields for the rest;
+/// the value may be in any position. With a `unit_column` each row is
+/// converted from the unit in that field to its target instead. A failed
+/// line gets an error answer
+/// naming its line number and the run goes on, unless it is the
+/// `max_errors`th failure: then the remaining requests are only counted.
+/// Blank lines and `#` comments are skipped.
+pub fn run_batch(
+    input: impl BufRead,
+    out: &mut (impl Write + ?Sized),
+    output: OutputOptions,
+    unit_column: Option<&UnitColumn>,
+    max_errors: Option<NonZeroUsize>,
+    warnings: &mut Warnings,
+) -> Result<BatchReport> {
+    let mut report = BatchReport {
+        converted: 0,
+        failed: 0,
+        closed: false,
+        aborted: false,
+        skipped: 0,
+    };
+
+    if output.format == OutputFormat::Csv
+        && output.csv_header
+        && !write_line(out, output::CSV_HEADER)?
+    {
+        report.closed = true;
+        return Ok(report);
+    }
+
+    let mut syntax = None;
+    for (index, line) in input.lines().enumerate() {
+        let line = line.context("Failed to read a batch request")?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if report.aborted {
+            report.skipped += 1;
+            continue;
+        }
+
+        let fields = syntax
+            .get_or_insert_with(|| BatchSyntax::detect(line))
+            .fields(line);
+        let mut line_warnings = Warnings::new();
+        let answer = match unit_column {
+            Some(column) => column
+                .request(&fields)
+                .and_then(|tokens| convert_request(&tokens, &mut line_warnings)),
+            None => convert_request(&fields, &mut line_warnings),
+        }
+        .with_context(|| format!("line {}", index + 1));
+        if answer.is_ok() {
+            report.converted += 1;
+        } else {
+            report.failed += 1;
+            report.aborted = max_errors.is_some_and(|limit| report.failed >= limit.get());
+        }
+        let answer = render_answer(answer, &mut line_warnings, output)?;
+        warnings.append(&mut line_warnings);
+        if let Some(answer) = answer {
+            if !write_line(out, &answer)? {
+                report.closed = true;
+                return Ok(report);
+            }
+        }
+    }
+
+    report.closed = !write_lines(out, std::iter::empty::<&str>())?;
+    Ok(report)
+}
+
+/// Whether a batch input is a JSON array rather than request lines, judged
+/// by its first non-blank character without consuming anything
+pub fn starts_json_array(input: &mut impl BufRead) -> Result<bool> {
+    let buffer = input.fill_buf().context("Failed to read a batch request")?;
+    Ok(buffer.iter().find(|byte| !byte.is_ascii_whitespace()) == Some(&b'['))
+}
+
+/// `batch` over a JSON array of `ConversionRequest`s: prints a JSON array
+/// with one answer per entry, in order. An entry that isn't a valid request
+/// gets an `{"error": ...}` object in its place; the others are answered
+/// as `ConversionResult`s, failed or not. Unlike request lines the array is
+/// read whole; after the `max_errors`th failure the rest of it goes
+/// unanswered.
+pub fn run_json_batch(
+    input: impl BufRead,
+    out: &mut (impl Write + ?Sized),
+    max_errors: Option<NonZeroUsize>,
+) -> Result<BatchReport> {
+    let entries: Vec<serde_json::Value> =
+        serde_json::from_reader(input).context("Batch input is not a valid JSON array")?;
+
+    let total = entries.len();
+    let mut answers = Vec::new();
+    let mut failed = 0;
+    for (index, entry) in entries.into_iter().enumerate() {
+        let answer = match serde_json::from_value::<ConversionRequest>(entry) {
+            Ok(request) => BatchAnswer::Answered(answer_json_request(request)),
+            Err(e) => BatchAnswer::Malformed(ErrorOutput {
+                error: format!("entry {}: {}", index + 1, e),
+            }),
+        };
+        if answer.failed() {
+            failed += 1;
+        }
+        answers.push(answer);
+        if max_errors.is_some_and(|limit| failed >= limit.get()) {
+            break;
+        }
+    }
+
+    Ok(BatchReport {
+        converted: answers.len() - failed,
+        failed,
+        closed: !write_lines(out, [serde_json::to_string_pretty(&answers)?])?,
+        aborted: max_errors.is_some_and(|limit| failed >= limit.get()),
+        skipped: total - answers.len(),
+    })
+}
+
+/// Converts one JSON request, keeping its warnings with the answer
+pub fn answer_json_request(request: ConversionRequest) -> ConversionResult {
+    let mut warnings = Warnings::new();
+    let answer = (|| {
+        note_assumed_gallon(&[&request.from, &request.to], &mut warnings);
+        let from = require_unit(&request.from).map_err(|e| ("invalid-unit", e))?;
+        let to = require_unit(&request.to).map_err(|e| ("invalid-unit", e))?;
+        convert_recognized(request.value, &from, &to, &mut warnings)
+            .map_err(|e| ("conversion-failed", e))
+    })();
+
+    match answer {
+        Ok(result) => ConversionResult::success(request, result, warnings),
+        Err((code, e)) => ConversionResult::failure(
+            request,
+            ErrorObject {
+                code,
+                message: format!("{:#}", e),
+            },
+            warnings,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conv::UnitOrder;
+    use crate::format::Rounding;
+    use crate::output::{DecimalSeparator, UnitNames};
+
+    /// Output that only becomes visible when flushed, like a pipe behind
+    /// a buffer
+    #[derive(Clone, Default)]
+    struct Visible(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+    impl Write for Visible {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Hands out one request line per read, first checking that every
+    /// earlier request has already been answered
+    struct Requests {
+        lines: Vec<&'static str>,
+        next: usize,
+        answers: Visible,
+    }
+
+    impl std::io::Read for Requests {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let answered = self
+                .answers
+                .0
+                .borrow()
+                .iter()
+                .filter(|&&b| b == b'\n')
+                .count();
+            assert_eq!(
+                answered, self.next,
+                "answer to line {} not flushed",
+                self.next
+            );
+
+            let Some(line) = self.lines.get(self.next) else {
+                return Ok(0);
+            };
+            self.next += 1;
+            let line = format!("{}\n", line);
+            buf[..line.len()].copy_from_slice(line.as_bytes());
+            Ok(line.len())
+        }
+    }
+
+    #[test]
+    fn test_watch_answers_each_line_immediately() -> Result<()> {
+        let answers = Visible::default();
+        let requests = Requests {
+            lines: vec!["100 c f", "1 kg lb", "3 kg cm", "254 cm in"],
+            next: 0,
+            answers: answers.clone(),
+        };
+        let output = OutputOptions {
+            rounding: Rounding::HalfEven,
+            fail_on_precision_loss: false,
+            unit_order: UnitOrder::Declaration,
+            color_temperature: false,
+            format: OutputFormat::Text,
+            csv_header: false,
+            precision: None,
+            sig_figs: None,
+            scientific: false,
+            fraction: None,
+            raw: false,
+            explain: false,
+            group_digits: false,
+            decimal_separator: DecimalSeparator::Dot,
+            unit_names: UnitNames::Short,
+            category: None,
+        };
+
+        // A BufWriter holds answers back until flushed
+        let mut out = std::io::BufWriter::new(answers.clone());
+        watch_stdin(std::io::BufReader::new(requests), &mut out, output)?;
+
+        let text = String::from_utf8(answers.0.borrow().clone())?;
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "100 °C = 212 °F");
+        assert!(lines[1].starts_with("1 kg = 2"));
+        assert!(lines[2].starts_with("error: "));
+        assert_eq!(lines[3], "254 cm = 100 in");
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_goes_on_after_bad_lines() -> Result<()> {
+        let input = "cm,inch,254\n\n# comment\n188,cm,furlongz\n100 c f\ncm,inch\n";
+        let output = OutputOptions {
+            rounding: Rounding::HalfEven,
+            fail_on_precision_loss: false,
+            unit_order: UnitOrder::Declaration,
+            color_temperature: false,
+            format: OutputFormat::Text,
+            csv_header: false,
+            precision: None,
+            sig_figs: None,
+            scientific: false,
+            fraction: None,
+            raw: false,
+            explain: false,
+            group_digits: false,
+            decimal_separator: DecimalSeparator::Dot,
+            unit_names: UnitNames::Short,
+            category: None,
+        };
+
+        let batch = |input: &str, output| -> Result<(BatchReport, Vec<String>)> {
+            let mut out = Vec::new();
+            let report = run_batch(
+                input.as_bytes(),
+                &mut out,
+                output,
+                None,
+                None,
+                &mut Warnings::new(),
+            )?;
+            let lines = String::from_utf8(out)?
+                .lines()
+                .map(str::to_string)
+                .collect();
+            Ok((report, lines))
+        };
+
+        let (report, lines) = batch(input, output)?;
+        assert_eq!((report.converted, report.failed), (1, 3));
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "254 cm = 100 in");
+        assert!(
+            lines[1].starts_with("error: line 4: Unrecognized unit: 'furlongz'"),
+            "{}",
+            lines[1]
+        );
+        // The first line chose comma-separated fields
+        assert!(
+            lines[2].starts_with("error: line 5: Expected 3"),
+            "{}",
+            lines[2]
+        );
+        assert!(lines[3].starts_with("error: line 6: Expected 3"));
+
+        let (report, lines) = batch("100 c f\n254 cm in\n", output)?;
+        assert_eq!((report.converted, report.failed), (2, 0));
+        assert_eq!(lines, ["100 °C = 212 °F", "254 cm = 100 in"]);
+
+        let json = OutputOptions {
+            format: OutputFormat::Json,
+            ..output
+        };
+        let (_, lines) = batch("cm,inch,254\nx,y,1\n", json)?;
+        assert!(lines[0].starts_with("{\"value\":254.0"));
+        assert!(lines[1].starts_with("{\"error\":\"line 2: "));
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_batch_answers_every_entry() -> Result<()> {
+        let input = r#"[
+            {"from": "cm", "to": "in", "value": 254},
+            {"from": "cm", "to": "in"},
+            {"from": "c", "to": "f", "value": 100},
+            {"from": "zz", "to": "f", "value": 1}
+        ]"#;
+        let mut reader = input.as_bytes();
+        assert!(starts_json_array(&mut reader)?);
+
+        let mut out = Vec::new();
+        let report = run_json_batch(reader, &mut out, None)?;
+        assert_eq!((report.converted, report.failed), (2, 2));
+
+        let answers: Vec<serde_json::Value> = serde_json::from_slice(&out)?;
+        assert_eq!(answers.len(), 4);
+        assert_eq!(answers[0]["result"], 100.0);
+        assert!(answers[0]["error"].is_null());
+        assert!(answers[1]["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("entry 2: missing field `value`"));
+        assert_eq!(answers[2]["result"], 212.0);
+        assert_eq!(answers[3]["error"]["code"], "invalid-unit");
+        assert!(answers[3]["result"].is_null());
+
+        assert!(!starts_json_array(&mut "  100 c f\n".as_bytes())?);
+        assert!(run_json_batch("[1, 2".as_bytes(), &mut Vec::new(), None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unit_column_converts_mixed_units() -> Result<()> {
+        let input = "2024-05-01,sensor-a,12.5,cm\n\
+                     2024-05-01,sensor-b,3,in\n\
+                     2024-05-02,sensor-c,1,furlongz\n\
+                     2024-05-02,sensor-d,0.5,m\n\
+                     2024-05-03,7,2,ft\n";
+        let output = OutputOptions {
+            rounding: Rounding::HalfEven,
+            fail_on_precision_loss: false,
+            unit_order: UnitOrder::Declaration,
+            color_temperature: false,
+            format: OutputFormat::Text,
+            csv_header: false,
+            precision: None,
+            sig_figs: None,
+            scientific: false,
+            fraction: None,
+            raw: false,
+            explain: false,
+            group_digits: false,
+            decimal_separator: DecimalSeparator::Dot,
+            unit_names: UnitNames::Short,
+            category: None,
+        };
+        let column = UnitColumn {
+            from: 3,
+            to: "mm".to_string(),
+        };
+
+        let mut out = Vec::new();
+        let report = run_batch(
+            input.as_bytes(),
+            &mut out,
+            output,
+            Some(&column),
+            None,
+            &mut Warnings::new(),
+        )?;
+        assert_eq!((report.converted, report.failed), (3, 2));
+        let answers = String::from_utf8(out)?;
+        let lines: Vec<&str> = answers.lines().collect();
+        assert_eq!(lines[0], "12.5 cm = 125 mm");
+        assert_eq!(lines[1], "3 in = 76.2 mm");
+        // A bad unit fails its own row only
+        assert!(
+            lines[2].starts_with("error: line 3: Unrecognized unit: 'furlongz'"),
+            "{}",
+            lines[2]
+        );
+        assert_eq!(lines[3], "0.5 m = 500 mm");
+        assert!(
+            lines[4].starts_with("error: line 5: Ambiguous row"),
+            "{}",
+            lines[4]
+        );
+
+        let fields = BatchSyntax::Csv.fields("12,cm");
+        assert!(column.request(&fields).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_errors_aborts_the_batch() -> Result<()> {
+        let input = "100 c kg\n1 kg g\n1 furlongs psi\n1 bogus g\n\n1 m cm\n";
+        let output = OutputOptions {
+            rounding: Rounding::HalfEven,
+            fail_on_precision_loss: false,
+            unit_order: UnitOrder::Declaration,
+            color_temperature: false,
+            format: OutputFormat::Text,
+            csv_header: false,
+            precision: None,
+            sig_figs: None,
+            scientific: false,
+            fraction: None,
+            raw: false,
+            explain: false,
+            group_digits: false,
+            decimal_separator: DecimalSeparator::Dot,
+            unit_names: UnitNames::Short,
+            category: None,
+        };
+
+        // Three bad lines with a limit of two: the run stops at the second one
+        let mut out = Vec::new();
+        let report = run_batch(
+            input.as_bytes(),
+            &mut out,
+            output,
+            None,
+            NonZeroUsize::new(2),
+            &mut Warnings::new(),
+        )?;
+        assert!(report.aborted);
+        assert_eq!((report.converted, report.failed, report.skipped), (1, 2, 2));
+        let answers = String::from_utf8(out)?;
+        assert_eq!(answers.lines().count(), 3);
+        assert!(answers
+            .lines()
+            .nth(2)
+            .unwrap()
+            .starts_with("error: line 3:"));
+
+        // The same input without a limit runs to the end
+        let report = run_batch(
+            input.as_bytes(),
+            &mut Vec::new(),
+            output,
+            None,
+            None,
+            &mut Warnings::new(),
+        )?;
+        assert!(!report.aborted);
+        assert_eq!((report.converted, report.failed, report.skipped), (2, 3, 0));
+
+        let json = r#"[{"from": "c", "to": "kg", "value": 1},
+                       {"from": "kg", "to": "g", "value": 1},
+                       {"from": "furlongs", "to": "psi", "value": 1},
+                       {"from": "bogus", "to": "g", "value": 1}]"#;
+        let mut out = Vec::new();
+        let report = run_json_batch(json.as_bytes(), &mut out, NonZeroUsize::new(2))?;
+        assert!(report.aborted);
+        assert_eq!((report.converted, report.failed, report.skipped), (1, 2, 1));
+        let answers: Vec<serde_json::Value> = serde_json::from_slice(&out)?;
+        assert_eq!(answers.len(), 3);
+        Ok(())
+    }
+}
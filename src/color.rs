@@ -0,0 +1,110 @@
+use clap::ValueEnum;
+use colored::{Color, Colorize};
+use serde::Deserialize;
+
+/// Colors for `--color-temp`, coldest first: each temperature gets the color
+/// of the first row whose upper bound (°C, exclusive) lies above it
+const TEMPERATURE_COLORS: &[(f64, Color)] = &[
+    (-20.0, Color::BrightCyan),
+    (0.0, Color::Blue),
+    (25.0, Color::Green),
+    (30.0, Color::Yellow),
+    (f64::INFINITY, Color::Red),
+];
+
+/// The color for a temperature given in degrees Celsius
+pub fn temperature_color(celsius: f64) -> Color {
+    TEMPERATURE_COLORS
+        .iter()
+        .find(|(upper_bound, _)| celsius < *upper_bound)
+        .map_or(Color::Red, |(_, color)| *color)
+}
+
+/// `--color`: when colored output is allowed
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorChoice {
+    /// Only on a terminal, and not when `NO_COLOR` is set
+    Auto,
+    /// Always, even when piped or when `NO_COLOR` is set
+    Always,
+    /// Never
+    Never,
+}
+
+/// Whether the `NO_COLOR` convention (<https://no-color.org>) asks for plain
+/// output: the variable is set, to any value
+pub fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some()
+}
+
+/// Whether `--color-temp` output is actually colored. `--color always`
+/// colors even piped output and overrides `NO_COLOR`; `auto` colors only a
+/// terminal and yields to `NO_COLOR`, so piped output stays plain text.
+pub fn color_enabled(
+    color_temp: bool,
+    choice: ColorChoice,
+    no_color_env: bool,
+    stdout_is_terminal: bool,
+) -> bool {
+    color_temp
+        && match choice {
+            ColorChoice::Always => true,
+            ColorChoice::Auto => stdout_is_terminal && !no_color_env,
+            ColorChoice::Never => false,
+        }
+}
+
+/// `text` in the color for `celsius`, or unchanged when color is disabled
+pub fn paint_temperature(text: &str, celsius: f64, enabled: bool) -> String {
+    if !enabled {
+        return text.to_string();
+    }
+
+    text.color(temperature_color(celsius)).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threshold_table() {
+        assert_eq!(temperature_color(-40.0), Color::BrightCyan);
+        assert_eq!(temperature_color(-0.5), Color::Blue);
+        assert_eq!(temperature_color(0.0), Color::Green);
+        assert_eq!(temperature_color(24.9), Color::Green);
+        assert_eq!(temperature_color(27.0), Color::Yellow);
+        assert_eq!(temperature_color(30.0), Color::Red);
+        assert_eq!(temperature_color(1000.0), Color::Red);
+    }
+
+    #[test]
+    fn test_disabled_color_leaves_value_intact() {
+        assert_eq!(paint_temperature("77 °F", 25.0, false), "77 °F");
+        assert!(!color_enabled(false, ColorChoice::Always, false, true));
+        assert!(!color_enabled(true, ColorChoice::Never, false, true));
+    }
+
+    #[test]
+    fn test_no_color_precedence() {
+        // NO_COLOR beats auto, but an explicit --color always beats NO_COLOR
+        assert!(!color_enabled(true, ColorChoice::Auto, true, true));
+        assert!(color_enabled(true, ColorChoice::Always, true, true));
+        assert!(!color_enabled(true, ColorChoice::Never, true, true));
+    }
+
+    #[test]
+    fn test_color_only_on_terminals() {
+        assert!(color_enabled(true, ColorChoice::Auto, false, true));
+        assert!(!color_enabled(true, ColorChoice::Auto, false, false));
+        assert!(color_enabled(true, ColorChoice::Always, false, false));
+
+        // Mirrors what main does once color is enabled
+        colored::control::set_override(true);
+        let painted = paint_temperature("-4 °F", -20.0, true);
+        colored::control::unset_override();
+        assert!(painted.starts_with("\x1b["));
+        assert!(painted.contains("-4 °F"));
+    }
+}
@@ -0,0 +1,110 @@
+use crate::color::ColorChoice;
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use uniconv::format::Rounding;
+use uniconv::jobs::MAX_PRECISION;
+use uniconv::output::OutputFormat;
+
+/// Names a config file to use instead of the default one
+pub const CONFIG_ENV: &str = "UNICONV_CONFIG";
+
+/// Defaults for the global flags, read from `~/.config/uniconv/config.toml`:
+///
+/// ```toml
+/// precision = 2
+/// format = "json"
+/// rounding = "half-up"
+/// color = "never"
+/// ```
+///
+/// A flag given on the command line wins over the file, which wins over the
+/// built-in default.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub precision: Option<u32>,
+    pub format: Option<OutputFormat>,
+    pub rounding: Option<Rounding>,
+    pub color: Option<ColorChoice>,
+}
+
+impl Config {
+    /// Reads the config file: `path` (from `--config`), else the file named
+    /// by `UNICONV_CONFIG`, else the default location. Only the default may
+    /// be missing, which leaves every setting at its built-in value.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let explicit = path
+            .map(Path::to_path_buf)
+            .or_else(|| std::env::var_os(CONFIG_ENV).map(PathBuf::from));
+        let (path, required) = match explicit {
+            Some(path) => (path, true),
+            None => match default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Config::default()),
+            },
+        };
+
+        if !required && !path.exists() {
+            return Ok(Config::default());
+        }
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file '{}'", path.display()))?;
+        Self::parse(&contents).with_context(|| format!("Invalid config file '{}'", path.display()))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self> {
+        let config: Config = toml::from_str(contents)?;
+        if let Some(precision) = config.precision {
+            if precision > MAX_PRECISION {
+                return Err(anyhow!(
+                    "precision is {}; at most {} decimals are supported.",
+                    precision,
+                    MAX_PRECISION
+                ));
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// `$XDG_CONFIG_HOME/uniconv/config.toml`, falling back to
+/// `~/.config/uniconv/config.toml`
+fn default_path() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_home.join("uniconv").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() -> Result<()> {
+        let config = Config::parse(
+            "precision = 2\nformat = \"json\"\nrounding = \"half-up\"\ncolor = \"never\"\n",
+        )?;
+        assert_eq!(
+            config,
+            Config {
+                precision: Some(2),
+                format: Some(OutputFormat::Json),
+                rounding: Some(Rounding::HalfUp),
+                color: Some(ColorChoice::Never),
+            }
+        );
+
+        assert_eq!(Config::parse("")?, Config::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_configs() {
+        assert!(Config::parse("precision = 40\n").is_err());
+        assert!(Config::parse("format = \"yaml\"\n").is_err());
+        assert!(Config::parse("colour = \"never\"\n").is_err());
+    }
+}
@@ -0,0 +1,250 @@
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use std::f64::consts::PI;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::sync::OnceLock;
+
+/// Largest angle magnitude accepted before the input is considered unrealistic
+const MAX_ANGLE_RESULT: f64 = 1e18;
+
+/// Arcseconds in one degree; every unit but the radian is a whole number of
+/// arcseconds, which keeps turns, degrees and gradians exact
+const ARCSECONDS_PER_DEGREE: f64 = 3600.0;
+
+/// `arcseconds_per_unit` for every `Angle`, indexed by variant
+static FACTOR_TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+
+/// Plane angle units. Defined here rather than next to the other unit enums
+/// because `Angle::Degree` would read like the temperature `Degree`: it is
+/// always written qualified and displayed as `deg`, never `°`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Angle {
+    Degree,
+    Radian,
+    Gradian,
+    Turn,
+    Arcminute,
+    Arcsecond,
+}
+
+impl Display for Angle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Angle::Degree => write!(f, "deg"),
+            Angle::Radian => write!(f, "rad"),
+            Angle::Gradian => write!(f, "grad"),
+            Angle::Turn => write!(f, "turn"),
+            Angle::Arcminute => write!(f, "arcmin"),
+            Angle::Arcsecond => write!(f, "arcsec"),
+        }
+    }
+}
+
+pub struct AngleConverter {
+    pub value: f64,
+    pub unit: Angle,
+}
+
+pub struct AngleResults {
+    pub degree: f64,
+    pub radian: f64,
+    pub gradian: f64,
+    pub turn: f64,
+    pub arcminute: f64,
+    pub arcsecond: f64,
+}
+
+impl AngleConverter {
+    pub fn new(value: f64, unit: Angle) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn convert_to(&self, target_unit: Angle) -> Result<f64> {
+        let from = Self::arcseconds_per_unit(&self.unit);
+        let to = Self::arcseconds_per_unit(&target_unit);
+        self.scale(from, to, &target_unit)
+    }
+
+    /// Builds the factor table used by `convert_to_all` ahead of time
+    pub fn warm_up() {
+        Self::factor_table();
+    }
+
+    fn factor_table() -> &'static [f64] {
+        FACTOR_TABLE.get_or_init(|| {
+            Angle::value_variants()
+                .iter()
+                .map(Self::arcseconds_per_unit)
+                .collect()
+        })
+    }
+
+    fn scale(&self, from: f64, to: f64, target_unit: &Angle) -> Result<f64> {
+        // Always scale by a ratio of at least 1 so exact relationships stay
+        // exact (1 turn is 360 deg)
+        let result = if from >= to {
+            self.value * (from / to)
+        } else {
+            self.value / (to / from)
+        };
+
+        self.check_conversion_result(result, &format!("{:?} to {:?}", self.unit, target_unit))
+    }
+
+    /// Size of one unit in arcseconds. A radian is 180/π degrees.
+    fn arcseconds_per_unit(unit: &Angle) -> f64 {
+        match unit {
+            Angle::Degree => ARCSECONDS_PER_DEGREE,
+            Angle::Radian => 180.0 * ARCSECONDS_PER_DEGREE / PI,
+            Angle::Gradian => 0.9 * ARCSECONDS_PER_DEGREE,
+            Angle::Turn => 360.0 * ARCSECONDS_PER_DEGREE,
+            Angle::Arcminute => 60.0,
+            Angle::Arcsecond => 1.0,
+        }
+    }
+
+    /// Angles may be negative (clockwise), so only NaN, infinity and
+    /// runaway magnitudes are rejected
+    fn check_conversion_result(&self, result: f64, conversion_type: &str) -> Result<f64> {
+        if result.is_nan() {
+            return Err(anyhow!(
+                "{} conversion resulted in NaN. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result.is_infinite() {
+            return Err(anyhow!(
+                "{} conversion resulted in infinity. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result.abs() > MAX_ANGLE_RESULT {
+            return Err(anyhow!(
+                "{} conversion resulted in an unrealistically large angle: {:.2}. Please check your input.",
+                conversion_type, result
+            ));
+        }
+
+        Ok(result)
+    }
+
+    /// Same results as calling `convert_to` for every unit, with the factors
+    /// taken from a table built once
+    pub fn convert_to_all(&self) -> Result<AngleResults> {
+        let factors = Self::factor_table();
+        let from = factors[self.unit as usize];
+        let convert = |target: Angle| self.scale(from, factors[target as usize], &target);
+
+        Ok(AngleResults {
+            degree: convert(Angle::Degree)?,
+            radian: convert(Angle::Radian)?,
+            gradian: convert(Angle::Gradian)?,
+            turn: convert(Angle::Turn)?,
+            arcminute: convert(Angle::Arcminute)?,
+            arcsecond: convert(Angle::Arcsecond)?,
+        })
+    }
+}
+
+impl AngleResults {
+    /// Every unit with its value, in the order `convert_to_all` fills them
+    pub fn rows(&self) -> Vec<(Angle, f64)> {
+        vec![
+            (Angle::Degree, self.degree),
+            (Angle::Radian, self.radian),
+            (Angle::Gradian, self.gradian),
+            (Angle::Turn, self.turn),
+            (Angle::Arcminute, self.arcminute),
+            (Angle::Arcsecond, self.arcsecond),
+        ]
+    }
+}
+
+impl Display for AngleResults {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "Degree: {:.6} deg, Radian: {:.6} rad, Gradian: {:.6} grad, Turn: {:.6} turn, Arcminute: {:.6} arcmin, Arcsecond: {:.6} arcsec",
+            self.degree, self.radian, self.gradian, self.turn, self.arcminute, self.arcsecond
+        )
+    }
+}
+
+// Helper functions with error handling
+pub fn deg_to_rad(value: f64) -> Result<f64> {
+    AngleConverter::new(value, Angle::Degree).convert_to(Angle::Radian)
+}
+
+pub fn rad_to_deg(value: f64) -> Result<f64> {
+    AngleConverter::new(value, Angle::Radian).convert_to(Angle::Degree)
+}
+
+pub fn turn_to_deg(value: f64) -> Result<f64> {
+    AngleConverter::new(value, Angle::Turn).convert_to(Angle::Degree)
+}
+
+pub fn deg_to_grad(value: f64) -> Result<f64> {
+    AngleConverter::new(value, Angle::Degree).convert_to(Angle::Gradian)
+}
+
+pub fn deg_to_arcmin(value: f64) -> Result<f64> {
+    AngleConverter::new(value, Angle::Degree).convert_to(Angle::Arcminute)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_degrees_and_radians() -> Result<()> {
+        assert!((deg_to_rad(180.0)? - PI).abs() < 1e-15);
+        assert!((rad_to_deg(PI)? - 180.0).abs() < 1e-12);
+        assert!((deg_to_rad(-90.0)? + PI / 2.0).abs() < 1e-15);
+        Ok(())
+    }
+
+    #[test]
+    fn test_exact_conversions() -> Result<()> {
+        assert_eq!(turn_to_deg(1.0)?, 360.0);
+        assert_eq!(deg_to_grad(90.0)?, 100.0);
+        assert_eq!(deg_to_arcmin(1.5)?, 90.0);
+        let converter = AngleConverter::new(1.0, Angle::Turn);
+        assert_eq!(converter.convert_to(Angle::Gradian)?, 400.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_all() -> Result<()> {
+        AngleConverter::warm_up();
+        let converter = AngleConverter::new(0.5, Angle::Turn);
+        let all = converter.convert_to_all()?;
+
+        assert_eq!(all.degree, 180.0);
+        assert_eq!(
+            all.radian.to_bits(),
+            converter.convert_to(Angle::Radian)?.to_bits()
+        );
+        assert!(all.to_string().contains("Degree: 180.000000 deg"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_conversions() -> Result<()> {
+        let converter = AngleConverter::new(f64::NAN, Angle::Degree);
+        assert!(converter.convert_to(Angle::Radian).is_err());
+
+        let converter = AngleConverter::new(f64::INFINITY, Angle::Degree);
+        assert!(converter.convert_to(Angle::Radian).is_err());
+
+        // Negative angles are valid, unlike negative lengths
+        let converter = AngleConverter::new(-1.0, Angle::Turn);
+        assert_eq!(converter.convert_to(Angle::Degree)?, -360.0);
+        Ok(())
+    }
+}
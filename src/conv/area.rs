@@ -0,0 +1,251 @@
+use super::Area;
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use std::fmt::{Display, Result as FmtResult};
+use std::sync::OnceLock;
+
+/// Largest area result accepted before the input is considered unrealistic
+const MAX_AREA_RESULT: f64 = 1e18;
+
+/// Side lengths in micrometers. Every area below is the square of one of
+/// these (times a whole number for the acre), and micrometers keep all of
+/// those squares whole numbers that `f64` holds exactly.
+const MICROMETERS_PER_METER: f64 = 1e6;
+const MICROMETERS_PER_FOOT: f64 = 304_800.0;
+/// One acre is 43,560 square feet (a furlong by a chain)
+const SQUARE_FEET_PER_ACRE: f64 = 43_560.0;
+
+/// `square_micrometers_per_unit` for every `Area`, indexed by variant
+static FACTOR_TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+
+pub struct AreaConverter {
+    pub value: f64,
+    pub unit: Area,
+}
+
+pub struct AreaResults {
+    pub square_meter: f64,
+    pub square_kilometer: f64,
+    pub square_centimeter: f64,
+    pub hectare: f64,
+    pub acre: f64,
+    pub square_foot: f64,
+    pub square_mile: f64,
+}
+
+impl AreaConverter {
+    pub fn new(value: f64, unit: Area) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn convert_to(&self, target_unit: Area) -> Result<f64> {
+        let from = Self::square_micrometers_per_unit(&self.unit);
+        let to = Self::square_micrometers_per_unit(&target_unit);
+        self.scale(from, to, &target_unit)
+    }
+
+    /// Builds the factor table used by `convert_to_all` ahead of time
+    pub fn warm_up() {
+        Self::factor_table();
+    }
+
+    fn factor_table() -> &'static [f64] {
+        FACTOR_TABLE.get_or_init(|| {
+            Area::value_variants()
+                .iter()
+                .map(Self::square_micrometers_per_unit)
+                .collect()
+        })
+    }
+
+    fn scale(&self, from: f64, to: f64, target_unit: &Area) -> Result<f64> {
+        // Always scale by a ratio of at least 1 so exact relationships stay
+        // exact (1 ha is 10,000 m², 1 mi² is 640 ac)
+        let result = if from >= to {
+            self.value * (from / to)
+        } else {
+            self.value / (to / from)
+        };
+
+        self.check_conversion_result(result, &format!("{:?} to {:?}", self.unit, target_unit))
+    }
+
+    /// Size of one unit in square micrometers, derived from side lengths
+    fn square_micrometers_per_unit(unit: &Area) -> f64 {
+        let square = |side: f64| side * side;
+        match unit {
+            Area::SquareMeter => square(MICROMETERS_PER_METER),
+            Area::SquareKilometer => square(1000.0 * MICROMETERS_PER_METER),
+            Area::SquareCentimeter => square(MICROMETERS_PER_METER / 100.0),
+            Area::Hectare => square(100.0 * MICROMETERS_PER_METER),
+            Area::Acre => SQUARE_FEET_PER_ACRE * square(MICROMETERS_PER_FOOT),
+            Area::SquareFoot => square(MICROMETERS_PER_FOOT),
+            Area::SquareMile => square(5280.0 * MICROMETERS_PER_FOOT),
+        }
+    }
+
+    fn check_conversion_result(&self, result: f64, conversion_type: &str) -> Result<f64> {
+        if result.is_nan() {
+            return Err(anyhow!(
+                "{} conversion resulted in NaN. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result.is_infinite() {
+            return Err(anyhow!(
+                "{} conversion resulted in infinity. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result < 0.0 {
+            return Err(anyhow!(
+                "{} conversion resulted in negative area: {:.6}. This should not happen with positive input.",
+                conversion_type, result
+            ));
+        }
+
+        if result > MAX_AREA_RESULT {
+            return Err(anyhow!(
+                "{} conversion resulted in an unrealistically large area: {:.2}. Please check your input.",
+                conversion_type, result
+            ));
+        }
+
+        Ok(result)
+    }
+
+    /// Same results as calling `convert_to` for every unit, with the factors
+    /// taken from a table built once
+    pub fn convert_to_all(&self) -> Result<AreaResults> {
+        let factors = Self::factor_table();
+        let from = factors[self.unit as usize];
+        let convert = |target: Area| self.scale(from, factors[target as usize], &target);
+
+        Ok(AreaResults {
+            square_meter: convert(Area::SquareMeter)?,
+            square_kilometer: convert(Area::SquareKilometer)?,
+            square_centimeter: convert(Area::SquareCentimeter)?,
+            hectare: convert(Area::Hectare)?,
+            acre: convert(Area::Acre)?,
+            square_foot: convert(Area::SquareFoot)?,
+            square_mile: convert(Area::SquareMile)?,
+        })
+    }
+}
+
+impl AreaResults {
+    /// Every unit with its value, in the order `convert_to_all` fills them
+    pub fn rows(&self) -> Vec<(Area, f64)> {
+        vec![
+            (Area::SquareMeter, self.square_meter),
+            (Area::SquareKilometer, self.square_kilometer),
+            (Area::SquareCentimeter, self.square_centimeter),
+            (Area::Hectare, self.hectare),
+            (Area::Acre, self.acre),
+            (Area::SquareFoot, self.square_foot),
+            (Area::SquareMile, self.square_mile),
+        ]
+    }
+}
+
+impl Display for AreaResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "Square Meter: {:.6} m², Square Kilometer: {:.6} km², Square Centimeter: {:.6} cm², Hectare: {:.6} ha, Acre: {:.6} ac, Square Foot: {:.6} ft², Square Mile: {:.6} mi²",
+            self.square_meter, self.square_kilometer, self.square_centimeter, self.hectare, self.acre, self.square_foot, self.square_mile
+        )
+    }
+}
+
+// Helper functions with error handling
+pub fn ha_to_m2(value: f64) -> Result<f64> {
+    AreaConverter::new(value, Area::Hectare).convert_to(Area::SquareMeter)
+}
+
+pub fn acre_to_m2(value: f64) -> Result<f64> {
+    AreaConverter::new(value, Area::Acre).convert_to(Area::SquareMeter)
+}
+
+pub fn acre_to_ha(value: f64) -> Result<f64> {
+    AreaConverter::new(value, Area::Acre).convert_to(Area::Hectare)
+}
+
+pub fn mi2_to_acre(value: f64) -> Result<f64> {
+    AreaConverter::new(value, Area::SquareMile).convert_to(Area::Acre)
+}
+
+pub fn km2_to_ha(value: f64) -> Result<f64> {
+    AreaConverter::new(value, Area::SquareKilometer).convert_to(Area::Hectare)
+}
+
+pub fn m2_to_cm2(value: f64) -> Result<f64> {
+    AreaConverter::new(value, Area::SquareMeter).convert_to(Area::SquareCentimeter)
+}
+
+pub fn m2_to_ft2(value: f64) -> Result<f64> {
+    AreaConverter::new(value, Area::SquareMeter).convert_to(Area::SquareFoot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_conversions() -> Result<()> {
+        assert_eq!(ha_to_m2(1.0)?, 10_000.0);
+        assert_eq!(km2_to_ha(1.0)?, 100.0);
+        assert_eq!(m2_to_cm2(1.0)?, 10_000.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_imperial_conversions() -> Result<()> {
+        assert_eq!(acre_to_m2(1.0)?, 4046.8564224);
+        assert_eq!(mi2_to_acre(1.0)?, 640.0);
+        assert!((acre_to_ha(1.0)? - 0.40468564224).abs() < 1e-15);
+        assert!((m2_to_ft2(1.0)? - 10.7639).abs() < 0.0001);
+        Ok(())
+    }
+
+    #[test]
+    fn test_factors_are_squares_of_lengths() -> Result<()> {
+        // 1 m² is 100 cm × 100 cm, 1 ft² is 12 in × 12 in = 929.0304 cm²
+        let converter = AreaConverter::new(1.0, Area::SquareFoot);
+        assert_eq!(converter.convert_to(Area::SquareCentimeter)?, 929.0304);
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_all() -> Result<()> {
+        AreaConverter::warm_up();
+        let converter = AreaConverter::new(1.0, Area::Hectare);
+        let all = converter.convert_to_all()?;
+
+        assert_eq!(all.square_meter, 10_000.0);
+        assert_eq!(
+            all.acre.to_bits(),
+            converter.convert_to(Area::Acre)?.to_bits()
+        );
+        assert!(all.to_string().contains("Hectare: 1.000000 ha"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_conversions() {
+        let converter = AreaConverter::new(f64::NAN, Area::SquareMeter);
+        assert!(converter.convert_to(Area::Hectare).is_err());
+
+        let converter = AreaConverter::new(f64::INFINITY, Area::SquareMeter);
+        assert!(converter.convert_to(Area::Hectare).is_err());
+
+        let converter = AreaConverter::new(-1.0, Area::SquareMeter);
+        assert!(converter.convert_to(Area::Hectare).is_err());
+    }
+}
@@ -0,0 +1,243 @@
+use super::AspectTarget;
+use anyhow::{anyhow, Result};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// A media quantity as written on the command line (`1920x1080`, `16:9`,
+/// `1920px`, `20in`) or produced by a conversion
+#[derive(Debug, Clone, PartialEq)]
+pub enum MediaValue {
+    Resolution {
+        width: u64,
+        height: u64,
+    },
+    Ratio {
+        width: u64,
+        height: u64,
+    },
+    Pixels(f64),
+    Inches(f64),
+    /// Physical width and height in inches
+    Size {
+        width: f64,
+        height: f64,
+    },
+}
+
+impl MediaValue {
+    pub fn parse(input: &str) -> Result<Self> {
+        let cleaned = input.trim().to_lowercase();
+
+        if let Some(pixels) = cleaned.strip_suffix("px") {
+            return Ok(MediaValue::Pixels(parse_length(pixels, input)?));
+        }
+        if let Some(inches) = cleaned
+            .strip_suffix("inch")
+            .or_else(|| cleaned.strip_suffix("in"))
+        {
+            return Ok(MediaValue::Inches(parse_length(inches, input)?));
+        }
+        if let Some((width, height)) = cleaned.split_once(['x', '×']) {
+            let (width, height) = parse_dimensions(width, height, input)?;
+            return Ok(MediaValue::Resolution { width, height });
+        }
+        if let Some((width, height)) = cleaned.split_once(':') {
+            let (width, height) = parse_dimensions(width, height, input)?;
+            return Ok(MediaValue::Ratio { width, height });
+        }
+
+        Err(anyhow!(
+            "Cannot read '{}'. Use a resolution (1920x1080), a ratio (16:9), pixels (1920px) or inches (20in).",
+            input.trim()
+        ))
+    }
+
+    /// The value without its unit: `1920` for `1920 px`, `16:9` for a ratio
+    pub fn magnitude(&self) -> String {
+        match self {
+            MediaValue::Pixels(pixels) => round_for_display(*pixels).to_string(),
+            MediaValue::Inches(inches) => round_for_display(*inches).to_string(),
+            MediaValue::Size { width, height } => format!(
+                "{} x {}",
+                round_for_display(*width),
+                round_for_display(*height)
+            ),
+            MediaValue::Resolution { .. } | MediaValue::Ratio { .. } => self.to_string(),
+        }
+    }
+}
+
+impl Display for MediaValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            MediaValue::Resolution { width, height } => write!(f, "{}x{}", width, height),
+            MediaValue::Ratio { width, height } => write!(f, "{}:{}", width, height),
+            MediaValue::Pixels(pixels) => write!(f, "{} px", round_for_display(*pixels)),
+            MediaValue::Inches(inches) => write!(f, "{} in", round_for_display(*inches)),
+            MediaValue::Size { width, height } => write!(
+                f,
+                "{} x {} in",
+                round_for_display(*width),
+                round_for_display(*height)
+            ),
+        }
+    }
+}
+
+/// Converts resolutions to reduced aspect ratios, and pixels to physical
+/// inches (and back) at a given dots-per-inch
+pub struct AspectConverter {
+    pub value: MediaValue,
+    pub dpi: Option<f64>,
+}
+
+impl AspectConverter {
+    pub fn new(value: MediaValue, dpi: Option<f64>) -> Self {
+        Self { value, dpi }
+    }
+
+    pub fn convert_to(&self, target: AspectTarget) -> Result<MediaValue> {
+        let result = match (&self.value, &target) {
+            (
+                MediaValue::Resolution { width, height } | MediaValue::Ratio { width, height },
+                AspectTarget::Ratio,
+            ) => reduce_ratio(*width, *height),
+            (MediaValue::Resolution { width, height }, AspectTarget::Inch) => {
+                let dpi = self.require_dpi()?;
+                MediaValue::Size {
+                    width: *width as f64 / dpi,
+                    height: *height as f64 / dpi,
+                }
+            }
+            (MediaValue::Pixels(pixels), AspectTarget::Inch) => {
+                MediaValue::Inches(pixels / self.require_dpi()?)
+            }
+            (MediaValue::Pixels(pixels), AspectTarget::Px) => MediaValue::Pixels(*pixels),
+            (MediaValue::Inches(inches), AspectTarget::Px) => {
+                MediaValue::Pixels(inches * self.require_dpi()?)
+            }
+            (MediaValue::Inches(inches), AspectTarget::Inch) => MediaValue::Inches(*inches),
+            (value, target) => {
+                return Err(anyhow!("Cannot convert {} to {}.", value, target));
+            }
+        };
+
+        Ok(result)
+    }
+
+    fn require_dpi(&self) -> Result<f64> {
+        match self.dpi {
+            Some(dpi) if dpi.is_finite() && dpi > 0.0 => Ok(dpi),
+            Some(dpi) => Err(anyhow!("DPI must be a positive number, got {}.", dpi)),
+            None => Err(anyhow!(
+                "Converting between pixels and inches needs a density, e.g. --dpi 96."
+            )),
+        }
+    }
+}
+
+fn parse_dimensions(width: &str, height: &str, input: &str) -> Result<(u64, u64)> {
+    let parse = |part: &str| part.trim().parse::<u64>().ok().filter(|&n| n > 0);
+    match (parse(width), parse(height)) {
+        (Some(width), Some(height)) => Ok((width, height)),
+        _ => Err(anyhow!(
+            "'{}' needs two positive whole numbers, e.g. 1920x1080 or 16:9.",
+            input.trim()
+        )),
+    }
+}
+
+fn parse_length(number: &str, input: &str) -> Result<f64> {
+    match number.trim().parse::<f64>() {
+        Ok(value) if value.is_finite() && value >= 0.0 => Ok(value),
+        _ => Err(anyhow!(
+            "'{}' needs a non-negative number, e.g. 1920px or 20in.",
+            input.trim()
+        )),
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn reduce_ratio(width: u64, height: u64) -> MediaValue {
+    let divisor = gcd(width, height);
+    MediaValue::Ratio {
+        width: width / divisor,
+        height: height / divisor,
+    }
+}
+
+/// Three decimals are plenty for pixel counts and screen sizes
+fn round_for_display(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+// Helper functions with error handling
+pub fn resolution_to_ratio(resolution: &str) -> Result<MediaValue> {
+    AspectConverter::new(MediaValue::parse(resolution)?, None).convert_to(AspectTarget::Ratio)
+}
+
+pub fn px_to_inch(pixels: f64, dpi: f64) -> Result<f64> {
+    match AspectConverter::new(MediaValue::Pixels(pixels), Some(dpi))
+        .convert_to(AspectTarget::Inch)?
+    {
+        MediaValue::Inches(inches) => Ok(inches),
+        other => Err(anyhow!("Unexpected conversion result: {}", other)),
+    }
+}
+
+pub fn inch_to_px(inches: f64, dpi: f64) -> Result<f64> {
+    match AspectConverter::new(MediaValue::Inches(inches), Some(dpi))
+        .convert_to(AspectTarget::Px)?
+    {
+        MediaValue::Pixels(pixels) => Ok(pixels),
+        other => Err(anyhow!("Unexpected conversion result: {}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolution_to_ratio() -> Result<()> {
+        assert_eq!(resolution_to_ratio("1920x1080")?.to_string(), "16:9");
+        assert_eq!(resolution_to_ratio("1280x1024")?.to_string(), "5:4");
+        assert_eq!(resolution_to_ratio("2560×1080")?.to_string(), "64:27");
+        assert_eq!(resolution_to_ratio("32:18")?.to_string(), "16:9");
+        Ok(())
+    }
+
+    #[test]
+    fn test_dpi_conversions() -> Result<()> {
+        assert_eq!(px_to_inch(1920.0, 96.0)?, 20.0);
+        assert_eq!(inch_to_px(8.5, 300.0)?, 2550.0);
+
+        let size = AspectConverter::new(MediaValue::parse("1920x1080")?, Some(96.0))
+            .convert_to(AspectTarget::Inch)?;
+        assert_eq!(size.to_string(), "20 x 11.25 in");
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_aspect_input() {
+        assert!(MediaValue::parse("1920x0").is_err());
+        assert!(MediaValue::parse("wide").is_err());
+        assert_eq!(
+            MediaValue::parse("1920px").unwrap(),
+            MediaValue::Pixels(1920.0)
+        );
+        assert!(px_to_inch(100.0, 0.0).is_err());
+        assert!(AspectConverter::new(MediaValue::Pixels(100.0), None)
+            .convert_to(AspectTarget::Inch)
+            .is_err());
+        assert!(AspectConverter::new(MediaValue::Pixels(100.0), None)
+            .convert_to(AspectTarget::Ratio)
+            .is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use super::Unit;
+use crate::convert::convert_recognized;
+use crate::errors::Warnings;
+use anyhow::{anyhow, Result};
+
+/// A single conversion, set up one piece at a time:
+///
+/// ```
+/// use uniconv::{Conversion, Degree};
+///
+/// let fahrenheit = Conversion::builder()
+///     .value(25.0)
+///     .from(Degree::Celsius)
+///     .to(Degree::Fahrenheit)
+///     .run()?;
+/// assert_eq!(fahrenheit, 77.0);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+///
+/// The units pick the converter, and both must be of the same category, so
+/// mixing them doesn't compile:
+///
+/// ```compile_fail
+/// use uniconv::{Conversion, Degree, Length};
+///
+/// Conversion::builder().value(1.0).from(Degree::Celsius).to(Length::Meter);
+/// ```
+pub struct Conversion;
+
+impl Conversion {
+    pub fn builder<U: Unit>() -> ConversionBuilder<U> {
+        ConversionBuilder {
+            value: None,
+            from: None,
+            to: None,
+        }
+    }
+}
+
+/// Collects the value and units of a [`Conversion`]
+#[derive(Debug, Clone)]
+pub struct ConversionBuilder<U> {
+    value: Option<f64>,
+    from: Option<U>,
+    to: Option<U>,
+}
+
+impl<U: Unit> ConversionBuilder<U> {
+    pub fn value(mut self, value: f64) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    pub fn from(mut self, unit: U) -> Self {
+        self.from = Some(unit);
+        self
+    }
+
+    pub fn to(mut self, unit: U) -> Self {
+        self.to = Some(unit);
+        self
+    }
+
+    /// Converts with the same input checks as `convert` (finite values,
+    /// absolute zero, no negative masses, ...), after checking nothing was
+    /// left out
+    pub fn run(self) -> Result<f64> {
+        let missing = |part: &str| anyhow!("The conversion has no {0}; call .{0}(...)", part);
+        let value = self.value.ok_or_else(|| missing("value"))?;
+        let from = self.from.ok_or_else(|| missing("from"))?;
+        let to = self.to.ok_or_else(|| missing("to"))?;
+        convert_recognized(value, &from.into(), &to.into(), &mut Warnings::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conv::{Degree, Length, Mass};
+    use crate::errors::ValidationError;
+
+    #[test]
+    fn test_builder_runs_the_category_converter() -> Result<()> {
+        let fahrenheit = Conversion::builder()
+            .value(100.0)
+            .from(Degree::Celsius)
+            .to(Degree::Fahrenheit)
+            .run()?;
+        assert_eq!(fahrenheit, 212.0);
+
+        // The order of the calls doesn't matter
+        let meters = Conversion::builder()
+            .to(Length::Meter)
+            .from(Length::Kilometer)
+            .value(1.5)
+            .run()?;
+        assert_eq!(meters, 1500.0);
+
+        let grams = Conversion::builder()
+            .value(2.0)
+            .from(Mass::Kilogram)
+            .to(Mass::Gram)
+            .run()?;
+        assert_eq!(grams, 2000.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        let error = Conversion::builder()
+            .from(Degree::Celsius)
+            .to(Degree::Kelvin)
+            .run()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The conversion has no value; call .value(...)"
+        );
+
+        let error = Conversion::builder()
+            .value(1.0)
+            .from(Length::Meter)
+            .run()
+            .unwrap_err();
+        assert!(error.to_string().contains("no to"));
+
+        // The converter's own checks still apply
+        assert!(Conversion::builder()
+            .value(20.0)
+            .from(Degree::Celsius)
+            .to(Degree::GasMark)
+            .run()
+            .is_err());
+    }
+
+    #[test]
+    fn test_builder_validates_the_input() {
+        let error = Conversion::builder()
+            .value(-500.0)
+            .from(Degree::Celsius)
+            .to(Degree::Kelvin)
+            .run()
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<ValidationError>(),
+            Some(ValidationError::PhysicallyInvalid(_))
+        ));
+
+        let error = Conversion::builder()
+            .value(f64::NAN)
+            .from(Length::Meter)
+            .to(Length::Foot)
+            .run()
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<ValidationError>(),
+            Some(ValidationError::InvalidNumber(_))
+        ));
+
+        let error = Conversion::builder()
+            .value(-1.0)
+            .from(Mass::Kilogram)
+            .to(Mass::Gram)
+            .run()
+            .unwrap_err();
+        assert!(error.to_string().contains("Mass cannot be negative"));
+    }
+}
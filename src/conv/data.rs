@@ -0,0 +1,251 @@
+use super::Data;
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use std::fmt::{Display, Result as FmtResult};
+use std::sync::OnceLock;
+
+/// Largest data-size result accepted before the input is considered
+/// unrealistic. Counted in bits, large drives already reach 1e14.
+const MAX_DATA_RESULT: f64 = 1e24;
+
+const BITS_PER_BYTE: f64 = 8.0;
+/// SI prefixes step by 1000, IEC binary prefixes by 1024
+const SI_STEP: f64 = 1000.0;
+const IEC_STEP: f64 = 1024.0;
+
+/// `bits_per_unit` for every `Data`, indexed by variant
+static FACTOR_TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+
+pub struct DataConverter {
+    pub value: f64,
+    pub unit: Data,
+}
+
+pub struct DataResults {
+    pub bit: f64,
+    pub byte: f64,
+    pub kilobyte: f64,
+    pub megabyte: f64,
+    pub gigabyte: f64,
+    pub terabyte: f64,
+    pub kibibyte: f64,
+    pub mebibyte: f64,
+    pub gibibyte: f64,
+    pub tebibyte: f64,
+}
+
+impl DataConverter {
+    pub fn new(value: f64, unit: Data) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn convert_to(&self, target_unit: Data) -> Result<f64> {
+        let from = Self::bits_per_unit(&self.unit);
+        let to = Self::bits_per_unit(&target_unit);
+        self.scale(from, to, &target_unit)
+    }
+
+    /// Builds the factor table used by `convert_to_all` ahead of time
+    pub fn warm_up() {
+        Self::factor_table();
+    }
+
+    fn factor_table() -> &'static [f64] {
+        FACTOR_TABLE.get_or_init(|| {
+            Data::value_variants()
+                .iter()
+                .map(Self::bits_per_unit)
+                .collect()
+        })
+    }
+
+    fn scale(&self, from: f64, to: f64, target_unit: &Data) -> Result<f64> {
+        // Always scale by a ratio of at least 1 so exact relationships stay
+        // exact (1 MiB is 1,048,576 B)
+        let result = if from >= to {
+            self.value * (from / to)
+        } else {
+            self.value / (to / from)
+        };
+
+        self.check_conversion_result(result, &format!("{:?} to {:?}", self.unit, target_unit))
+    }
+
+    /// Size of one unit in bits. Kilobyte and up are SI (powers of 1000);
+    /// the IEC units (kibibyte and up) are powers of 1024. Both are exact.
+    fn bits_per_unit(unit: &Data) -> f64 {
+        match unit {
+            Data::Bit => 1.0,
+            Data::Byte => BITS_PER_BYTE,
+            Data::Kilobyte => BITS_PER_BYTE * SI_STEP,
+            Data::Megabyte => BITS_PER_BYTE * SI_STEP.powi(2),
+            Data::Gigabyte => BITS_PER_BYTE * SI_STEP.powi(3),
+            Data::Terabyte => BITS_PER_BYTE * SI_STEP.powi(4),
+            Data::Kibibyte => BITS_PER_BYTE * IEC_STEP,
+            Data::Mebibyte => BITS_PER_BYTE * IEC_STEP.powi(2),
+            Data::Gibibyte => BITS_PER_BYTE * IEC_STEP.powi(3),
+            Data::Tebibyte => BITS_PER_BYTE * IEC_STEP.powi(4),
+        }
+    }
+
+    fn check_conversion_result(&self, result: f64, conversion_type: &str) -> Result<f64> {
+        if result.is_nan() {
+            return Err(anyhow!(
+                "{} conversion resulted in NaN. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result.is_infinite() {
+            return Err(anyhow!(
+                "{} conversion resulted in infinity. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result < 0.0 {
+            return Err(anyhow!(
+                "{} conversion resulted in negative data size: {:.6}. This should not happen with positive input.",
+                conversion_type, result
+            ));
+        }
+
+        if result > MAX_DATA_RESULT {
+            return Err(anyhow!(
+                "{} conversion resulted in an unrealistically large data size: {:.2}. Please check your input.",
+                conversion_type, result
+            ));
+        }
+
+        Ok(result)
+    }
+
+    /// Same results as calling `convert_to` for every unit, with the factors
+    /// taken from a table built once
+    pub fn convert_to_all(&self) -> Result<DataResults> {
+        let factors = Self::factor_table();
+        let from = factors[self.unit as usize];
+        let convert = |target: Data| self.scale(from, factors[target as usize], &target);
+
+        Ok(DataResults {
+            bit: convert(Data::Bit)?,
+            byte: convert(Data::Byte)?,
+            kilobyte: convert(Data::Kilobyte)?,
+            megabyte: convert(Data::Megabyte)?,
+            gigabyte: convert(Data::Gigabyte)?,
+            terabyte: convert(Data::Terabyte)?,
+            kibibyte: convert(Data::Kibibyte)?,
+            mebibyte: convert(Data::Mebibyte)?,
+            gibibyte: convert(Data::Gibibyte)?,
+            tebibyte: convert(Data::Tebibyte)?,
+        })
+    }
+}
+
+impl DataResults {
+    /// Every unit with its value, in the order `convert_to_all` fills them
+    pub fn rows(&self) -> Vec<(Data, f64)> {
+        vec![
+            (Data::Bit, self.bit),
+            (Data::Byte, self.byte),
+            (Data::Kilobyte, self.kilobyte),
+            (Data::Megabyte, self.megabyte),
+            (Data::Gigabyte, self.gigabyte),
+            (Data::Terabyte, self.terabyte),
+            (Data::Kibibyte, self.kibibyte),
+            (Data::Mebibyte, self.mebibyte),
+            (Data::Gibibyte, self.gibibyte),
+            (Data::Tebibyte, self.tebibyte),
+        ]
+    }
+}
+
+impl Display for DataResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "Bit: {:.6} bit, Byte: {:.6} B, Kilobyte: {:.6} kB, Megabyte: {:.6} MB, Gigabyte: {:.6} GB, Terabyte: {:.6} TB, Kibibyte: {:.6} KiB, Mebibyte: {:.6} MiB, Gibibyte: {:.6} GiB, Tebibyte: {:.6} TiB",
+            self.bit, self.byte, self.kilobyte, self.megabyte, self.gigabyte, self.terabyte, self.kibibyte, self.mebibyte, self.gibibyte, self.tebibyte
+        )
+    }
+}
+
+// Helper functions with error handling
+pub fn bit_to_byte(value: f64) -> Result<f64> {
+    DataConverter::new(value, Data::Bit).convert_to(Data::Byte)
+}
+
+pub fn mib_to_byte(value: f64) -> Result<f64> {
+    DataConverter::new(value, Data::Mebibyte).convert_to(Data::Byte)
+}
+
+pub fn mb_to_byte(value: f64) -> Result<f64> {
+    DataConverter::new(value, Data::Megabyte).convert_to(Data::Byte)
+}
+
+pub fn gib_to_gb(value: f64) -> Result<f64> {
+    DataConverter::new(value, Data::Gibibyte).convert_to(Data::Gigabyte)
+}
+
+pub fn tb_to_tib(value: f64) -> Result<f64> {
+    DataConverter::new(value, Data::Terabyte).convert_to(Data::Tebibyte)
+}
+
+pub fn kib_to_kb(value: f64) -> Result<f64> {
+    DataConverter::new(value, Data::Kibibyte).convert_to(Data::Kilobyte)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bits_and_bytes() -> Result<()> {
+        assert_eq!(bit_to_byte(8.0)?, 1.0);
+        assert_eq!(bit_to_byte(1.0)?, 0.125);
+        Ok(())
+    }
+
+    #[test]
+    fn test_si_and_binary_prefixes() -> Result<()> {
+        assert_eq!(mib_to_byte(1.0)?, 1_048_576.0);
+        assert_eq!(mb_to_byte(1.0)?, 1_000_000.0);
+        assert_eq!(kib_to_kb(1.0)?, 1.024);
+        assert!((gib_to_gb(1.0)? - 1.073741824).abs() < 1e-12);
+        // A "1 TB" drive holds about 0.909 TiB
+        assert!((tb_to_tib(1.0)? - 0.9094947).abs() < 1e-7);
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_all() -> Result<()> {
+        DataConverter::warm_up();
+        let converter = DataConverter::new(1.0, Data::Gibibyte);
+        let all = converter.convert_to_all()?;
+
+        assert_eq!(all.mebibyte, 1024.0);
+        assert_eq!(all.byte, 1_073_741_824.0);
+        assert_eq!(
+            all.gigabyte.to_bits(),
+            converter.convert_to(Data::Gigabyte)?.to_bits()
+        );
+        assert!(all.to_string().contains("Gibibyte: 1.000000 GiB"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_conversions() {
+        let converter = DataConverter::new(f64::NAN, Data::Byte);
+        assert!(converter.convert_to(Data::Bit).is_err());
+
+        let converter = DataConverter::new(f64::INFINITY, Data::Byte);
+        assert!(converter.convert_to(Data::Bit).is_err());
+
+        let converter = DataConverter::new(-1.0, Data::Byte);
+        assert!(converter.convert_to(Data::Bit).is_err());
+    }
+}
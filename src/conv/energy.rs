@@ -0,0 +1,233 @@
+use super::Energy;
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use std::fmt::{Display, Result as FmtResult};
+use std::sync::OnceLock;
+
+/// Largest energy result accepted before the input is considered unrealistic
+const MAX_ENERGY_RESULT: f64 = 1e24;
+
+/// `joules_per_unit` for every `Energy`, indexed by variant
+static FACTOR_TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+
+pub struct EnergyConverter {
+    pub value: f64,
+    pub unit: Energy,
+}
+
+pub struct EnergyResults {
+    pub joule: f64,
+    pub kilojoule: f64,
+    pub calorie: f64,
+    pub kilocalorie: f64,
+    pub watt_hour: f64,
+    pub kilowatt_hour: f64,
+    pub btu: f64,
+}
+
+impl EnergyConverter {
+    pub fn new(value: f64, unit: Energy) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn convert_to(&self, target_unit: Energy) -> Result<f64> {
+        let from = Self::joules_per_unit(&self.unit);
+        let to = Self::joules_per_unit(&target_unit);
+        self.scale(from, to, &target_unit)
+    }
+
+    /// Builds the factor table used by `convert_to_all` ahead of time
+    pub fn warm_up() {
+        Self::factor_table();
+    }
+
+    fn factor_table() -> &'static [f64] {
+        FACTOR_TABLE.get_or_init(|| {
+            Energy::value_variants()
+                .iter()
+                .map(Self::joules_per_unit)
+                .collect()
+        })
+    }
+
+    fn scale(&self, from: f64, to: f64, target_unit: &Energy) -> Result<f64> {
+        // Always scale by a ratio of at least 1 so exact relationships stay
+        // exact (1 kWh is 3,600,000 J)
+        let result = if from >= to {
+            self.value * (from / to)
+        } else {
+            self.value / (to / from)
+        };
+
+        self.check_conversion_result(result, &format!("{:?} to {:?}", self.unit, target_unit))
+    }
+
+    /// Size of one unit in joules. The calorie is the small (thermochemical)
+    /// calorie of 4.184 J; the "Calorie" on nutrition labels is the
+    /// kilocalorie. The BTU is the International Table BTU.
+    fn joules_per_unit(unit: &Energy) -> f64 {
+        match unit {
+            Energy::Joule => 1.0,
+            Energy::Kilojoule => 1e3,
+            Energy::Calorie => 4.184,
+            Energy::Kilocalorie => 4184.0,
+            Energy::WattHour => 3600.0,
+            Energy::KilowattHour => 3.6e6,
+            Energy::Btu => 1_055.055_852_62,
+        }
+    }
+
+    fn check_conversion_result(&self, result: f64, conversion_type: &str) -> Result<f64> {
+        if result.is_nan() {
+            return Err(anyhow!(
+                "{} conversion resulted in NaN. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result.is_infinite() {
+            return Err(anyhow!(
+                "{} conversion resulted in infinity. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result < 0.0 {
+            return Err(anyhow!(
+                "{} conversion resulted in negative energy: {:.6}. This should not happen with positive input.",
+                conversion_type, result
+            ));
+        }
+
+        if result > MAX_ENERGY_RESULT {
+            return Err(anyhow!(
+                "{} conversion resulted in an unrealistically large energy: {:.2}. Please check your input.",
+                conversion_type, result
+            ));
+        }
+
+        Ok(result)
+    }
+
+    /// Same results as calling `convert_to` for every unit, with the factors
+    /// taken from a table built once
+    pub fn convert_to_all(&self) -> Result<EnergyResults> {
+        let factors = Self::factor_table();
+        let from = factors[self.unit as usize];
+        let convert = |target: Energy| self.scale(from, factors[target as usize], &target);
+
+        Ok(EnergyResults {
+            joule: convert(Energy::Joule)?,
+            kilojoule: convert(Energy::Kilojoule)?,
+            calorie: convert(Energy::Calorie)?,
+            kilocalorie: convert(Energy::Kilocalorie)?,
+            watt_hour: convert(Energy::WattHour)?,
+            kilowatt_hour: convert(Energy::KilowattHour)?,
+            btu: convert(Energy::Btu)?,
+        })
+    }
+}
+
+impl EnergyResults {
+    /// Every unit with its value, in the order `convert_to_all` fills them
+    pub fn rows(&self) -> Vec<(Energy, f64)> {
+        vec![
+            (Energy::Joule, self.joule),
+            (Energy::Kilojoule, self.kilojoule),
+            (Energy::Calorie, self.calorie),
+            (Energy::Kilocalorie, self.kilocalorie),
+            (Energy::WattHour, self.watt_hour),
+            (Energy::KilowattHour, self.kilowatt_hour),
+            (Energy::Btu, self.btu),
+        ]
+    }
+}
+
+impl Display for EnergyResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "Joule: {:.6} J, Kilojoule: {:.6} kJ, Calorie: {:.6} cal, Kilocalorie: {:.6} kcal, Watt-hour: {:.6} Wh, Kilowatt-hour: {:.6} kWh, BTU: {:.6} BTU",
+            self.joule, self.kilojoule, self.calorie, self.kilocalorie, self.watt_hour, self.kilowatt_hour, self.btu
+        )
+    }
+}
+
+// Helper functions with error handling
+pub fn kwh_to_j(value: f64) -> Result<f64> {
+    EnergyConverter::new(value, Energy::KilowattHour).convert_to(Energy::Joule)
+}
+
+pub fn kcal_to_j(value: f64) -> Result<f64> {
+    EnergyConverter::new(value, Energy::Kilocalorie).convert_to(Energy::Joule)
+}
+
+pub fn kcal_to_kj(value: f64) -> Result<f64> {
+    EnergyConverter::new(value, Energy::Kilocalorie).convert_to(Energy::Kilojoule)
+}
+
+pub fn cal_to_j(value: f64) -> Result<f64> {
+    EnergyConverter::new(value, Energy::Calorie).convert_to(Energy::Joule)
+}
+
+pub fn btu_to_j(value: f64) -> Result<f64> {
+    EnergyConverter::new(value, Energy::Btu).convert_to(Energy::Joule)
+}
+
+pub fn wh_to_kj(value: f64) -> Result<f64> {
+    EnergyConverter::new(value, Energy::WattHour).convert_to(Energy::Kilojoule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_conversions() -> Result<()> {
+        assert_eq!(kwh_to_j(1.0)?, 3_600_000.0);
+        assert_eq!(kcal_to_j(1.0)?, 4184.0);
+        assert_eq!(cal_to_j(1.0)?, 4.184);
+        assert_eq!(wh_to_kj(1.0)?, 3.6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_food_and_heating_units() -> Result<()> {
+        // A 2000 kcal diet is about 8.4 MJ
+        assert!((kcal_to_kj(2000.0)? - 8368.0).abs() < 1e-9);
+        assert!((btu_to_j(1.0)? - 1055.056).abs() < 1e-3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_all() -> Result<()> {
+        EnergyConverter::warm_up();
+        let converter = EnergyConverter::new(1.0, Energy::KilowattHour);
+        let all = converter.convert_to_all()?;
+
+        assert_eq!(all.watt_hour, 1000.0);
+        assert_eq!(all.kilojoule, 3600.0);
+        assert_eq!(
+            all.btu.to_bits(),
+            converter.convert_to(Energy::Btu)?.to_bits()
+        );
+        assert!(all.to_string().contains("Kilowatt-hour: 1.000000 kWh"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_conversions() {
+        let converter = EnergyConverter::new(f64::NAN, Energy::Joule);
+        assert!(converter.convert_to(Energy::Calorie).is_err());
+
+        let converter = EnergyConverter::new(f64::INFINITY, Energy::Joule);
+        assert!(converter.convert_to(Energy::Calorie).is_err());
+
+        let converter = EnergyConverter::new(-1.0, Energy::Joule);
+        assert!(converter.convert_to(Energy::Calorie).is_err());
+    }
+}
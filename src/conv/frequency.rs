@@ -0,0 +1,217 @@
+use super::Frequency;
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use std::fmt::{Display, Result as FmtResult};
+use std::sync::OnceLock;
+
+/// Largest frequency result accepted before the input is considered unrealistic
+const MAX_FREQUENCY_RESULT: f64 = 1e18;
+
+/// Revolutions per minute in one hertz (one cycle per second)
+const RPM_PER_HERTZ: f64 = 60.0;
+
+/// `rpm_per_unit` for every `Frequency`, indexed by variant
+static FACTOR_TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+
+pub struct FrequencyConverter {
+    pub value: f64,
+    pub unit: Frequency,
+}
+
+pub struct FrequencyResults {
+    pub hertz: f64,
+    pub kilohertz: f64,
+    pub megahertz: f64,
+    pub gigahertz: f64,
+    pub rpm: f64,
+}
+
+impl FrequencyConverter {
+    pub fn new(value: f64, unit: Frequency) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn convert_to(&self, target_unit: Frequency) -> Result<f64> {
+        let from = Self::rpm_per_unit(&self.unit);
+        let to = Self::rpm_per_unit(&target_unit);
+        self.scale(from, to, &target_unit)
+    }
+
+    /// Builds the factor table used by `convert_to_all` ahead of time
+    pub fn warm_up() {
+        Self::factor_table();
+    }
+
+    fn factor_table() -> &'static [f64] {
+        FACTOR_TABLE.get_or_init(|| {
+            Frequency::value_variants()
+                .iter()
+                .map(Self::rpm_per_unit)
+                .collect()
+        })
+    }
+
+    fn scale(&self, from: f64, to: f64, target_unit: &Frequency) -> Result<f64> {
+        // Always scale by a ratio of at least 1 so exact relationships stay
+        // exact (1 GHz is 1e9 Hz)
+        let result = if from >= to {
+            self.value * (from / to)
+        } else {
+            self.value / (to / from)
+        };
+
+        self.check_conversion_result(result, &format!("{:?} to {:?}", self.unit, target_unit))
+    }
+
+    /// Size of one unit in revolutions per minute, which makes every factor
+    /// a whole number
+    fn rpm_per_unit(unit: &Frequency) -> f64 {
+        match unit {
+            Frequency::Hertz => RPM_PER_HERTZ,
+            Frequency::Kilohertz => RPM_PER_HERTZ * 1e3,
+            Frequency::Megahertz => RPM_PER_HERTZ * 1e6,
+            Frequency::Gigahertz => RPM_PER_HERTZ * 1e9,
+            Frequency::Rpm => 1.0,
+        }
+    }
+
+    fn check_conversion_result(&self, result: f64, conversion_type: &str) -> Result<f64> {
+        if result.is_nan() {
+            return Err(anyhow!(
+                "{} conversion resulted in NaN. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result.is_infinite() {
+            return Err(anyhow!(
+                "{} conversion resulted in infinity. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result < 0.0 {
+            return Err(anyhow!(
+                "{} conversion resulted in negative frequency: {:.6}. This should not happen with positive input.",
+                conversion_type, result
+            ));
+        }
+
+        if result > MAX_FREQUENCY_RESULT {
+            return Err(anyhow!(
+                "{} conversion resulted in an unrealistically large frequency: {:.2}. Please check your input.",
+                conversion_type, result
+            ));
+        }
+
+        Ok(result)
+    }
+
+    /// Same results as calling `convert_to` for every unit, with the factors
+    /// taken from a table built once
+    pub fn convert_to_all(&self) -> Result<FrequencyResults> {
+        let factors = Self::factor_table();
+        let from = factors[self.unit as usize];
+        let convert = |target: Frequency| self.scale(from, factors[target as usize], &target);
+
+        Ok(FrequencyResults {
+            hertz: convert(Frequency::Hertz)?,
+            kilohertz: convert(Frequency::Kilohertz)?,
+            megahertz: convert(Frequency::Megahertz)?,
+            gigahertz: convert(Frequency::Gigahertz)?,
+            rpm: convert(Frequency::Rpm)?,
+        })
+    }
+}
+
+impl FrequencyResults {
+    /// Every unit with its value, in the order `convert_to_all` fills them
+    pub fn rows(&self) -> Vec<(Frequency, f64)> {
+        vec![
+            (Frequency::Hertz, self.hertz),
+            (Frequency::Kilohertz, self.kilohertz),
+            (Frequency::Megahertz, self.megahertz),
+            (Frequency::Gigahertz, self.gigahertz),
+            (Frequency::Rpm, self.rpm),
+        ]
+    }
+}
+
+impl Display for FrequencyResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "Hertz: {:.6} Hz, Kilohertz: {:.6} kHz, Megahertz: {:.6} MHz, Gigahertz: {:.6} GHz, RPM: {:.6} rpm",
+            self.hertz, self.kilohertz, self.megahertz, self.gigahertz, self.rpm
+        )
+    }
+}
+
+// Helper functions with error handling
+pub fn ghz_to_hz(value: f64) -> Result<f64> {
+    FrequencyConverter::new(value, Frequency::Gigahertz).convert_to(Frequency::Hertz)
+}
+
+pub fn mhz_to_khz(value: f64) -> Result<f64> {
+    FrequencyConverter::new(value, Frequency::Megahertz).convert_to(Frequency::Kilohertz)
+}
+
+pub fn rpm_to_hz(value: f64) -> Result<f64> {
+    FrequencyConverter::new(value, Frequency::Rpm).convert_to(Frequency::Hertz)
+}
+
+pub fn hz_to_rpm(value: f64) -> Result<f64> {
+    FrequencyConverter::new(value, Frequency::Hertz).convert_to(Frequency::Rpm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_conversions() -> Result<()> {
+        assert_eq!(ghz_to_hz(1.0)?, 1e9);
+        assert_eq!(mhz_to_khz(2.4)?, 2400.0);
+        let converter = FrequencyConverter::new(440.0, Frequency::Hertz);
+        assert_eq!(converter.convert_to(Frequency::Kilohertz)?, 0.44);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rpm_conversions() -> Result<()> {
+        assert_eq!(rpm_to_hz(3000.0)?, 50.0);
+        assert_eq!(hz_to_rpm(1.0)?, 60.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_all() -> Result<()> {
+        FrequencyConverter::warm_up();
+        let converter = FrequencyConverter::new(50.0, Frequency::Hertz);
+        let all = converter.convert_to_all()?;
+
+        assert_eq!(all.rpm, 3000.0);
+        assert_eq!(
+            all.megahertz.to_bits(),
+            converter.convert_to(Frequency::Megahertz)?.to_bits()
+        );
+        assert!(all.to_string().contains("RPM: 3000.000000 rpm"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_conversions() {
+        let converter = FrequencyConverter::new(f64::NAN, Frequency::Hertz);
+        assert!(converter.convert_to(Frequency::Rpm).is_err());
+
+        let converter = FrequencyConverter::new(f64::INFINITY, Frequency::Hertz);
+        assert!(converter.convert_to(Frequency::Rpm).is_err());
+
+        let converter = FrequencyConverter::new(-1.0, Frequency::Hertz);
+        assert!(converter.convert_to(Frequency::Rpm).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+use super::FuelEconomy;
+use anyhow::{anyhow, Result};
+use std::fmt::{Display, Result as FmtResult};
+
+/// Kilometers in a statute mile and liters in the two gallons (all exact)
+const KILOMETERS_PER_MILE: f64 = 1.609_344;
+const LITERS_PER_US_GALLON: f64 = 3.785_411_784;
+const LITERS_PER_IMPERIAL_GALLON: f64 = 4.546_09;
+
+/// Largest fuel economy result accepted before the input is considered
+/// unrealistic (either a tiny consumption or a tiny efficiency)
+const MAX_FUEL_RESULT: f64 = 1e12;
+
+/// Converts between distance-per-volume units (mpg, km/L) and the
+/// volume-per-distance L/100km. The two kinds are reciprocals of each other,
+/// so every conversion goes through km/L instead of a single factor:
+/// 30 mpg is about 7.84 L/100km, and 60 mpg is half that, not double.
+pub struct FuelEconomyConverter {
+    pub value: f64,
+    pub unit: FuelEconomy,
+}
+
+pub struct FuelEconomyResults {
+    pub mpg_us: f64,
+    pub mpg_imperial: f64,
+    pub l_100km: f64,
+    pub km_per_liter: f64,
+}
+
+impl FuelEconomyConverter {
+    pub fn new(value: f64, unit: FuelEconomy) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn convert_to(&self, target_unit: FuelEconomy) -> Result<f64> {
+        if !self.value.is_finite() || self.value <= 0.0 {
+            return Err(anyhow!(
+                "Fuel economy must be a positive number, got {} {:?}.",
+                self.value,
+                self.unit
+            ));
+        }
+
+        let km_per_liter = to_km_per_liter(self.value, &self.unit);
+        let result = from_km_per_liter(km_per_liter, &target_unit);
+        self.check_conversion_result(result, &format!("{:?} to {:?}", self.unit, target_unit))
+    }
+
+    fn check_conversion_result(&self, result: f64, conversion_type: &str) -> Result<f64> {
+        if !result.is_finite() {
+            return Err(anyhow!(
+                "{} conversion resulted in {}. Input value: {} {:?}",
+                conversion_type,
+                result,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result > MAX_FUEL_RESULT {
+            return Err(anyhow!(
+                "{} conversion resulted in an unrealistic fuel economy: {:.2}. Please check your input.",
+                conversion_type, result
+            ));
+        }
+
+        Ok(result)
+    }
+
+    pub fn convert_to_all(&self) -> Result<FuelEconomyResults> {
+        Ok(FuelEconomyResults {
+            mpg_us: self.convert_to(FuelEconomy::MpgUS)?,
+            mpg_imperial: self.convert_to(FuelEconomy::MpgImperial)?,
+            l_100km: self.convert_to(FuelEconomy::L100km)?,
+            km_per_liter: self.convert_to(FuelEconomy::KmPerLiter)?,
+        })
+    }
+}
+
+fn to_km_per_liter(value: f64, unit: &FuelEconomy) -> f64 {
+    match unit {
+        FuelEconomy::MpgUS => value * KILOMETERS_PER_MILE / LITERS_PER_US_GALLON,
+        FuelEconomy::MpgImperial => value * KILOMETERS_PER_MILE / LITERS_PER_IMPERIAL_GALLON,
+        FuelEconomy::L100km => 100.0 / value,
+        FuelEconomy::KmPerLiter => value,
+    }
+}
+
+fn from_km_per_liter(km_per_liter: f64, unit: &FuelEconomy) -> f64 {
+    match unit {
+        FuelEconomy::MpgUS => km_per_liter * LITERS_PER_US_GALLON / KILOMETERS_PER_MILE,
+        FuelEconomy::MpgImperial => km_per_liter * LITERS_PER_IMPERIAL_GALLON / KILOMETERS_PER_MILE,
+        FuelEconomy::L100km => 100.0 / km_per_liter,
+        FuelEconomy::KmPerLiter => km_per_liter,
+    }
+}
+
+impl FuelEconomyResults {
+    /// Every unit with its value, in the order `convert_to_all` fills them
+    pub fn rows(&self) -> Vec<(FuelEconomy, f64)> {
+        vec![
+            (FuelEconomy::MpgUS, self.mpg_us),
+            (FuelEconomy::MpgImperial, self.mpg_imperial),
+            (FuelEconomy::L100km, self.l_100km),
+            (FuelEconomy::KmPerLiter, self.km_per_liter),
+        ]
+    }
+}
+
+impl Display for FuelEconomyResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "MPG (US): {:.6} mpg, MPG (imperial): {:.6} mpg (imp), Liters per 100 km: {:.6} L/100km, Kilometers per liter: {:.6} km/L",
+            self.mpg_us, self.mpg_imperial, self.l_100km, self.km_per_liter
+        )
+    }
+}
+
+// Helper functions with error handling
+pub fn mpg_to_l100km(value: f64) -> Result<f64> {
+    FuelEconomyConverter::new(value, FuelEconomy::MpgUS).convert_to(FuelEconomy::L100km)
+}
+
+pub fn l100km_to_mpg(value: f64) -> Result<f64> {
+    FuelEconomyConverter::new(value, FuelEconomy::L100km).convert_to(FuelEconomy::MpgUS)
+}
+
+pub fn mpg_us_to_imperial(value: f64) -> Result<f64> {
+    FuelEconomyConverter::new(value, FuelEconomy::MpgUS).convert_to(FuelEconomy::MpgImperial)
+}
+
+pub fn kmpl_to_l100km(value: f64) -> Result<f64> {
+    FuelEconomyConverter::new(value, FuelEconomy::KmPerLiter).convert_to(FuelEconomy::L100km)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mpg_to_liters_per_100km() -> Result<()> {
+        assert!((mpg_to_l100km(30.0)? - 7.84).abs() < 0.005);
+        // Reciprocal, not linear: doubling the mpg halves the consumption
+        assert!((mpg_to_l100km(60.0)? * 2.0 - mpg_to_l100km(30.0)?).abs() < 1e-12);
+        assert_eq!(kmpl_to_l100km(20.0)?, 5.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_liters_per_100km_round_trips() -> Result<()> {
+        for l_100km in [3.5, 7.84, 12.0, 25.0] {
+            let back = FuelEconomyConverter::new(l100km_to_mpg(l_100km)?, FuelEconomy::MpgUS)
+                .convert_to(FuelEconomy::L100km)?;
+            assert!(
+                (back - l_100km).abs() < 1e-12,
+                "{} came back as {}",
+                l_100km,
+                back
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_us_and_imperial_mpg() -> Result<()> {
+        // An imperial gallon is bigger, so the same car gets more mpg (imp)
+        assert!((mpg_us_to_imperial(30.0)? - 36.03).abs() < 0.01);
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_all() -> Result<()> {
+        let converter = FuelEconomyConverter::new(5.0, FuelEconomy::L100km);
+        let all = converter.convert_to_all()?;
+
+        assert_eq!(all.km_per_liter, 20.0);
+        assert!(all
+            .to_string()
+            .contains("Liters per 100 km: 5.000000 L/100km"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_conversions() {
+        for value in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let converter = FuelEconomyConverter::new(value, FuelEconomy::L100km);
+            assert!(
+                converter.convert_to(FuelEconomy::MpgUS).is_err(),
+                "{}",
+                value
+            );
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use super::{Degree, Pressure, PressureConverter, TemperatureConverter, Volume, VolumeConverter};
+use anyhow::{anyhow, Result};
+
+/// Molar gas constant R in J/(mol·K), exact since the 2019 SI redefinition
+pub const GAS_CONSTANT: f64 = 8.314_462_618_153_24;
+
+/// An amount of ideal gas at a temperature and pressure, in any units.
+/// Both are normalized to SI (kelvin, pascals) by the temperature and
+/// pressure converters before PV = nRT is solved for V.
+pub struct IdealGas {
+    pub moles: f64,
+    pub temperature: f64,
+    pub temperature_unit: Degree,
+    pub pressure: f64,
+    pub pressure_unit: Pressure,
+}
+
+impl IdealGas {
+    pub fn new(
+        moles: f64,
+        temperature: f64,
+        temperature_unit: Degree,
+        pressure: f64,
+        pressure_unit: Pressure,
+    ) -> Self {
+        Self {
+            moles,
+            temperature,
+            temperature_unit,
+            pressure,
+            pressure_unit,
+        }
+    }
+
+    /// The volume in cubic meters
+    pub fn cubic_meters(&self) -> Result<f64> {
+        if !self.moles.is_finite() || self.moles <= 0.0 {
+            return Err(anyhow!(
+                "The amount of gas must be a positive number of moles, got {}.",
+                self.moles
+            ));
+        }
+
+        let kelvin = TemperatureConverter::new(self.temperature, self.temperature_unit)
+            .convert_to(Degree::Kelvin)?;
+        if kelvin <= 0.0 {
+            return Err(anyhow!(
+                "The gas temperature must be above absolute zero, got {} {}.",
+                self.temperature,
+                self.temperature_unit
+            ));
+        }
+
+        let pascals = PressureConverter::new(self.pressure, self.pressure_unit)
+            .convert_to(Pressure::Pascal)?;
+        if pascals <= 0.0 {
+            return Err(anyhow!(
+                "The gas pressure must be positive, got {} {}.",
+                self.pressure,
+                self.pressure_unit
+            ));
+        }
+
+        Ok(self.moles * GAS_CONSTANT * kelvin / pascals)
+    }
+
+    /// The volume in any volume unit, converted by `VolumeConverter`
+    pub fn convert_to(&self, unit: Volume) -> Result<f64> {
+        VolumeConverter::new(self.cubic_meters()?, Volume::CubicMeter).convert_to(unit)
+    }
+}
+
+// Helper functions with error handling
+pub fn liters_at_stp(moles: f64) -> Result<f64> {
+    IdealGas::new(moles, 273.15, Degree::Kelvin, 101_325.0, Pressure::Pascal)
+        .convert_to(Volume::Liter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_molar_volume_at_stp() -> Result<()> {
+        // 22.413 969 54 L/mol (CODATA molar volume at 273.15 K, 101.325 kPa)
+        let liters = liters_at_stp(1.0)?;
+        assert!((liters - 22.413_969_54).abs() < 1e-6, "got {} L", liters);
+        assert!((liters_at_stp(2.0)? - 2.0 * liters).abs() < 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn test_inputs_in_other_units() -> Result<()> {
+        // 0 °C and 1 atm are the same conditions as 273.15 K and 101325 Pa
+        let gas = IdealGas::new(1.0, 0.0, Degree::Celsius, 1.0, Pressure::Atmosphere);
+        assert!((gas.convert_to(Volume::Liter)? - liters_at_stp(1.0)?).abs() < 1e-9);
+
+        // Doubling the pressure halves the volume
+        let gas = IdealGas::new(1.0, 0.0, Degree::Celsius, 2.0, Pressure::Atmosphere);
+        assert!((gas.convert_to(Volume::Liter)? - liters_at_stp(1.0)? / 2.0).abs() < 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_gas() {
+        let volume = |moles, kelvin, pascals| {
+            IdealGas::new(moles, kelvin, Degree::Kelvin, pascals, Pressure::Pascal)
+                .convert_to(Volume::Liter)
+        };
+        assert!(volume(0.0, 273.15, 101_325.0).is_err());
+        assert!(volume(f64::NAN, 273.15, 101_325.0).is_err());
+        assert!(volume(1.0, 0.0, 101_325.0).is_err());
+        assert!(volume(1.0, 273.15, 0.0).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+use super::{Length, LengthConverter};
+use anyhow::{anyhow, Result};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Mean Earth radius (IUGG), the usual choice for haversine distances
+const EARTH_RADIUS_METERS: f64 = 6_371_008.8;
+
+/// A point on the globe in decimal degrees, written `latitude,longitude`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coordinate {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Coordinate {
+    pub fn new(latitude: f64, longitude: f64) -> Result<Self> {
+        if !latitude.is_finite() || !(-90.0..=90.0).contains(&latitude) {
+            return Err(anyhow!(
+                "Latitude must be between -90 and 90 degrees, got {}.",
+                latitude
+            ));
+        }
+        if !longitude.is_finite() || !(-180.0..=180.0).contains(&longitude) {
+            return Err(anyhow!(
+                "Longitude must be between -180 and 180 degrees, got {}.",
+                longitude
+            ));
+        }
+
+        Ok(Self {
+            latitude,
+            longitude,
+        })
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        let invalid = || {
+            anyhow!(
+                "Cannot read '{}'. Use decimal degrees as latitude,longitude (e.g. 40.7128,-74.0060).",
+                input.trim()
+            )
+        };
+
+        let (latitude, longitude) = input.split_once(',').ok_or_else(invalid)?;
+        let latitude = latitude.trim().parse::<f64>().map_err(|_| invalid())?;
+        let longitude = longitude.trim().parse::<f64>().map_err(|_| invalid())?;
+        Self::new(latitude, longitude)
+    }
+}
+
+impl Display for Coordinate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{},{}", self.latitude, self.longitude)
+    }
+}
+
+/// Great-circle distance between two coordinates on a spherical Earth
+pub struct GeoDistance {
+    pub from: Coordinate,
+    pub to: Coordinate,
+}
+
+impl GeoDistance {
+    pub fn new(from: Coordinate, to: Coordinate) -> Self {
+        Self { from, to }
+    }
+
+    /// Haversine formula; well-conditioned for short distances, unlike the
+    /// spherical law of cosines
+    pub fn meters(&self) -> f64 {
+        let from_latitude = self.from.latitude.to_radians();
+        let to_latitude = self.to.latitude.to_radians();
+        let delta_latitude = to_latitude - from_latitude;
+        let delta_longitude = (self.to.longitude - self.from.longitude).to_radians();
+
+        let a = (delta_latitude / 2.0).sin().powi(2)
+            + from_latitude.cos() * to_latitude.cos() * (delta_longitude / 2.0).sin().powi(2);
+        let central_angle = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
+
+        EARTH_RADIUS_METERS * central_angle
+    }
+
+    /// The distance in any length unit, converted by `LengthConverter`
+    pub fn convert_to(&self, unit: Length) -> Result<f64> {
+        LengthConverter::new(self.meters(), Length::Meter).convert_to(unit)
+    }
+}
+
+// Helper functions with error handling
+pub fn great_circle_km(from: &str, to: &str) -> Result<f64> {
+    GeoDistance::new(Coordinate::parse(from)?, Coordinate::parse(to)?).convert_to(Length::Kilometer)
+}
+
+pub fn great_circle_miles(from: &str, to: &str) -> Result<f64> {
+    GeoDistance::new(Coordinate::parse(from)?, Coordinate::parse(to)?).convert_to(Length::Miles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_city_pairs() -> Result<()> {
+        // New York City to Los Angeles, about 3936 km
+        let km = great_circle_km("40.7128,-74.0060", "34.0522,-118.2437")?;
+        assert!((km - 3935.75).abs() < 0.5, "got {} km", km);
+
+        // London to Paris, about 344 km (213 mi)
+        let km = great_circle_km("51.5074,-0.1278", "48.8566,2.3522")?;
+        assert!((km - 343.56).abs() < 0.5, "got {} km", km);
+        let miles = great_circle_miles("51.5074,-0.1278", "48.8566,2.3522")?;
+        assert!((miles - 213.47).abs() < 0.5, "got {} mi", miles);
+        Ok(())
+    }
+
+    #[test]
+    fn test_degenerate_distances() -> Result<()> {
+        assert_eq!(great_circle_km("10,20", "10,20")?, 0.0);
+
+        // Half the equator is half the circumference
+        let km = great_circle_km("0,0", "0,180")?;
+        assert!((km - std::f64::consts::PI * 6371.0088).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_coordinates() {
+        assert!(Coordinate::parse("40.7128").is_err());
+        assert!(Coordinate::parse("north,west").is_err());
+        assert!(Coordinate::parse("91,0").is_err());
+        assert!(Coordinate::parse("0,-181").is_err());
+        assert!(Coordinate::parse("nan,0").is_err());
+        assert_eq!(
+            Coordinate::parse(" 40.7128 , -74.0060 ").unwrap(),
+            Coordinate::new(40.7128, -74.006).unwrap()
+        );
+    }
+}
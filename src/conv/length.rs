@@ -1,145 +1,286 @@
-use super::Length;
-use anyhow::{anyhow, Result};
-use std::fmt::{Display, Result as FmtResult};
-
-pub struct LengthConverter {
-    pub value: f64,
+use super::{float, Length};
+use crate::errors::ConversionError;
+use anyhow::Result;
+use num_traits::Float;
+use std::f64::consts::PI;
+use std::fmt::{Display, LowerExp, Result as FmtResult};
+use std::num::FpCategory;
+use std::sync::OnceLock;
+
+/// Meters in one astronomical unit (IAU 2012, exact)
+const METERS_PER_AU: f64 = 149_597_870_700.0;
+/// Meters in one Julian light-year (IAU, exact)
+const METERS_PER_LIGHT_YEAR: f64 = 9_460_730_472_580_800.0;
+
+/// Largest length result accepted. Generous enough for astronomical units
+/// expressed in the smallest units (1 pc ≈ 3.1e25 nm) while still catching
+/// runaway inputs.
+const MAX_LENGTH_RESULT: f64 = 1e30;
+
+/// `picometers_per_unit` for every `Length`, indexed by variant
+static FACTOR_TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+
+/// Converts `value` from `unit`. `T` is the float type: `f64` through
+/// `new`, or `f32` (or `f64`) through `from_float`.
+pub struct LengthConverter<T = f64> {
+    pub value: T,
     pub unit: Length,
 }
 
+/// The value in every length unit; with the `serde` feature it serializes
+/// as `{"centimeter": ..., "inch": ..., ...}`
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct LengthResults {
     pub centimeter: f64,
     pub inch: f64,
     pub kilometer: f64,
     pub miles: f64,
+    pub meter: f64,
+    pub millimeter: f64,
+    pub micrometer: f64,
+    pub nanometer: f64,
+    pub foot: f64,
+    pub yard: f64,
+    pub nautical_mile: f64,
+    pub light_year: f64,
+    pub astronomical_unit: f64,
+    pub parsec: f64,
+    pub angstrom: f64,
+    pub thou: f64,
+    pub furlong: f64,
+    pub fathom: f64,
+    pub rod: f64,
+    pub chain: f64,
 }
 
-impl LengthConverter {
-    pub fn new(value: f64, unit: Length) -> Self {
+impl<T: Float + Display + LowerExp> LengthConverter<T> {
+    /// `new` for any float type, e.g. `LengthConverter::from_float(2.5f32, ...)`
+    pub fn from_float(value: T, unit: Length) -> Self {
         Self { value, unit }
     }
 
-    pub fn convert_to(&self, target_unit: Length) -> Result<f64> {
-        let result = match (&self.unit, &target_unit) {
-            // Same unit, no conversion needed
-            (Length::Centimeter, Length::Centimeter) => self.value,
-            (Length::Inch, Length::Inch) => self.value,
-            (Length::Kilometer, Length::Kilometer) => self.value,
-            (Length::Miles, Length::Miles) => self.value,
-
-            // Centimeter conversion
-            (Length::Centimeter, Length::Inch) => {
-                let result = self.value / 2.54;
-                self.check_conversion_result(result, "Centimeter to Inch")?
-            }
-            (Length::Centimeter, Length::Kilometer) => {
-                let result = self.value / 100000.0;
-                self.check_conversion_result(result, "Centimeter to Kilometer")?
-            }
-            (Length::Centimeter, Length::Miles) => {
-                let result = self.value / 160934.4;
-                self.check_conversion_result(result, "Centimeter to Miles")?
-            }
-
-            // Inch conversion
-            (Length::Inch, Length::Centimeter) => {
-                let result = self.value * 2.54;
-                self.check_conversion_result(result, "Inch to Centimeter")?
-            }
-            (Length::Inch, Length::Kilometer) => {
-                let result = self.value * 0.0000254;
-                self.check_conversion_result(result, "Inch to Kilometer")?
-            }
-            (Length::Inch, Length::Miles) => {
-                let result = self.value / 63360.0;
-                self.check_conversion_result(result, "Inch to Miles")?
-            }
-
-            // Kilometer conversion
-            (Length::Kilometer, Length::Centimeter) => {
-                let result = self.value * 100000.0;
-                self.check_conversion_result(result, "Kilometer to Centimeter")?
-            }
-            (Length::Kilometer, Length::Inch) => {
-                let result = self.value * 39370.08;
-                self.check_conversion_result(result, "Kilometer to Inch")?
-            }
-            (Length::Kilometer, Length::Miles) => {
-                let result = self.value / 1.609344;
-                self.check_conversion_result(result, "Kilometer to Miles")?
-            }
+    pub fn convert_to(&self, target_unit: Length) -> Result<T> {
+        // The factors are f64 whatever `T` is
+        let from = LengthConverter::picometers_per_unit(&self.unit);
+        let to = LengthConverter::picometers_per_unit(&target_unit);
+        self.scale(from, to, &target_unit)
+    }
 
-            // Miles conversion
-            (Length::Miles, Length::Centimeter) => {
-                let result = self.value * 160934.4;
-                self.check_conversion_result(result, "Miles to Centimeter")?
-            }
-            (Length::Miles, Length::Inch) => {
-                let result = self.value * 63360.0;
-                self.check_conversion_result(result, "Miles to Inch")?
-            }
-            (Length::Miles, Length::Kilometer) => {
-                let result = self.value * 1.609344;
-                self.check_conversion_result(result, "Miles to Kilometer")?
-            }
+    fn scale(&self, from: f64, to: f64, target_unit: &Length) -> Result<T> {
+        // Always scale by a ratio of at least 1 so exact relationships stay
+        // exact (1 mi is 63360 in, 8 furlongs are 1 mi). The ratio is taken
+        // in f64 and only then cast, so an `f32` conversion rounds once.
+        let result = if from >= to {
+            self.value * float(from / to)
+        } else {
+            self.value / float(to / from)
         };
 
-        Ok(result)
+        Ok(self.check_conversion_result(result, &format!("{:?} to {:?}", self.unit, target_unit))?)
     }
 
-    fn check_conversion_result(&self, result: f64, conversion_type: &str) -> Result<f64> {
+    fn check_conversion_result(
+        &self,
+        result: T,
+        conversion_type: &str,
+    ) -> Result<T, ConversionError> {
         if result.is_nan() {
-            return Err(anyhow!(
+            return Err(ConversionError::InvalidResult(format!(
                 "{} conversion resulted in NaN. Input value: {} {:?}",
-                conversion_type,
-                self.value,
-                self.unit
-            ));
+                conversion_type, self.value, self.unit
+            )));
         }
 
         if result.is_infinite() {
-            return Err(anyhow!(
+            return Err(ConversionError::Overflow(format!(
                 "{} conversion resulted in infinity. Input value: {} {:?}",
-                conversion_type,
-                self.value,
-                self.unit
-            ));
+                conversion_type, self.value, self.unit
+            )));
         }
 
         // Check for reasonable length ranges
-        if result < 0.0 {
-            return Err(anyhow!(
+        if result < T::zero() {
+            return Err(ConversionError::InvalidResult(format!(
                 "{} conversion resulted in negative length: {:.6}. This should not happen with positive input.",
                 conversion_type, result
-            ));
+            )));
         }
 
-        if result > 1e15 {
-            return Err(anyhow!(
+        if result > float(MAX_LENGTH_RESULT) {
+            return Err(ConversionError::Overflow(format!(
                 "{} conversion resulted in an unrealistically large length: {:.2}. Please check your input.",
                 conversion_type, result
-            ));
+            )));
+        }
+
+        // A tiny length in a much larger unit can fall off the bottom of
+        // `f64`: to zero, or to a subnormal with few significant bits left
+        if result.is_zero() && !self.value.is_zero() {
+            return Err(ConversionError::Underflow(format!(
+                "{} conversion of {} {:?} is too small to represent and became 0.",
+                conversion_type, self.value, self.unit
+            )));
+        }
+        if result.classify() == FpCategory::Subnormal {
+            return Err(ConversionError::PrecisionLoss(format!(
+                "{} conversion resulted in {:e}, too small to represent accurately. Input value: {} {:?}",
+                conversion_type, result, self.value, self.unit
+            )));
         }
 
         Ok(result)
     }
+}
+
+impl LengthConverter {
+    pub fn new(value: f64, unit: Length) -> Self {
+        Self::from_float(value, unit)
+    }
+
+    /// `convert_to` along with the scaling it does, written out with the
+    /// input value: `5 ÷ 1.609344` for 5 km to mi
+    pub fn convert_to_explained(&self, target_unit: Length) -> Result<(f64, String)> {
+        let result = self.convert_to(target_unit)?;
+        let from = Self::picometers_per_unit(&self.unit);
+        let to = Self::picometers_per_unit(&target_unit);
+        // The same ratio, the same way round, as `scale`
+        let formula = if from >= to {
+            format!("{} × {}", self.value, from / to)
+        } else {
+            format!("{} ÷ {}", self.value, to / from)
+        };
+        Ok((result, formula))
+    }
+
+    /// Builds the factor table used by `convert_to_all` ahead of time, so
+    /// the first batch conversion doesn't pay for it
+    pub fn warm_up() {
+        Self::factor_table();
+    }
+
+    fn factor_table() -> &'static [f64] {
+        FACTOR_TABLE.get_or_init(|| {
+            Length::all()
+                .iter()
+                .map(Self::picometers_per_unit)
+                .collect()
+        })
+    }
+
+    /// Size of one unit in picometers. Picometers keep every defined factor
+    /// (down to the angstrom and up to the mile) a whole number, so ratios
+    /// between them are as exact as floating point allows.
+    fn picometers_per_unit(unit: &Length) -> f64 {
+        match unit {
+            Length::Centimeter => 1e10,
+            Length::Inch => 2.54e10,
+            Length::Kilometer => 1e15,
+            Length::Miles => 1.609344e15,
+            Length::Meter => 1e12,
+            Length::Millimeter => 1e9,
+            Length::Micrometer => 1e6,
+            Length::Nanometer => 1e3,
+            Length::Foot => 3.048e11,
+            Length::Yard => 9.144e11,
+            Length::NauticalMile => 1.852e15,
+            Length::LightYear => METERS_PER_LIGHT_YEAR * 1e12,
+            Length::AstronomicalUnit => METERS_PER_AU * 1e12,
+            Length::Parsec => Self::meters_per_parsec() * 1e12,
+            Length::Angstrom => 100.0,
+            Length::Thou => 2.54e7,
+            Length::Furlong => 2.01168e14,
+            Length::Fathom => 1.8288e12,
+            Length::Rod => 5.0292e12,
+            Length::Chain => 2.01168e13,
+        }
+    }
+
+    /// A parsec is defined as 648000/π astronomical units
+    fn meters_per_parsec() -> f64 {
+        648_000.0 / PI * METERS_PER_AU
+    }
 
+    /// Every unit with its own result, so one target out of range (say,
+    /// parsecs in nanometers) doesn't hide the others the way it does in
+    /// `convert_to_all`
+    pub fn convert_to_each(&self) -> Vec<(Length, Result<f64>)> {
+        let factors = Self::factor_table();
+        let from = factors[self.unit as usize];
+        Length::all()
+            .iter()
+            .map(|target| {
+                let result = self.scale(from, factors[*target as usize], target);
+                (*target, result)
+            })
+            .collect()
+    }
+
+    /// Same results as calling `convert_to` for every unit, but the factors
+    /// come from a table built once instead of being resolved per target
     pub fn convert_to_all(&self) -> Result<LengthResults> {
+        let factors = Self::factor_table();
+        let from = factors[self.unit as usize];
+        let convert = |target: Length| self.scale(from, factors[target as usize], &target);
+
         Ok(LengthResults {
-            centimeter: self.convert_to(Length::Centimeter)?,
-            inch: self.convert_to(Length::Inch)?,
-            kilometer: self.convert_to(Length::Kilometer)?,
-            miles: self.convert_to(Length::Miles)?,
+            centimeter: convert(Length::Centimeter)?,
+            inch: convert(Length::Inch)?,
+            kilometer: convert(Length::Kilometer)?,
+            miles: convert(Length::Miles)?,
+            meter: convert(Length::Meter)?,
+            millimeter: convert(Length::Millimeter)?,
+            micrometer: convert(Length::Micrometer)?,
+            nanometer: convert(Length::Nanometer)?,
+            foot: convert(Length::Foot)?,
+            yard: convert(Length::Yard)?,
+            nautical_mile: convert(Length::NauticalMile)?,
+            light_year: convert(Length::LightYear)?,
+            astronomical_unit: convert(Length::AstronomicalUnit)?,
+            parsec: convert(Length::Parsec)?,
+            angstrom: convert(Length::Angstrom)?,
+            thou: convert(Length::Thou)?,
+            furlong: convert(Length::Furlong)?,
+            fathom: convert(Length::Fathom)?,
+            rod: convert(Length::Rod)?,
+            chain: convert(Length::Chain)?,
         })
     }
 }
 
+impl LengthResults {
+    /// Every unit with its value, in the order `convert_to_all` fills them
+    pub fn rows(&self) -> Vec<(Length, f64)> {
+        vec![
+            (Length::Centimeter, self.centimeter),
+            (Length::Inch, self.inch),
+            (Length::Kilometer, self.kilometer),
+            (Length::Miles, self.miles),
+            (Length::Meter, self.meter),
+            (Length::Millimeter, self.millimeter),
+            (Length::Micrometer, self.micrometer),
+            (Length::Nanometer, self.nanometer),
+            (Length::Foot, self.foot),
+            (Length::Yard, self.yard),
+            (Length::NauticalMile, self.nautical_mile),
+            (Length::LightYear, self.light_year),
+            (Length::AstronomicalUnit, self.astronomical_unit),
+            (Length::Parsec, self.parsec),
+            (Length::Angstrom, self.angstrom),
+            (Length::Thou, self.thou),
+            (Length::Furlong, self.furlong),
+            (Length::Fathom, self.fathom),
+            (Length::Rod, self.rod),
+            (Length::Chain, self.chain),
+        ]
+    }
+}
+
 impl Display for LengthResults {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> FmtResult {
         write!(
             f,
-            "Centimeter: {:.6} cm, Inch: {:.6} in, Kilometer: {:.6} km, Miles: {:.6} mi",
-            self.centimeter, self.inch, self.kilometer, self.miles
+            "Centimeter: {:.6} cm, Inch: {:.6} in, Kilometer: {:.6} km, Miles: {:.6} mi, Meter: {:.6} m, Millimeter: {:.6} mm, Micrometer: {:.6} µm, Nanometer: {:.6} nm, Foot: {:.6} ft, Yard: {:.6} yd, Nautical Mile: {:.6} nmi, Light-year: {:e} ly, Astronomical Unit: {:e} AU, Parsec: {:e} pc, Angstrom: {:.6} Å, Thou: {:.6} thou, Furlong: {:.6} fur, Fathom: {:.6} ftm, Rod: {:.6} rd, Chain: {:.6} ch",
+            self.centimeter, self.inch, self.kilometer, self.miles, self.meter, self.millimeter, self.micrometer, self.nanometer, self.foot, self.yard, self.nautical_mile, self.light_year, self.astronomical_unit, self.parsec, self.angstrom, self.thou, self.furlong, self.fathom, self.rod, self.chain
         )
     }
 }
@@ -193,9 +334,308 @@ pub fn mile_to_inch(value: f64) -> Result<f64> {
     LengthConverter::new(value, Length::Miles).convert_to(Length::Inch)
 }
 
+pub fn m_to_cm(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Meter).convert_to(Length::Centimeter)
+}
+
+pub fn m_to_inch(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Meter).convert_to(Length::Inch)
+}
+
+pub fn m_to_km(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Meter).convert_to(Length::Kilometer)
+}
+
+pub fn m_to_mile(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Meter).convert_to(Length::Miles)
+}
+
+pub fn cm_to_m(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Centimeter).convert_to(Length::Meter)
+}
+
+pub fn inch_to_m(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Inch).convert_to(Length::Meter)
+}
+
+pub fn km_to_m(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Kilometer).convert_to(Length::Meter)
+}
+
+pub fn mile_to_m(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Miles).convert_to(Length::Meter)
+}
+
+pub fn mm_to_cm(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Millimeter).convert_to(Length::Centimeter)
+}
+
+pub fn mm_to_inch(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Millimeter).convert_to(Length::Inch)
+}
+
+pub fn cm_to_mm(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Centimeter).convert_to(Length::Millimeter)
+}
+
+pub fn inch_to_mm(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Inch).convert_to(Length::Millimeter)
+}
+
+pub fn cm_to_nm(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Centimeter).convert_to(Length::Nanometer)
+}
+
+pub fn nm_to_cm(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Nanometer).convert_to(Length::Centimeter)
+}
+
+pub fn mm_to_um(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Millimeter).convert_to(Length::Micrometer)
+}
+
+pub fn um_to_mm(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Micrometer).convert_to(Length::Millimeter)
+}
+
+pub fn ft_to_inch(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Foot).convert_to(Length::Inch)
+}
+
+pub fn ft_to_cm(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Foot).convert_to(Length::Centimeter)
+}
+
+pub fn ft_to_m(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Foot).convert_to(Length::Meter)
+}
+
+pub fn m_to_ft(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Meter).convert_to(Length::Foot)
+}
+
+pub fn yd_to_ft(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Yard).convert_to(Length::Foot)
+}
+
+pub fn yd_to_m(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Yard).convert_to(Length::Meter)
+}
+
+pub fn m_to_yd(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Meter).convert_to(Length::Yard)
+}
+
+pub fn mile_to_ft(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Miles).convert_to(Length::Foot)
+}
+
+pub fn mile_to_yd(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Miles).convert_to(Length::Yard)
+}
+
+pub fn nmi_to_km(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::NauticalMile).convert_to(Length::Kilometer)
+}
+
+pub fn km_to_nmi(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Kilometer).convert_to(Length::NauticalMile)
+}
+
+pub fn nmi_to_mile(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::NauticalMile).convert_to(Length::Miles)
+}
+
+pub fn angstrom_to_cm(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Angstrom).convert_to(Length::Centimeter)
+}
+
+pub fn cm_to_angstrom(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Centimeter).convert_to(Length::Angstrom)
+}
+
+pub fn thou_to_mm(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Thou).convert_to(Length::Millimeter)
+}
+
+pub fn mm_to_thou(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Millimeter).convert_to(Length::Thou)
+}
+
+pub fn chain_to_furlong(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Chain).convert_to(Length::Furlong)
+}
+
+pub fn furlong_to_mile(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Furlong).convert_to(Length::Miles)
+}
+
+pub fn furlong_to_m(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Furlong).convert_to(Length::Meter)
+}
+
+pub fn fathom_to_ft(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Fathom).convert_to(Length::Foot)
+}
+
+pub fn rod_to_yd(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Rod).convert_to(Length::Yard)
+}
+
+pub fn au_to_km(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::AstronomicalUnit).convert_to(Length::Kilometer)
+}
+
+pub fn ly_to_km(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::LightYear).convert_to(Length::Kilometer)
+}
+
+pub fn pc_to_km(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Parsec).convert_to(Length::Kilometer)
+}
+
+pub fn pc_to_ly(value: f64) -> Result<f64> {
+    LengthConverter::new(value, Length::Parsec).convert_to(Length::LightYear)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn test_f32_matches_f64() -> Result<()> {
+        for &from in Length::all() {
+            for &to in Length::all() {
+                let single = match LengthConverter::from_float(2.5f32, from).convert_to(to) {
+                    Ok(result) => result,
+                    // Only where f64 fails too, like parsecs in nanometers
+                    Err(_) => {
+                        assert!(LengthConverter::new(2.5, from).convert_to(to).is_err());
+                        continue;
+                    }
+                };
+                let double = LengthConverter::new(2.5, from).convert_to(to)?;
+                let error = ((single as f64 - double) / double).abs();
+                assert!(
+                    error <= f32::EPSILON as f64,
+                    "{:?} to {:?}: {} (f32) vs {} (f64)",
+                    from,
+                    to,
+                    single,
+                    double
+                );
+            }
+        }
+
+        let inches =
+            LengthConverter::from_float(2.54f32, Length::Centimeter).convert_to(Length::Inch)?;
+        assert!((inches - 1.0).abs() <= f32::EPSILON);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pathological_results() {
+        let error = |value: f64, from: Length, to: Length| {
+            LengthConverter::new(value, from)
+                .convert_to(to)
+                .unwrap_err()
+                .downcast::<ConversionError>()
+                .unwrap()
+        };
+
+        assert!(matches!(
+            error(f64::NAN, Length::Meter, Length::Foot),
+            ConversionError::InvalidResult(_)
+        ));
+        assert!(matches!(
+            error(-1.0, Length::Meter, Length::Foot),
+            ConversionError::InvalidResult(_)
+        ));
+        assert!(matches!(
+            error(1e20, Length::Parsec, Length::Nanometer),
+            ConversionError::Overflow(_)
+        ));
+        assert!(matches!(
+            error(1e-300, Length::Angstrom, Length::Parsec),
+            ConversionError::Underflow(_)
+        ));
+        assert!(matches!(
+            error(1e-290, Length::Angstrom, Length::Parsec),
+            ConversionError::PrecisionLoss(_)
+        ));
+
+        // Zero stays an ordinary result
+        assert_eq!(
+            LengthConverter::new(0.0, Length::Angstrom)
+                .convert_to(Length::Parsec)
+                .unwrap(),
+            0.0
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_results_serialize() -> Result<()> {
+        let results = LengthConverter::new(1.0, Length::Meter).convert_to_all()?;
+        let json = serde_json::to_value(&results)?;
+        assert_eq!(
+            json.as_object().unwrap().len(),
+            Length::value_variants().len()
+        );
+        assert_eq!(json["centimeter"], 100.0);
+        assert_eq!(json["kilometer"], 0.001);
+        Ok(())
+    }
+
+    #[test]
+    fn test_explained_formulas() -> Result<()> {
+        let (result, formula) =
+            LengthConverter::new(5.0, Length::Kilometer).convert_to_explained(Length::Miles)?;
+        assert_eq!(formula, "5 ÷ 1.609344");
+        assert_eq!(result, 5.0 / 1.609344);
+
+        let (result, formula) =
+            LengthConverter::new(2.0, Length::Miles).convert_to_explained(Length::Inch)?;
+        assert_eq!(formula, "2 × 63360");
+        assert_eq!(result, 126720.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_each_keeps_the_units_that_fit() -> Result<()> {
+        let converter = LengthConverter::new(1e12, Length::Parsec);
+        assert!(converter.convert_to_all().is_err());
+
+        let each = converter.convert_to_each();
+        assert_eq!(each.len(), Length::value_variants().len());
+        let failed: Vec<String> = each
+            .iter()
+            .filter(|(_, result)| result.is_err())
+            .map(|(unit, _)| format!("{:?}", unit))
+            .collect();
+        assert_eq!(
+            failed,
+            [
+                "Centimeter",
+                "Inch",
+                "Millimeter",
+                "Micrometer",
+                "Nanometer",
+                "Angstrom",
+                "Thou"
+            ]
+        );
+
+        let all = LengthConverter::new(1.0, Length::Kilometer).convert_to_all()?;
+        let each: Vec<(Length, f64)> = LengthConverter::new(1.0, Length::Kilometer)
+            .convert_to_each()
+            .into_iter()
+            .map(|(unit, result)| result.map(|value| (unit, value)))
+            .collect::<Result<_>>()?;
+        assert_eq!(format!("{:?}", each), format!("{:?}", all.rows()));
+        Ok(())
+    }
 
     #[test]
     fn test_cm_conversions() -> Result<()> {
@@ -251,6 +691,213 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_meter_conversions() -> Result<()> {
+        let converter = LengthConverter::new(1.0, Length::Meter);
+
+        assert_eq!(converter.convert_to(Length::Centimeter)?, 100.0);
+        assert_eq!(converter.convert_to(Length::Kilometer)?, 0.001);
+
+        let inch_result = converter.convert_to(Length::Inch)?;
+        assert!((inch_result - 39.3701).abs() < 0.001);
+
+        let miles_result = converter.convert_to(Length::Miles)?;
+        assert!((miles_result - 0.000621371).abs() < 0.000001);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_meter_helper_functions() -> Result<()> {
+        assert_eq!(m_to_cm(2.0)?, 200.0);
+        assert_eq!(cm_to_m(250.0)?, 2.5);
+        assert_eq!(m_to_km(1500.0)?, 1.5);
+        assert_eq!(km_to_m(1.5)?, 1500.0);
+        assert_eq!(inch_to_m(100.0)?, 2.54);
+        assert_eq!(mile_to_m(1.0)?, 1609.344);
+        assert_eq!(m_to_mile(1609.344)?, 1.0);
+        assert!((m_to_inch(0.0254)? - 1.0).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_millimeter_conversions() -> Result<()> {
+        assert_eq!(inch_to_mm(1.0)?, 25.4);
+        assert_eq!(mm_to_inch(25.4)?, 1.0);
+        assert_eq!(cm_to_mm(1.0)?, 10.0);
+        assert_eq!(mm_to_cm(10.0)?, 1.0);
+
+        let converter = LengthConverter::new(1.0, Length::Millimeter);
+        assert_eq!(converter.convert_to(Length::Meter)?, 0.001);
+
+        // Tiny results must not trip the result-range guard
+        assert_eq!(converter.convert_to(Length::Kilometer)?, 0.000001);
+        let miles_result = converter.convert_to(Length::Miles)?;
+        assert!(miles_result > 0.0 && (miles_result - 6.21371e-7).abs() < 1e-11);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_micro_and_nanometer_conversions() -> Result<()> {
+        assert_eq!(cm_to_nm(1.0)?, 10_000_000.0);
+        assert_eq!(nm_to_cm(10_000_000.0)?, 1.0);
+        assert_eq!(mm_to_um(1.0)?, 1000.0);
+        assert_eq!(um_to_mm(1000.0)?, 1.0);
+
+        let converter = LengthConverter::new(1.0, Length::Micrometer);
+        assert_eq!(converter.convert_to(Length::Nanometer)?, 1000.0);
+        assert_eq!(converter.convert_to(Length::Meter)?, 0.000001);
+
+        // Tiny-but-valid results are allowed through the result guard
+        let converter = LengthConverter::new(1.0, Length::Nanometer);
+        let miles_result = converter.convert_to(Length::Miles)?;
+        assert!(miles_result > 0.0 && (miles_result - 6.21371e-13).abs() < 1e-17);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_foot_and_yard_conversions() -> Result<()> {
+        assert_eq!(ft_to_inch(1.0)?, 12.0);
+        assert_eq!(ft_to_cm(1.0)?, 30.48);
+        assert_eq!(yd_to_ft(1.0)?, 3.0);
+        assert_eq!(yd_to_m(1.0)?, 0.9144);
+        assert!((ft_to_m(1.0)? - 0.3048).abs() < 1e-12);
+        assert!((m_to_ft(1.0)? - 3.28084).abs() < 1e-5);
+        assert!((m_to_yd(1.0)? - 1.09361).abs() < 1e-5);
+
+        // Must agree exactly with the existing 63360 in/mile constant
+        assert_eq!(mile_to_ft(1.0)?, 5280.0);
+        assert_eq!(mile_to_yd(1.0)?, 1760.0);
+        assert_eq!(
+            LengthConverter::new(5280.0, Length::Foot).convert_to(Length::Miles)?,
+            1.0
+        );
+        assert_eq!(
+            LengthConverter::new(1760.0, Length::Yard).convert_to(Length::Miles)?,
+            1.0
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_nautical_mile_conversions() -> Result<()> {
+        assert_eq!(nmi_to_km(1.0)?, 1.852);
+        assert_eq!(km_to_nmi(1.852)?, 1.0);
+        assert_eq!(
+            LengthConverter::new(1.0, Length::NauticalMile).convert_to(Length::Meter)?,
+            1852.0
+        );
+
+        // A nautical mile is not a statute mile
+        let statute = nmi_to_mile(1.0)?;
+        assert!((statute - 1.150779).abs() < 1e-6);
+        assert_ne!(
+            LengthConverter::new(10.0, Length::NauticalMile).convert_to(Length::Kilometer)?,
+            LengthConverter::new(10.0, Length::Miles).convert_to(Length::Kilometer)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_astronomical_conversions() -> Result<()> {
+        assert_eq!(au_to_km(1.0)?, 149_597_870.7);
+        assert!((ly_to_km(1.0)? - 9.4607e12).abs() / 9.4607e12 < 1e-5);
+        assert!((pc_to_km(1.0)? - 3.0857e13).abs() / 3.0857e13 < 1e-5);
+        assert!((pc_to_ly(1.0)? - 3.26156).abs() < 1e-5);
+
+        // Cosmic scales in small units must clear the result-range guard
+        let converter = LengthConverter::new(1.0, Length::LightYear);
+        assert!((converter.convert_to(Length::Centimeter)? - 9.4607e17).abs() / 9.4607e17 < 1e-5);
+        let converter = LengthConverter::new(1.0, Length::Parsec);
+        assert!(converter.convert_to(Length::Nanometer)? > 3e25);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_angstrom_and_thou_conversions() -> Result<()> {
+        // Tiny results must survive the result checks rather than collapse to 0
+        assert!((angstrom_to_cm(1.0)? - 1e-8).abs() < 1e-20);
+        assert!((cm_to_angstrom(1.0)? - 1e8).abs() < 1e-4);
+        let converter = LengthConverter::new(1.0, Length::Angstrom);
+        assert!((converter.convert_to(Length::Nanometer)? - 0.1).abs() < 1e-12);
+        assert!(converter.convert_to(Length::Miles)? > 0.0);
+
+        assert!((thou_to_mm(1.0)? - 0.0254).abs() < 1e-12);
+        assert!((mm_to_thou(0.0254)? - 1.0).abs() < 1e-9);
+        let converter = LengthConverter::new(1000.0, Length::Thou);
+        assert!((converter.convert_to(Length::Inch)? - 1.0).abs() < 1e-12);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_surveyor_conversions() -> Result<()> {
+        assert_eq!(chain_to_furlong(10.0)?, 1.0);
+        assert_eq!(furlong_to_mile(8.0)?, 1.0);
+        assert_eq!(furlong_to_m(1.0)?, 201.168);
+        assert_eq!(fathom_to_ft(1.0)?, 6.0);
+        assert_eq!(rod_to_yd(1.0)?, 5.5);
+
+        let converter = LengthConverter::new(1.0, Length::Chain);
+        assert_eq!(converter.convert_to(Length::Rod)?, 4.0);
+        assert_eq!(converter.convert_to(Length::Meter)?, 20.1168);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_precomputed_convert_to_all_matches_convert_to() -> Result<()> {
+        LengthConverter::warm_up();
+
+        for unit in Length::value_variants() {
+            for value in [0.0, 1.0, 2.5, 12.0, 1609.344, 3000.7] {
+                let converter = LengthConverter::new(value, *unit);
+                let all = converter.convert_to_all()?;
+                let precomputed = [
+                    all.centimeter,
+                    all.inch,
+                    all.kilometer,
+                    all.miles,
+                    all.meter,
+                    all.millimeter,
+                    all.micrometer,
+                    all.nanometer,
+                    all.foot,
+                    all.yard,
+                    all.nautical_mile,
+                    all.light_year,
+                    all.astronomical_unit,
+                    all.parsec,
+                    all.angstrom,
+                    all.thou,
+                    all.furlong,
+                    all.fathom,
+                    all.rod,
+                    all.chain,
+                ];
+
+                assert_eq!(precomputed.len(), Length::value_variants().len());
+                for (target, result) in Length::value_variants().iter().zip(precomputed) {
+                    let on_the_fly = converter.convert_to(*target)?;
+                    assert_eq!(
+                        result.to_bits(),
+                        on_the_fly.to_bits(),
+                        "{} {:?} to {:?}",
+                        value,
+                        unit,
+                        target
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_invalid_conversions() {
         // Test NaN handling
@@ -0,0 +1,271 @@
+use super::Mass;
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use std::fmt::{Display, Result as FmtResult};
+use std::sync::OnceLock;
+
+/// Largest mass result accepted before the input is considered unrealistic
+const MAX_MASS_RESULT: f64 = 1e18;
+
+/// `nanograms_per_unit` for every `Mass`, indexed by variant
+static FACTOR_TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+
+pub struct MassConverter {
+    pub value: f64,
+    pub unit: Mass,
+}
+
+pub struct MassResults {
+    pub gram: f64,
+    pub kilogram: f64,
+    pub milligram: f64,
+    pub tonne: f64,
+    pub pound: f64,
+    pub ounce: f64,
+    pub stone: f64,
+}
+
+impl MassConverter {
+    pub fn new(value: f64, unit: Mass) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn convert_to(&self, target_unit: Mass) -> Result<f64> {
+        let from = Self::nanograms_per_unit(&self.unit);
+        let to = Self::nanograms_per_unit(&target_unit);
+        self.scale(from, to, &target_unit)
+    }
+
+    /// Builds the factor table used by `convert_to_all` ahead of time
+    pub fn warm_up() {
+        Self::factor_table();
+    }
+
+    fn factor_table() -> &'static [f64] {
+        FACTOR_TABLE.get_or_init(|| {
+            Mass::value_variants()
+                .iter()
+                .map(Self::nanograms_per_unit)
+                .collect()
+        })
+    }
+
+    fn scale(&self, from: f64, to: f64, target_unit: &Mass) -> Result<f64> {
+        // Always scale by a ratio of at least 1 so exact relationships stay
+        // exact (1 st is 14 lb, 1 lb is 16 oz)
+        let result = if from >= to {
+            self.value * (from / to)
+        } else {
+            self.value / (to / from)
+        };
+
+        self.check_conversion_result(result, &format!("{:?} to {:?}", self.unit, target_unit))
+    }
+
+    /// Size of one unit in nanograms. The pound is defined as exactly
+    /// 0.45359237 kg, so nanograms keep the ounce (1/16 lb) a whole number.
+    fn nanograms_per_unit(unit: &Mass) -> f64 {
+        match unit {
+            Mass::Gram => 1e9,
+            Mass::Kilogram => 1e12,
+            Mass::Milligram => 1e6,
+            Mass::Tonne => 1e15,
+            Mass::Pound => 453_592_370_000.0,
+            Mass::Ounce => 28_349_523_125.0,
+            Mass::Stone => 6_350_293_180_000.0,
+        }
+    }
+
+    fn check_conversion_result(&self, result: f64, conversion_type: &str) -> Result<f64> {
+        if result.is_nan() {
+            return Err(anyhow!(
+                "{} conversion resulted in NaN. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result.is_infinite() {
+            return Err(anyhow!(
+                "{} conversion resulted in infinity. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result < 0.0 {
+            return Err(anyhow!(
+                "{} conversion resulted in negative mass: {:.6}. This should not happen with positive input.",
+                conversion_type, result
+            ));
+        }
+
+        if result > MAX_MASS_RESULT {
+            return Err(anyhow!(
+                "{} conversion resulted in an unrealistically large mass: {:.2}. Please check your input.",
+                conversion_type, result
+            ));
+        }
+
+        Ok(result)
+    }
+
+    /// Same results as calling `convert_to` for every unit, with the factors
+    /// taken from a table built once
+    pub fn convert_to_all(&self) -> Result<MassResults> {
+        let factors = Self::factor_table();
+        let from = factors[self.unit as usize];
+        let convert = |target: Mass| self.scale(from, factors[target as usize], &target);
+
+        Ok(MassResults {
+            gram: convert(Mass::Gram)?,
+            kilogram: convert(Mass::Kilogram)?,
+            milligram: convert(Mass::Milligram)?,
+            tonne: convert(Mass::Tonne)?,
+            pound: convert(Mass::Pound)?,
+            ounce: convert(Mass::Ounce)?,
+            stone: convert(Mass::Stone)?,
+        })
+    }
+}
+
+impl MassResults {
+    /// Every unit with its value, in the order `convert_to_all` fills them
+    pub fn rows(&self) -> Vec<(Mass, f64)> {
+        vec![
+            (Mass::Gram, self.gram),
+            (Mass::Kilogram, self.kilogram),
+            (Mass::Milligram, self.milligram),
+            (Mass::Tonne, self.tonne),
+            (Mass::Pound, self.pound),
+            (Mass::Ounce, self.ounce),
+            (Mass::Stone, self.stone),
+        ]
+    }
+}
+
+impl Display for MassResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "Gram: {:.6} g, Kilogram: {:.6} kg, Milligram: {:.6} mg, Tonne: {:.6} t, Pound: {:.6} lb, Ounce: {:.6} oz, Stone: {:.6} st",
+            self.gram, self.kilogram, self.milligram, self.tonne, self.pound, self.ounce, self.stone
+        )
+    }
+}
+
+// Helper functions with error handling
+pub fn kg_to_g(value: f64) -> Result<f64> {
+    MassConverter::new(value, Mass::Kilogram).convert_to(Mass::Gram)
+}
+
+pub fn g_to_kg(value: f64) -> Result<f64> {
+    MassConverter::new(value, Mass::Gram).convert_to(Mass::Kilogram)
+}
+
+pub fn kg_to_lb(value: f64) -> Result<f64> {
+    MassConverter::new(value, Mass::Kilogram).convert_to(Mass::Pound)
+}
+
+pub fn lb_to_kg(value: f64) -> Result<f64> {
+    MassConverter::new(value, Mass::Pound).convert_to(Mass::Kilogram)
+}
+
+pub fn lb_to_oz(value: f64) -> Result<f64> {
+    MassConverter::new(value, Mass::Pound).convert_to(Mass::Ounce)
+}
+
+pub fn oz_to_g(value: f64) -> Result<f64> {
+    MassConverter::new(value, Mass::Ounce).convert_to(Mass::Gram)
+}
+
+pub fn g_to_oz(value: f64) -> Result<f64> {
+    MassConverter::new(value, Mass::Gram).convert_to(Mass::Ounce)
+}
+
+pub fn st_to_lb(value: f64) -> Result<f64> {
+    MassConverter::new(value, Mass::Stone).convert_to(Mass::Pound)
+}
+
+pub fn st_to_kg(value: f64) -> Result<f64> {
+    MassConverter::new(value, Mass::Stone).convert_to(Mass::Kilogram)
+}
+
+pub fn t_to_kg(value: f64) -> Result<f64> {
+    MassConverter::new(value, Mass::Tonne).convert_to(Mass::Kilogram)
+}
+
+pub fn mg_to_g(value: f64) -> Result<f64> {
+    MassConverter::new(value, Mass::Milligram).convert_to(Mass::Gram)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kilogram_conversions() -> Result<()> {
+        let converter = MassConverter::new(1.0, Mass::Kilogram);
+
+        assert_eq!(converter.convert_to(Mass::Gram)?, 1000.0);
+        assert_eq!(converter.convert_to(Mass::Milligram)?, 1_000_000.0);
+        assert_eq!(converter.convert_to(Mass::Tonne)?, 0.001);
+
+        let pound_result = converter.convert_to(Mass::Pound)?;
+        assert!((pound_result - 2.2046).abs() < 0.0001);
+
+        let ounce_result = converter.convert_to(Mass::Ounce)?;
+        assert!((ounce_result - 35.274).abs() < 0.001);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_imperial_conversions() -> Result<()> {
+        assert_eq!(lb_to_kg(1.0)?, 0.45359237);
+        assert_eq!(lb_to_oz(1.0)?, 16.0);
+        assert_eq!(st_to_lb(1.0)?, 14.0);
+        assert_eq!(oz_to_g(1.0)?, 28.349523125);
+        assert!((st_to_kg(1.0)? - 6.35029318).abs() < 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trips() -> Result<()> {
+        assert_eq!(g_to_kg(kg_to_g(1.0)?)?, 1.0);
+        assert!((lb_to_kg(kg_to_lb(1.0)?)? - 1.0).abs() < 1e-12);
+        assert!((oz_to_g(g_to_oz(250.0)?)? - 250.0).abs() < 1e-9);
+        assert_eq!(t_to_kg(1.0)?, 1000.0);
+        assert_eq!(mg_to_g(1000.0)?, 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_all() -> Result<()> {
+        MassConverter::warm_up();
+        let converter = MassConverter::new(1.0, Mass::Kilogram);
+        let all = converter.convert_to_all()?;
+
+        assert_eq!(all.gram, 1000.0);
+        assert_eq!(
+            all.pound.to_bits(),
+            converter.convert_to(Mass::Pound)?.to_bits()
+        );
+        assert!(all.to_string().contains("Kilogram: 1.000000 kg"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_conversions() {
+        let converter = MassConverter::new(f64::NAN, Mass::Gram);
+        assert!(converter.convert_to(Mass::Kilogram).is_err());
+
+        let converter = MassConverter::new(f64::INFINITY, Mass::Gram);
+        assert!(converter.convert_to(Mass::Kilogram).is_err());
+
+        let converter = MassConverter::new(-1.0, Mass::Gram);
+        assert!(converter.convert_to(Mass::Kilogram).is_err());
+    }
+}
@@ -1,17 +1,86 @@
+mod angle;
+mod area;
+mod aspect;
+mod builder;
+mod data;
+mod energy;
+mod frequency;
+mod fuel;
+mod gas;
+mod geo;
 mod length;
+mod mass;
+mod pace;
+mod power;
+mod pressure;
+#[cfg(test)]
+mod roundtrip;
+mod shoe;
+mod speed;
 mod temperature;
+mod time;
+mod validate;
+mod volume;
+mod wire;
 
+use crate::errors::UnitParseError;
+use crate::units::{lookup_length_unit, lookup_temperature_unit, RecognizedUnit};
+pub use angle::*;
+use anyhow::Result;
+pub use area::*;
+pub use aspect::*;
+pub use builder::*;
 use clap::ValueEnum;
+pub use data::*;
+pub use energy::*;
+pub use frequency::*;
+pub use fuel::*;
+pub use gas::*;
+pub use geo::*;
 pub use length::*;
+pub use mass::*;
+use num_traits::Float;
+pub use pace::*;
+pub use power::*;
+pub use pressure::*;
+pub use shoe::*;
+pub use speed::*;
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
 pub use temperature::*;
+pub use time::*;
+pub use validate::*;
+pub use volume::*;
+pub use wire::*;
 
-#[derive(Debug, Clone, ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "kebab-case")
+)]
 pub enum Length {
     Centimeter,
     Inch,
     Kilometer,
     Miles,
+    Meter,
+    Millimeter,
+    Micrometer,
+    Nanometer,
+    Foot,
+    Yard,
+    NauticalMile,
+    LightYear,
+    AstronomicalUnit,
+    Parsec,
+    Angstrom,
+    Thou,
+    Furlong,
+    Fathom,
+    Rod,
+    Chain,
 }
 impl Display for Length {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
@@ -20,15 +89,339 @@ impl Display for Length {
             Length::Inch => write!(f, "in"),
             Length::Kilometer => write!(f, "km"),
             Length::Miles => write!(f, "mi"),
+            Length::Meter => write!(f, "m"),
+            Length::Millimeter => write!(f, "mm"),
+            Length::Micrometer => write!(f, "µm"),
+            Length::Nanometer => write!(f, "nm"),
+            Length::Foot => write!(f, "ft"),
+            Length::Yard => write!(f, "yd"),
+            Length::NauticalMile => write!(f, "nmi"),
+            Length::LightYear => write!(f, "ly"),
+            Length::AstronomicalUnit => write!(f, "AU"),
+            Length::Parsec => write!(f, "pc"),
+            Length::Angstrom => write!(f, "Å"),
+            Length::Thou => write!(f, "thou"),
+            Length::Furlong => write!(f, "fur"),
+            Length::Fathom => write!(f, "ftm"),
+            Length::Rod => write!(f, "rd"),
+            Length::Chain => write!(f, "ch"),
         }
     }
 }
 
-#[derive(Debug, Clone, ValueEnum)]
+impl Length {
+    /// Every length unit, in declaration order
+    pub fn all() -> &'static [Length] {
+        Length::value_variants()
+    }
+}
+
+impl FromStr for Length {
+    type Err = UnitParseError;
+
+    /// Reads a unit name, plural or abbreviation in any case, e.g. `metre`
+    /// or `KM`
+    fn from_str(unit: &str) -> Result<Self, Self::Err> {
+        let unit = unit.trim();
+        if unit.is_empty() {
+            return Err(UnitParseError::EmptyInput);
+        }
+
+        lookup_length_unit(unit).ok_or_else(|| UnitParseError::UnknownUnit(unit.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Mass {
+    Gram,
+    Kilogram,
+    Milligram,
+    Tonne,
+    Pound,
+    Ounce,
+    Stone,
+}
+
+impl Display for Mass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Mass::Gram => write!(f, "g"),
+            Mass::Kilogram => write!(f, "kg"),
+            Mass::Milligram => write!(f, "mg"),
+            Mass::Tonne => write!(f, "t"),
+            Mass::Pound => write!(f, "lb"),
+            Mass::Ounce => write!(f, "oz"),
+            Mass::Stone => write!(f, "st"),
+        }
+    }
+}
+
+/// Quart, pint and fluid ounce are the US customary (liquid) units. The US
+/// and imperial gallons differ by about 20% and are kept apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Volume {
+    Liter,
+    Milliliter,
+    CubicMeter,
+    #[value(name = "gallon-us")]
+    GallonUS,
+    GallonImperial,
+    Quart,
+    Pint,
+    FluidOunce,
+}
+
+impl Display for Volume {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Volume::Liter => write!(f, "L"),
+            Volume::Milliliter => write!(f, "mL"),
+            Volume::CubicMeter => write!(f, "m³"),
+            Volume::GallonUS => write!(f, "gal"),
+            Volume::GallonImperial => write!(f, "imp gal"),
+            Volume::Quart => write!(f, "qt"),
+            Volume::Pint => write!(f, "pt"),
+            Volume::FluidOunce => write!(f, "fl oz"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Area {
+    SquareMeter,
+    SquareKilometer,
+    SquareCentimeter,
+    Hectare,
+    Acre,
+    SquareFoot,
+    SquareMile,
+}
+
+impl Display for Area {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Area::SquareMeter => write!(f, "m²"),
+            Area::SquareKilometer => write!(f, "km²"),
+            Area::SquareCentimeter => write!(f, "cm²"),
+            Area::Hectare => write!(f, "ha"),
+            Area::Acre => write!(f, "ac"),
+            Area::SquareFoot => write!(f, "ft²"),
+            Area::SquareMile => write!(f, "mi²"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Speed {
+    MeterPerSecond,
+    KilometerPerHour,
+    MilePerHour,
+    Knot,
+    FootPerSecond,
+}
+
+impl Display for Speed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Speed::MeterPerSecond => write!(f, "m/s"),
+            Speed::KilometerPerHour => write!(f, "km/h"),
+            Speed::MilePerHour => write!(f, "mph"),
+            Speed::Knot => write!(f, "kn"),
+            Speed::FootPerSecond => write!(f, "ft/s"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Time {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Millisecond,
+    /// Average month (30.44 days); calendar months run 28 to 31 days
+    Month,
+    /// Julian year (365.25 days)
+    Year,
+}
+
+impl Display for Time {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Time::Second => write!(f, "s"),
+            Time::Minute => write!(f, "min"),
+            Time::Hour => write!(f, "h"),
+            Time::Day => write!(f, "d"),
+            Time::Week => write!(f, "wk"),
+            Time::Millisecond => write!(f, "ms"),
+            Time::Month => write!(f, "mo"),
+            Time::Year => write!(f, "yr"),
+        }
+    }
+}
+
+/// Digital storage sizes. Kilobyte and up use SI prefixes (1 kB = 1000 B);
+/// kibibyte and up use IEC binary prefixes (1 KiB = 1024 B).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Data {
+    Bit,
+    Byte,
+    Kilobyte,
+    Megabyte,
+    Gigabyte,
+    Terabyte,
+    Kibibyte,
+    Mebibyte,
+    Gibibyte,
+    Tebibyte,
+}
+
+impl Display for Data {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Data::Bit => write!(f, "bit"),
+            Data::Byte => write!(f, "B"),
+            Data::Kilobyte => write!(f, "kB"),
+            Data::Megabyte => write!(f, "MB"),
+            Data::Gigabyte => write!(f, "GB"),
+            Data::Terabyte => write!(f, "TB"),
+            Data::Kibibyte => write!(f, "KiB"),
+            Data::Mebibyte => write!(f, "MiB"),
+            Data::Gibibyte => write!(f, "GiB"),
+            Data::Tebibyte => write!(f, "TiB"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Pressure {
+    Pascal,
+    Kilopascal,
+    Bar,
+    Atmosphere,
+    Psi,
+    MmHg,
+}
+
+impl Display for Pressure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Pressure::Pascal => write!(f, "Pa"),
+            Pressure::Kilopascal => write!(f, "kPa"),
+            Pressure::Bar => write!(f, "bar"),
+            Pressure::Atmosphere => write!(f, "atm"),
+            Pressure::Psi => write!(f, "psi"),
+            Pressure::MmHg => write!(f, "mmHg"),
+        }
+    }
+}
+
+/// Calorie is the small calorie (4.184 J). The "Calorie" on nutrition labels
+/// is the kilocalorie, so `kcal` and a capitalized `Cal` both mean 4184 J.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Energy {
+    Joule,
+    Kilojoule,
+    Calorie,
+    Kilocalorie,
+    WattHour,
+    KilowattHour,
+    Btu,
+}
+
+impl Display for Energy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Energy::Joule => write!(f, "J"),
+            Energy::Kilojoule => write!(f, "kJ"),
+            Energy::Calorie => write!(f, "cal"),
+            Energy::Kilocalorie => write!(f, "kcal"),
+            Energy::WattHour => write!(f, "Wh"),
+            Energy::KilowattHour => write!(f, "kWh"),
+            Energy::Btu => write!(f, "BTU"),
+        }
+    }
+}
+
+/// Bare "horsepower" is mechanical horsepower (550 ft·lbf/s, about 745.7 W).
+/// Metric horsepower (PS) is about 1.4% smaller, so the two are kept apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Power {
+    Watt,
+    Kilowatt,
+    Megawatt,
+    HorsepowerMechanical,
+    HorsepowerMetric,
+}
+
+impl Display for Power {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Power::Watt => write!(f, "W"),
+            Power::Kilowatt => write!(f, "kW"),
+            Power::Megawatt => write!(f, "MW"),
+            Power::HorsepowerMechanical => write!(f, "hp"),
+            Power::HorsepowerMetric => write!(f, "PS"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Frequency {
+    Hertz,
+    Kilohertz,
+    Megahertz,
+    Gigahertz,
+    Rpm,
+}
+
+impl Display for Frequency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Frequency::Hertz => write!(f, "Hz"),
+            Frequency::Kilohertz => write!(f, "kHz"),
+            Frequency::Megahertz => write!(f, "MHz"),
+            Frequency::Gigahertz => write!(f, "GHz"),
+            Frequency::Rpm => write!(f, "rpm"),
+        }
+    }
+}
+
+/// Fuel economy as distance per volume (mpg, km/L) or volume per distance
+/// (L/100km). Plain mpg is per US gallon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum FuelEconomy {
+    MpgUS,
+    MpgImperial,
+    L100km,
+    KmPerLiter,
+}
+
+impl Display for FuelEconomy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            FuelEconomy::MpgUS => write!(f, "mpg"),
+            FuelEconomy::MpgImperial => write!(f, "mpg (imp)"),
+            FuelEconomy::L100km => write!(f, "L/100km"),
+            FuelEconomy::KmPerLiter => write!(f, "km/L"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "kebab-case")
+)]
 pub enum Degree {
     Celsius,
     Fahrenheit,
     Kelvin,
+    Delisle,
+    Newton,
+    GasMark,
 }
 
 impl Display for Degree {
@@ -37,6 +430,603 @@ impl Display for Degree {
             Degree::Celsius => write!(f, "°C"),
             Degree::Fahrenheit => write!(f, "°F"),
             Degree::Kelvin => write!(f, "K"),
+            Degree::Delisle => write!(f, "°De"),
+            Degree::Newton => write!(f, "°N"),
+            Degree::GasMark => write!(f, "gas mark"),
+        }
+    }
+}
+
+impl Degree {
+    /// Every temperature unit, in declaration order
+    pub fn all() -> &'static [Degree] {
+        Degree::value_variants()
+    }
+}
+
+impl FromStr for Degree {
+    type Err = UnitParseError;
+
+    /// Reads a unit name, abbreviation or symbol in any case, e.g. `celsius`,
+    /// `C` or `°C`
+    fn from_str(unit: &str) -> Result<Self, Self::Err> {
+        let unit = unit.trim();
+        if unit.is_empty() {
+            return Err(UnitParseError::EmptyInput);
+        }
+
+        lookup_temperature_unit(unit).ok_or_else(|| UnitParseError::UnknownUnit(unit.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum WireGauge {
+    Awg,
+    Millimeter,
+}
+
+impl Display for WireGauge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            WireGauge::Awg => write!(f, "AWG"),
+            WireGauge::Millimeter => write!(f, "mm"),
+        }
+    }
+}
+
+/// Running paces (minutes per distance) and the speeds they correspond to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Pace {
+    #[value(alias = "min/km")]
+    MinPerKm,
+    #[value(alias = "min/mi")]
+    MinPerMile,
+    #[value(alias = "km/h")]
+    KmPerHour,
+    #[value(alias = "mph")]
+    MilePerHour,
+}
+
+impl Display for Pace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Pace::MinPerKm => write!(f, "min/km"),
+            Pace::MinPerMile => write!(f, "min/mi"),
+            Pace::KmPerHour => write!(f, "km/h"),
+            Pace::MilePerHour => write!(f, "mph"),
+        }
+    }
+}
+
+/// Shoe size systems handled by the `shoesize` command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum ShoeSystem {
+    Us,
+    Uk,
+    Eu,
+}
+
+impl Display for ShoeSystem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            ShoeSystem::Us => write!(f, "US"),
+            ShoeSystem::Uk => write!(f, "UK"),
+            ShoeSystem::Eu => write!(f, "EU"),
+        }
+    }
+}
+
+/// US shoe sizes differ for men and women, so every conversion needs one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum ShoeGender {
+    Men,
+    Women,
+}
+
+impl Display for ShoeGender {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            ShoeGender::Men => write!(f, "men's"),
+            ShoeGender::Women => write!(f, "women's"),
+        }
+    }
+}
+
+/// What the `aspect` command converts a resolution, pixel count or size into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum AspectTarget {
+    Ratio,
+    Inch,
+    Px,
+}
+
+impl Display for AspectTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            AspectTarget::Ratio => write!(f, "aspect ratio"),
+            AspectTarget::Inch => write!(f, "in"),
+            AspectTarget::Px => write!(f, "px"),
         }
     }
 }
+
+/// Units serialize as their canonical names (`"celsius"`, `"nautical-mile"`)
+/// and deserialize through `FromStr`, so every alias the CLI takes is read
+/// too
+#[cfg(feature = "serde")]
+fn deserialize_unit<'de, D, U>(deserializer: D) -> Result<U, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    U: FromStr<Err = UnitParseError>,
+{
+    let unit = <String as serde::Deserialize>::deserialize(deserializer)?;
+    unit.parse().map_err(serde::de::Error::custom)
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Degree {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_unit(deserializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Length {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_unit(deserializer)
+    }
+}
+
+/// A value in one unit of a category, convertible to the category's other
+/// units. Every category with a `convert_to_all` implements it, so generic
+/// code and tests can work across categories.
+pub trait Converter {
+    /// The category's unit enum, e.g. `Degree`
+    type Unit;
+    /// The value in every unit at once, e.g. `TemperatureResults`
+    type Results;
+
+    fn convert_to(&self, target: Self::Unit) -> Result<f64>;
+    fn convert_to_all(&self) -> Result<Self::Results>;
+
+    /// `convert_to_all` keyed by each unit's name (`"celsius"`, `"inch"`),
+    /// for callers that iterate the results without knowing the struct
+    fn convert_to_all_map(&self) -> Result<HashMap<String, f64>>;
+}
+
+/// `x` as the float type of a generic converter. The constants passed in
+/// are all finite and within `f32` range, so the cast can't fail.
+pub(crate) fn float<T: Float>(x: f64) -> T {
+    T::from(x).expect("conversion constants fit every float type")
+}
+
+/// A category's unit enum, tied to the converter that converts it and, as a
+/// `RecognizedUnit`, to the input checks of its `convert_*` function
+pub trait Unit: Sized + Into<RecognizedUnit> {
+    type Converter: Converter<Unit = Self>;
+
+    /// The converter for `value` in `unit`, e.g. `TemperatureConverter`
+    fn converter(value: f64, unit: Self) -> Self::Converter;
+}
+
+/// Results rows keyed by unit name, as `convert_to_all_map` returns them
+fn unit_map<U: ValueEnum>(rows: Vec<(U, f64)>) -> HashMap<String, f64> {
+    rows.into_iter()
+        .filter_map(|(unit, value)| {
+            let name = unit.to_possible_value()?.get_name().to_string();
+            Some((name, value))
+        })
+        .collect()
+}
+
+/// Implements `Converter` by forwarding to each converter's own methods, and
+/// `Unit` for the converter's unit enum
+macro_rules! impl_converter {
+    ($($converter:ty => $unit:ty, $results:ty;)*) => {
+        $(
+            impl Converter for $converter {
+                type Unit = $unit;
+                type Results = $results;
+
+                fn convert_to(&self, target: $unit) -> Result<f64> {
+                    <$converter>::convert_to(self, target)
+                }
+
+                fn convert_to_all(&self) -> Result<$results> {
+                    <$converter>::convert_to_all(self)
+                }
+
+                fn convert_to_all_map(&self) -> Result<HashMap<String, f64>> {
+                    Ok(unit_map(<$converter>::convert_to_all(self)?.rows()))
+                }
+            }
+
+            impl Unit for $unit {
+                type Converter = $converter;
+
+                fn converter(value: f64, unit: $unit) -> $converter {
+                    <$converter>::new(value, unit)
+                }
+            }
+        )*
+    };
+}
+
+impl_converter! {
+    AngleConverter => Angle, AngleResults;
+    AreaConverter => Area, AreaResults;
+    DataConverter => Data, DataResults;
+    EnergyConverter => Energy, EnergyResults;
+    FrequencyConverter => Frequency, FrequencyResults;
+    FuelEconomyConverter => FuelEconomy, FuelEconomyResults;
+    LengthConverter => Length, LengthResults;
+    MassConverter => Mass, MassResults;
+    PowerConverter => Power, PowerResults;
+    PressureConverter => Pressure, PressureResults;
+    SpeedConverter => Speed, SpeedResults;
+    TemperatureConverter => Degree, TemperatureResults;
+    TimeConverter => Time, TimeResults;
+    VolumeConverter => Volume, VolumeResults;
+}
+
+/// Order of the units in a convert-to-all listing
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum UnitOrder {
+    /// The order the units are declared in
+    #[default]
+    Declaration,
+    /// Alphabetical by unit symbol, so adding a unit never moves the others
+    Symbol,
+}
+
+/// Every unit of a category, in the requested order. Ties between equal
+/// symbols keep their declaration order.
+pub fn ordered_units<U: ValueEnum + Display>(order: UnitOrder) -> Vec<U> {
+    let mut units = U::value_variants().to_vec();
+    if order == UnitOrder::Symbol {
+        units.sort_by_cached_key(|unit| unit.to_string().to_lowercase());
+    }
+    units
+}
+
+/// `rows` (a unit and the value in it) rearranged into `ordered_units`
+/// order. Units without a row, like gas mark in temperature results, are
+/// left out.
+pub fn in_unit_order<U: ValueEnum + Display + PartialEq>(
+    rows: Vec<(U, f64)>,
+    order: UnitOrder,
+) -> Vec<(U, f64)> {
+    ordered_units::<U>(order)
+        .into_iter()
+        .filter_map(|unit| {
+            let value = rows.iter().find(|(row, _)| *row == unit)?.1;
+            Some((unit, value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Converting any value to the unit it is already in gives it back
+    fn assert_same_unit_identity<C>(new: fn(f64, C::Unit) -> C)
+    where
+        C: Converter,
+        C::Unit: ValueEnum + std::fmt::Debug,
+    {
+        for unit in C::Unit::value_variants() {
+            // In range for every unit, gas marks included
+            for value in [1.0, 5.0] {
+                let result = new(value, unit.clone())
+                    .convert_to(unit.clone())
+                    .unwrap_or_else(|e| panic!("{:?}: {}", unit, e));
+                assert!(
+                    (result - value).abs() <= 1e-12 * value,
+                    "{:?}: {} came back as {}",
+                    unit,
+                    value,
+                    result
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_same_unit_identity() {
+        assert_same_unit_identity(AngleConverter::new);
+        assert_same_unit_identity(AreaConverter::new);
+        assert_same_unit_identity(DataConverter::new);
+        assert_same_unit_identity(EnergyConverter::new);
+        assert_same_unit_identity(FrequencyConverter::new);
+        assert_same_unit_identity(FuelEconomyConverter::new);
+        assert_same_unit_identity(LengthConverter::new);
+        assert_same_unit_identity(MassConverter::new);
+        assert_same_unit_identity(PowerConverter::new);
+        assert_same_unit_identity(PressureConverter::new);
+        assert_same_unit_identity(SpeedConverter::new);
+        assert_same_unit_identity(TemperatureConverter::new);
+        assert_same_unit_identity(TimeConverter::new);
+        assert_same_unit_identity(VolumeConverter::new);
+    }
+
+    /// `convert_to_all_map` holds each of the typed results' units, under
+    /// its name, with the same value
+    fn assert_map_matches_results<C, R>(converter: C, rows: R)
+    where
+        C: Converter,
+        C::Unit: ValueEnum,
+        R: Fn(&C::Results) -> Vec<(C::Unit, f64)>,
+    {
+        let map = converter.convert_to_all_map().unwrap();
+        let rows = rows(&converter.convert_to_all().unwrap());
+        assert_eq!(map.len(), rows.len());
+        for (unit, value) in rows {
+            let name = unit.to_possible_value().unwrap().get_name().to_string();
+            assert_eq!(map.get(&name), Some(&value), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_convert_to_all_map() {
+        assert_map_matches_results(
+            AngleConverter::new(2.0, Angle::value_variants()[0]),
+            AngleResults::rows,
+        );
+        assert_map_matches_results(
+            AreaConverter::new(2.0, Area::value_variants()[0]),
+            AreaResults::rows,
+        );
+        assert_map_matches_results(
+            DataConverter::new(2.0, Data::value_variants()[0]),
+            DataResults::rows,
+        );
+        assert_map_matches_results(
+            EnergyConverter::new(2.0, Energy::value_variants()[0]),
+            EnergyResults::rows,
+        );
+        assert_map_matches_results(
+            FrequencyConverter::new(2.0, Frequency::value_variants()[0]),
+            FrequencyResults::rows,
+        );
+        assert_map_matches_results(
+            FuelEconomyConverter::new(2.0, FuelEconomy::value_variants()[0]),
+            FuelEconomyResults::rows,
+        );
+        assert_map_matches_results(
+            LengthConverter::new(2.0, Length::value_variants()[0]),
+            LengthResults::rows,
+        );
+        assert_map_matches_results(
+            MassConverter::new(2.0, Mass::value_variants()[0]),
+            MassResults::rows,
+        );
+        assert_map_matches_results(
+            PowerConverter::new(2.0, Power::value_variants()[0]),
+            PowerResults::rows,
+        );
+        assert_map_matches_results(
+            PressureConverter::new(2.0, Pressure::value_variants()[0]),
+            PressureResults::rows,
+        );
+        assert_map_matches_results(
+            SpeedConverter::new(2.0, Speed::value_variants()[0]),
+            SpeedResults::rows,
+        );
+        assert_map_matches_results(
+            TimeConverter::new(2.0, Time::value_variants()[0]),
+            TimeResults::rows,
+        );
+        assert_map_matches_results(
+            VolumeConverter::new(2.0, Volume::value_variants()[0]),
+            VolumeResults::rows,
+        );
+        // Gas mark only covers oven temperatures, so it is left out of both
+        let temperatures = || TemperatureConverter::new(100.0, Degree::Celsius);
+        assert_map_matches_results(temperatures(), TemperatureResults::rows);
+        let map = temperatures().convert_to_all_map().unwrap();
+        assert_eq!(map.len(), Degree::value_variants().len() - 1);
+        assert_eq!(map["fahrenheit"], 212.0);
+        assert!(!map.contains_key("gas-mark"));
+
+        let lengths = LengthConverter::new(1.0, Length::Meter)
+            .convert_to_all_map()
+            .unwrap();
+        assert_eq!(lengths.len(), Length::value_variants().len());
+        assert_eq!(lengths["centimeter"], 100.0);
+    }
+
+    #[test]
+    fn test_parse_degree() {
+        let aliases = [
+            ("celsius", "°C"),
+            ("c", "°C"),
+            ("fahrenheit", "°F"),
+            ("f", "°F"),
+            ("kelvin", "K"),
+            ("k", "K"),
+            ("delisle", "°De"),
+            ("de", "°De"),
+            ("newton", "°N"),
+            ("n", "°N"),
+            ("gasmark", "gas mark"),
+            ("gas-mark", "gas mark"),
+            ("gm", "gas mark"),
+        ];
+        for (alias, symbol) in aliases {
+            let degree: Degree = alias.parse().unwrap();
+            assert_eq!(degree.to_string(), symbol, "{}", alias);
+        }
+
+        assert_eq!("Celsius".parse::<Degree>().unwrap().to_string(), "°C");
+        assert_eq!("  KELVIN ".parse::<Degree>().unwrap().to_string(), "K");
+
+        match "rankine".parse::<Degree>() {
+            Err(UnitParseError::UnknownUnit(unit)) => assert_eq!(unit, "rankine"),
+            other => panic!("expected UnknownUnit, got {:?}", other),
+        }
+        assert!(matches!(
+            " ".parse::<Degree>(),
+            Err(UnitParseError::EmptyInput)
+        ));
+    }
+
+    #[test]
+    fn test_all_units() {
+        assert_eq!(Degree::all().len(), 6);
+        assert_eq!(Length::all().len(), 20);
+
+        // What a unit prints as reads back as the same unit
+        for unit in Degree::all() {
+            assert_eq!(unit.to_string().parse::<Degree>().unwrap(), *unit);
+        }
+        for unit in Length::all() {
+            assert_eq!(unit.to_string().parse::<Length>().unwrap(), *unit);
+        }
+    }
+
+    #[test]
+    fn test_units_as_map_keys() -> Result<()> {
+        // 200 °C is hot enough to have a gas mark too
+        let converter = TemperatureConverter::new(200.0, Degree::Celsius);
+        let mut results = HashMap::new();
+        for &unit in Degree::all() {
+            results.insert(unit, converter.convert_to(unit)?);
+        }
+
+        assert_eq!(results.len(), Degree::all().len());
+        assert_eq!(results[&Degree::Fahrenheit], 392.0);
+        assert_eq!(results[&Degree::Kelvin], 473.15);
+
+        let mut lengths: HashMap<Length, usize> = HashMap::new();
+        for unit in ["m", "metre", "km", "meter"] {
+            *lengths.entry(unit.parse()?).or_default() += 1;
+        }
+        assert_eq!(lengths[&Length::Meter], 3);
+        assert_eq!(lengths[&Length::Kilometer], 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_length() {
+        let aliases = [
+            ("centimeter", "cm"),
+            ("cm", "cm"),
+            ("inch", "in"),
+            ("in", "in"),
+            ("kilometer", "km"),
+            ("km", "km"),
+            ("miles", "mi"),
+            ("mile", "mi"),
+            ("mi", "mi"),
+            ("metre", "m"),
+            ("µm", "µm"),
+            ("feet", "ft"),
+            ("nautical-mile", "nmi"),
+            ("au", "AU"),
+            ("Å", "Å"),
+            ("perch", "rd"),
+        ];
+        for (alias, symbol) in aliases {
+            let length: Length = alias.parse().unwrap();
+            assert_eq!(length.to_string(), symbol, "{}", alias);
+        }
+
+        // Every unit's clap name parses back to it
+        for unit in Length::value_variants() {
+            let name = unit.to_possible_value().unwrap().get_name().to_string();
+            assert_eq!(
+                name.parse::<Length>().unwrap().to_string(),
+                unit.to_string()
+            );
+        }
+
+        assert_eq!(" KM\t".parse::<Length>().unwrap().to_string(), "km");
+        assert!(matches!(
+            "".parse::<Length>(),
+            Err(UnitParseError::EmptyInput)
+        ));
+        assert!(matches!(
+            "  ".parse::<Length>(),
+            Err(UnitParseError::EmptyInput)
+        ));
+        match " cubit ".parse::<Length>() {
+            Err(UnitParseError::UnknownUnit(unit)) => assert_eq!(unit, "cubit"),
+            other => panic!("expected UnknownUnit, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_unit_serde_round_trip() -> serde_json::Result<()> {
+        for unit in Degree::value_variants() {
+            let json = serde_json::to_string(unit)?;
+            let name = unit.to_possible_value().unwrap().get_name().to_string();
+            assert_eq!(json, format!("\"{}\"", name));
+            let back: Degree = serde_json::from_str(&json)?;
+            assert_eq!(back.to_string(), unit.to_string());
+        }
+        for unit in Length::value_variants() {
+            let json = serde_json::to_string(unit)?;
+            let back: Length = serde_json::from_str(&json)?;
+            assert_eq!(back.to_string(), unit.to_string(), "{}", json);
+        }
+
+        let celsius: Degree = serde_json::from_str("\"c\"")?;
+        assert!(matches!(celsius, Degree::Celsius));
+        let metre: Length = serde_json::from_str("\"metre\"")?;
+        assert!(matches!(metre, Length::Meter));
+
+        let error = serde_json::from_str::<Degree>("\"rankine\"").unwrap_err();
+        assert!(error.to_string().contains("Unknown unit: 'rankine'"));
+        Ok(())
+    }
+
+    fn symbols<U: ValueEnum + Display>(order: UnitOrder) -> Vec<String> {
+        ordered_units::<U>(order)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_declaration_order() {
+        assert_eq!(UnitOrder::default(), UnitOrder::Declaration);
+        assert_eq!(
+            symbols::<Mass>(UnitOrder::Declaration),
+            ["g", "kg", "mg", "t", "lb", "oz", "st"]
+        );
+        assert_eq!(
+            symbols::<Time>(UnitOrder::Declaration),
+            ["s", "min", "h", "d", "wk", "ms", "mo", "yr"]
+        );
+    }
+
+    #[test]
+    fn test_symbol_order() {
+        assert_eq!(
+            symbols::<Mass>(UnitOrder::Symbol),
+            ["g", "kg", "lb", "mg", "oz", "st", "t"]
+        );
+        assert_eq!(
+            symbols::<Time>(UnitOrder::Symbol),
+            ["d", "h", "min", "mo", "ms", "s", "wk", "yr"]
+        );
+    }
+
+    #[test]
+    fn test_rows_in_unit_order() {
+        let rows = vec![
+            (Degree::Celsius, 100.0),
+            (Degree::Fahrenheit, 212.0),
+            (Degree::Kelvin, 373.15),
+        ];
+        assert_eq!(in_unit_order(rows.clone(), UnitOrder::Declaration), rows);
+
+        let units: Vec<Degree> = in_unit_order(rows, UnitOrder::Symbol)
+            .into_iter()
+            .map(|(unit, _)| unit)
+            .collect();
+        assert_eq!(units, [Degree::Kelvin, Degree::Celsius, Degree::Fahrenheit]);
+    }
+}
@@ -0,0 +1,188 @@
+use super::Pace;
+use anyhow::{anyhow, Result};
+
+/// Kilometers in a statute mile (exact)
+const KILOMETERS_PER_MILE: f64 = 1.609_344;
+
+/// Slowest pace and fastest speed accepted, far beyond any runner or walker
+const MAX_PACE_MINUTES: f64 = 24.0 * 60.0;
+const MAX_SPEED_KMH: f64 = 1000.0;
+
+/// Converts between running paces (minutes per distance) and speeds
+/// (distance per hour). Pace is the reciprocal of speed, so conversions go
+/// through km/h: 4:00 min/km is 15 km/h, and a faster pace is a smaller
+/// number. Pace values are minutes, so 5:30 is 5.5.
+pub struct PaceConverter {
+    pub value: f64,
+    pub unit: Pace,
+}
+
+impl PaceConverter {
+    pub fn new(value: f64, unit: Pace) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn convert_to(&self, target_unit: Pace) -> Result<f64> {
+        if !self.value.is_finite() || self.value <= 0.0 {
+            return Err(anyhow!(
+                "A pace or speed must be a positive number, got {} {}.",
+                self.value,
+                self.unit
+            ));
+        }
+
+        let kmh = to_kmh(self.value, &self.unit);
+        if kmh > MAX_SPEED_KMH || 60.0 / kmh > MAX_PACE_MINUTES {
+            return Err(anyhow!(
+                "{} {} is not a realistic pace. Paces must be under 24 hours per unit and speeds under {} km/h.",
+                self.value,
+                self.unit,
+                MAX_SPEED_KMH
+            ));
+        }
+
+        Ok(from_kmh(kmh, &target_unit))
+    }
+}
+
+fn to_kmh(value: f64, unit: &Pace) -> f64 {
+    match unit {
+        Pace::MinPerKm => 60.0 / value,
+        Pace::MinPerMile => 60.0 * KILOMETERS_PER_MILE / value,
+        Pace::KmPerHour => value,
+        Pace::MilePerHour => value * KILOMETERS_PER_MILE,
+    }
+}
+
+fn from_kmh(kmh: f64, unit: &Pace) -> f64 {
+    match unit {
+        Pace::MinPerKm => 60.0 / kmh,
+        Pace::MinPerMile => 60.0 * KILOMETERS_PER_MILE / kmh,
+        Pace::KmPerHour => kmh,
+        Pace::MilePerHour => kmh / KILOMETERS_PER_MILE,
+    }
+}
+
+/// Whether values of this unit are paces (minutes), written as `m:ss`
+pub fn is_pace(unit: &Pace) -> bool {
+    matches!(unit, Pace::MinPerKm | Pace::MinPerMile)
+}
+
+/// Reads a pace as `m:ss` or `h:mm:ss` (e.g. `5:30`, `1:02:15`) or a plain
+/// number of minutes or speed (e.g. `5.5`, `12`), returning minutes for
+/// clock notation
+pub fn parse_pace_value(input: &str) -> Result<f64> {
+    let input = input.trim();
+    let invalid = || {
+        anyhow!(
+            "Cannot read '{}' as a pace. Use minutes:seconds like 5:30 (or h:mm:ss), or a plain number.",
+            input
+        )
+    };
+
+    if !input.contains(':') {
+        return input.parse::<f64>().map_err(|_| invalid());
+    }
+
+    let parts: Vec<&str> = input.split(':').collect();
+    if parts.len() > 3 {
+        return Err(invalid());
+    }
+
+    let mut minutes = 0.0;
+    for (index, part) in parts.iter().enumerate() {
+        let field = part.parse::<u32>().map_err(|_| invalid())?;
+        // Every field after the first is base 60
+        if index > 0 && (field >= 60 || part.len() != 2) {
+            return Err(invalid());
+        }
+        minutes = minutes * 60.0 + f64::from(field);
+    }
+
+    // `m:ss` ends in seconds after one step; `h:mm:ss` after two
+    Ok(minutes / 60.0)
+}
+
+/// Formats minutes as `m:ss`, or `h:mm:ss` from an hour up, rounded to the
+/// nearest second
+pub fn format_pace(minutes: f64) -> String {
+    let total_seconds = (minutes * 60.0).round() as u64;
+    let (hours, rest) = (total_seconds / 3600, total_seconds % 3600);
+    let (minutes, seconds) = (rest / 60, rest % 60);
+
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+// Helper functions with error handling
+pub fn min_per_km_to_kmh(pace: &str) -> Result<f64> {
+    PaceConverter::new(parse_pace_value(pace)?, Pace::MinPerKm).convert_to(Pace::KmPerHour)
+}
+
+pub fn min_per_km_to_min_per_mile(pace: &str) -> Result<String> {
+    let minutes =
+        PaceConverter::new(parse_pace_value(pace)?, Pace::MinPerKm).convert_to(Pace::MinPerMile)?;
+    Ok(format_pace(minutes))
+}
+
+pub fn mph_to_min_per_mile(speed: f64) -> Result<String> {
+    let minutes = PaceConverter::new(speed, Pace::MilePerHour).convert_to(Pace::MinPerMile)?;
+    Ok(format_pace(minutes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pace_to_speed() -> Result<()> {
+        assert_eq!(min_per_km_to_kmh("4:00")?, 15.0);
+        assert_eq!(min_per_km_to_kmh("6:00")?, 10.0);
+        assert_eq!(mph_to_min_per_mile(6.0)?, "10:00");
+        Ok(())
+    }
+
+    #[test]
+    fn test_pace_per_mile() -> Result<()> {
+        // 4:00 min/km × 1.609344 = 6.437 min, which is 6:26 per mile
+        assert_eq!(min_per_km_to_min_per_mile("4:00")?, "6:26");
+        assert_eq!(min_per_km_to_min_per_mile("5:30")?, "8:51");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_pace_value() -> Result<()> {
+        assert_eq!(parse_pace_value("5:30")?, 5.5);
+        assert_eq!(parse_pace_value("1:02:15")?, 62.25);
+        assert_eq!(parse_pace_value(" 12.5 ")?, 12.5);
+
+        assert!(parse_pace_value("5:75").is_err());
+        assert!(parse_pace_value("5:3").is_err());
+        assert!(parse_pace_value("1:2:3:4").is_err());
+        assert!(parse_pace_value("fast").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_pace() {
+        assert_eq!(format_pace(5.5), "5:30");
+        assert_eq!(format_pace(4.999), "5:00");
+        assert_eq!(format_pace(62.25), "1:02:15");
+    }
+
+    #[test]
+    fn test_invalid_paces() {
+        assert!(PaceConverter::new(0.0, Pace::MinPerKm)
+            .convert_to(Pace::KmPerHour)
+            .is_err());
+        assert!(PaceConverter::new(f64::NAN, Pace::KmPerHour)
+            .convert_to(Pace::MinPerKm)
+            .is_err());
+        assert!(PaceConverter::new(5000.0, Pace::KmPerHour)
+            .convert_to(Pace::MinPerKm)
+            .is_err());
+    }
+}
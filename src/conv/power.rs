@@ -0,0 +1,233 @@
+use super::Power;
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use std::fmt::{Display, Result as FmtResult};
+use std::sync::OnceLock;
+
+/// Largest power result accepted before the input is considered unrealistic
+const MAX_POWER_RESULT: f64 = 1e18;
+
+/// Standard gravity, the pound and the foot, used to derive both horsepowers
+/// from their definitions
+const STANDARD_GRAVITY: f64 = 9.80665;
+const KILOGRAMS_PER_POUND: f64 = 0.45359237;
+const METERS_PER_FOOT: f64 = 0.3048;
+
+/// `watts_per_unit` for every `Power`, indexed by variant
+static FACTOR_TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+
+pub struct PowerConverter {
+    pub value: f64,
+    pub unit: Power,
+}
+
+pub struct PowerResults {
+    pub watt: f64,
+    pub kilowatt: f64,
+    pub megawatt: f64,
+    pub horsepower_mechanical: f64,
+    pub horsepower_metric: f64,
+}
+
+impl PowerConverter {
+    pub fn new(value: f64, unit: Power) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn convert_to(&self, target_unit: Power) -> Result<f64> {
+        let from = Self::watts_per_unit(&self.unit);
+        let to = Self::watts_per_unit(&target_unit);
+        self.scale(from, to, &target_unit)
+    }
+
+    /// Builds the factor table used by `convert_to_all` ahead of time
+    pub fn warm_up() {
+        Self::factor_table();
+    }
+
+    fn factor_table() -> &'static [f64] {
+        FACTOR_TABLE.get_or_init(|| {
+            Power::value_variants()
+                .iter()
+                .map(Self::watts_per_unit)
+                .collect()
+        })
+    }
+
+    fn scale(&self, from: f64, to: f64, target_unit: &Power) -> Result<f64> {
+        // Always scale by a ratio of at least 1 so exact relationships stay
+        // exact (1 MW is 1000 kW)
+        let result = if from >= to {
+            self.value * (from / to)
+        } else {
+            self.value / (to / from)
+        };
+
+        self.check_conversion_result(result, &format!("{:?} to {:?}", self.unit, target_unit))
+    }
+
+    /// Size of one unit in watts. Mechanical horsepower is 550 foot-pounds
+    /// force per second (about 745.699 W); metric horsepower (PS) is
+    /// 75 kilogram-force meters per second (735.49875 W).
+    fn watts_per_unit(unit: &Power) -> f64 {
+        match unit {
+            Power::Watt => 1.0,
+            Power::Kilowatt => 1e3,
+            Power::Megawatt => 1e6,
+            Power::HorsepowerMechanical => {
+                550.0 * METERS_PER_FOOT * KILOGRAMS_PER_POUND * STANDARD_GRAVITY
+            }
+            Power::HorsepowerMetric => 75.0 * STANDARD_GRAVITY,
+        }
+    }
+
+    fn check_conversion_result(&self, result: f64, conversion_type: &str) -> Result<f64> {
+        if result.is_nan() {
+            return Err(anyhow!(
+                "{} conversion resulted in NaN. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result.is_infinite() {
+            return Err(anyhow!(
+                "{} conversion resulted in infinity. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result < 0.0 {
+            return Err(anyhow!(
+                "{} conversion resulted in negative power: {:.6}. This should not happen with positive input.",
+                conversion_type, result
+            ));
+        }
+
+        if result > MAX_POWER_RESULT {
+            return Err(anyhow!(
+                "{} conversion resulted in an unrealistically large power: {:.2}. Please check your input.",
+                conversion_type, result
+            ));
+        }
+
+        Ok(result)
+    }
+
+    /// Same results as calling `convert_to` for every unit, with the factors
+    /// taken from a table built once
+    pub fn convert_to_all(&self) -> Result<PowerResults> {
+        let factors = Self::factor_table();
+        let from = factors[self.unit as usize];
+        let convert = |target: Power| self.scale(from, factors[target as usize], &target);
+
+        Ok(PowerResults {
+            watt: convert(Power::Watt)?,
+            kilowatt: convert(Power::Kilowatt)?,
+            megawatt: convert(Power::Megawatt)?,
+            horsepower_mechanical: convert(Power::HorsepowerMechanical)?,
+            horsepower_metric: convert(Power::HorsepowerMetric)?,
+        })
+    }
+}
+
+impl PowerResults {
+    /// Every unit with its value, in the order `convert_to_all` fills them
+    pub fn rows(&self) -> Vec<(Power, f64)> {
+        vec![
+            (Power::Watt, self.watt),
+            (Power::Kilowatt, self.kilowatt),
+            (Power::Megawatt, self.megawatt),
+            (Power::HorsepowerMechanical, self.horsepower_mechanical),
+            (Power::HorsepowerMetric, self.horsepower_metric),
+        ]
+    }
+}
+
+impl Display for PowerResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "Watt: {:.6} W, Kilowatt: {:.6} kW, Megawatt: {:.6} MW, Horsepower (mechanical): {:.6} hp, Horsepower (metric): {:.6} PS",
+            self.watt, self.kilowatt, self.megawatt, self.horsepower_mechanical, self.horsepower_metric
+        )
+    }
+}
+
+// Helper functions with error handling
+pub fn hp_to_kw(value: f64) -> Result<f64> {
+    PowerConverter::new(value, Power::HorsepowerMechanical).convert_to(Power::Kilowatt)
+}
+
+pub fn ps_to_kw(value: f64) -> Result<f64> {
+    PowerConverter::new(value, Power::HorsepowerMetric).convert_to(Power::Kilowatt)
+}
+
+pub fn kw_to_hp(value: f64) -> Result<f64> {
+    PowerConverter::new(value, Power::Kilowatt).convert_to(Power::HorsepowerMechanical)
+}
+
+pub fn hp_to_ps(value: f64) -> Result<f64> {
+    PowerConverter::new(value, Power::HorsepowerMechanical).convert_to(Power::HorsepowerMetric)
+}
+
+pub fn mw_to_kw(value: f64) -> Result<f64> {
+    PowerConverter::new(value, Power::Megawatt).convert_to(Power::Kilowatt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_conversions() -> Result<()> {
+        assert_eq!(mw_to_kw(1.0)?, 1000.0);
+        let converter = PowerConverter::new(2500.0, Power::Watt);
+        assert_eq!(converter.convert_to(Power::Kilowatt)?, 2.5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_horsepower_definitions_diverge() -> Result<()> {
+        // 100 hp is about 74.57 kW but 100 PS only about 73.55 kW
+        let mechanical = hp_to_kw(100.0)?;
+        let metric = ps_to_kw(100.0)?;
+        assert!((mechanical - 74.569_987).abs() < 1e-6);
+        assert!((metric - 73.549_875).abs() < 1e-9);
+        assert!(mechanical - metric > 1.0);
+
+        assert!((hp_to_ps(100.0)? - 101.386_97).abs() < 1e-5);
+        assert!((kw_to_hp(1.0)? - 1.341_022).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_all() -> Result<()> {
+        PowerConverter::warm_up();
+        let converter = PowerConverter::new(1.0, Power::Megawatt);
+        let all = converter.convert_to_all()?;
+
+        assert_eq!(all.watt, 1e6);
+        assert_eq!(
+            all.horsepower_metric.to_bits(),
+            converter.convert_to(Power::HorsepowerMetric)?.to_bits()
+        );
+        assert!(all.to_string().contains("Megawatt: 1.000000 MW"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_conversions() {
+        let converter = PowerConverter::new(f64::NAN, Power::Watt);
+        assert!(converter.convert_to(Power::Kilowatt).is_err());
+
+        let converter = PowerConverter::new(f64::INFINITY, Power::Watt);
+        assert!(converter.convert_to(Power::Kilowatt).is_err());
+
+        let converter = PowerConverter::new(-1.0, Power::Watt);
+        assert!(converter.convert_to(Power::Kilowatt).is_err());
+    }
+}
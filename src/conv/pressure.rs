@@ -0,0 +1,241 @@
+use super::Pressure;
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use std::fmt::{Display, Result as FmtResult};
+use std::sync::OnceLock;
+
+/// Largest pressure result accepted before the input is considered unrealistic
+const MAX_PRESSURE_RESULT: f64 = 1e18;
+
+/// Standard gravity and the inch, used to derive the psi from the pound
+const STANDARD_GRAVITY: f64 = 9.80665;
+const METERS_PER_INCH: f64 = 0.0254;
+const KILOGRAMS_PER_POUND: f64 = 0.45359237;
+
+/// `pascals_per_unit` for every `Pressure`, indexed by variant
+static FACTOR_TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+
+pub struct PressureConverter {
+    pub value: f64,
+    pub unit: Pressure,
+}
+
+pub struct PressureResults {
+    pub pascal: f64,
+    pub kilopascal: f64,
+    pub bar: f64,
+    pub atmosphere: f64,
+    pub psi: f64,
+    pub mm_hg: f64,
+}
+
+impl PressureConverter {
+    pub fn new(value: f64, unit: Pressure) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn convert_to(&self, target_unit: Pressure) -> Result<f64> {
+        let from = Self::pascals_per_unit(&self.unit);
+        let to = Self::pascals_per_unit(&target_unit);
+        self.scale(from, to, &target_unit)
+    }
+
+    /// Builds the factor table used by `convert_to_all` ahead of time
+    pub fn warm_up() {
+        Self::factor_table();
+    }
+
+    fn factor_table() -> &'static [f64] {
+        FACTOR_TABLE.get_or_init(|| {
+            Pressure::value_variants()
+                .iter()
+                .map(Self::pascals_per_unit)
+                .collect()
+        })
+    }
+
+    fn scale(&self, from: f64, to: f64, target_unit: &Pressure) -> Result<f64> {
+        // Always scale by a ratio of at least 1 so exact relationships stay
+        // exact (1 bar is 100 kPa)
+        let result = if from >= to {
+            self.value * (from / to)
+        } else {
+            self.value / (to / from)
+        };
+
+        self.check_conversion_result(result, &format!("{:?} to {:?}", self.unit, target_unit))
+    }
+
+    /// Size of one unit in pascals. The psi is one pound-force per square
+    /// inch (about 6894.757 Pa) and the mmHg is the conventional
+    /// 133.322387415 Pa (about 133.322 Pa).
+    fn pascals_per_unit(unit: &Pressure) -> f64 {
+        match unit {
+            Pressure::Pascal => 1.0,
+            Pressure::Kilopascal => 1e3,
+            Pressure::Bar => 1e5,
+            Pressure::Atmosphere => 101_325.0,
+            Pressure::Psi => {
+                KILOGRAMS_PER_POUND * STANDARD_GRAVITY / (METERS_PER_INCH * METERS_PER_INCH)
+            }
+            Pressure::MmHg => 133.322_387_415,
+        }
+    }
+
+    fn check_conversion_result(&self, result: f64, conversion_type: &str) -> Result<f64> {
+        if result.is_nan() {
+            return Err(anyhow!(
+                "{} conversion resulted in NaN. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result.is_infinite() {
+            return Err(anyhow!(
+                "{} conversion resulted in infinity. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result < 0.0 {
+            return Err(anyhow!(
+                "{} conversion resulted in negative pressure: {:.6}. This should not happen with positive input.",
+                conversion_type, result
+            ));
+        }
+
+        if result > MAX_PRESSURE_RESULT {
+            return Err(anyhow!(
+                "{} conversion resulted in an unrealistically large pressure: {:.2}. Please check your input.",
+                conversion_type, result
+            ));
+        }
+
+        Ok(result)
+    }
+
+    /// Same results as calling `convert_to` for every unit, with the factors
+    /// taken from a table built once
+    pub fn convert_to_all(&self) -> Result<PressureResults> {
+        let factors = Self::factor_table();
+        let from = factors[self.unit as usize];
+        let convert = |target: Pressure| self.scale(from, factors[target as usize], &target);
+
+        Ok(PressureResults {
+            pascal: convert(Pressure::Pascal)?,
+            kilopascal: convert(Pressure::Kilopascal)?,
+            bar: convert(Pressure::Bar)?,
+            atmosphere: convert(Pressure::Atmosphere)?,
+            psi: convert(Pressure::Psi)?,
+            mm_hg: convert(Pressure::MmHg)?,
+        })
+    }
+}
+
+impl PressureResults {
+    /// Every unit with its value, in the order `convert_to_all` fills them
+    pub fn rows(&self) -> Vec<(Pressure, f64)> {
+        vec![
+            (Pressure::Pascal, self.pascal),
+            (Pressure::Kilopascal, self.kilopascal),
+            (Pressure::Bar, self.bar),
+            (Pressure::Atmosphere, self.atmosphere),
+            (Pressure::Psi, self.psi),
+            (Pressure::MmHg, self.mm_hg),
+        ]
+    }
+}
+
+impl Display for PressureResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "Pascal: {:.6} Pa, Kilopascal: {:.6} kPa, Bar: {:.6} bar, Atmosphere: {:.6} atm, PSI: {:.6} psi, Millimeter of Mercury: {:.6} mmHg",
+            self.pascal, self.kilopascal, self.bar, self.atmosphere, self.psi, self.mm_hg
+        )
+    }
+}
+
+// Helper functions with error handling
+pub fn bar_to_pa(value: f64) -> Result<f64> {
+    PressureConverter::new(value, Pressure::Bar).convert_to(Pressure::Pascal)
+}
+
+pub fn atm_to_pa(value: f64) -> Result<f64> {
+    PressureConverter::new(value, Pressure::Atmosphere).convert_to(Pressure::Pascal)
+}
+
+pub fn atm_to_psi(value: f64) -> Result<f64> {
+    PressureConverter::new(value, Pressure::Atmosphere).convert_to(Pressure::Psi)
+}
+
+pub fn psi_to_kpa(value: f64) -> Result<f64> {
+    PressureConverter::new(value, Pressure::Psi).convert_to(Pressure::Kilopascal)
+}
+
+pub fn bar_to_psi(value: f64) -> Result<f64> {
+    PressureConverter::new(value, Pressure::Bar).convert_to(Pressure::Psi)
+}
+
+pub fn atm_to_mmhg(value: f64) -> Result<f64> {
+    PressureConverter::new(value, Pressure::Atmosphere).convert_to(Pressure::MmHg)
+}
+
+pub fn mmhg_to_pa(value: f64) -> Result<f64> {
+    PressureConverter::new(value, Pressure::MmHg).convert_to(Pressure::Pascal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_conversions() -> Result<()> {
+        assert_eq!(bar_to_pa(1.0)?, 100_000.0);
+        assert_eq!(atm_to_pa(1.0)?, 101_325.0);
+        let converter = PressureConverter::new(1.0, Pressure::Bar);
+        assert_eq!(converter.convert_to(Pressure::Kilopascal)?, 100.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_psi_and_mmhg() -> Result<()> {
+        assert!((atm_to_psi(1.0)? - 14.696).abs() < 1e-3);
+        assert!((psi_to_kpa(1.0)? - 6.894757).abs() < 1e-6);
+        assert!((bar_to_psi(2.2)? - 31.908).abs() < 1e-3);
+        assert!((mmhg_to_pa(1.0)? - 133.322).abs() < 1e-3);
+        assert!((atm_to_mmhg(1.0)? - 760.0).abs() < 1e-3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_all() -> Result<()> {
+        PressureConverter::warm_up();
+        let converter = PressureConverter::new(1.0, Pressure::Atmosphere);
+        let all = converter.convert_to_all()?;
+
+        assert_eq!(all.pascal, 101_325.0);
+        assert_eq!(
+            all.psi.to_bits(),
+            converter.convert_to(Pressure::Psi)?.to_bits()
+        );
+        assert!(all.to_string().contains("Atmosphere: 1.000000 atm"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_conversions() {
+        let converter = PressureConverter::new(f64::NAN, Pressure::Bar);
+        assert!(converter.convert_to(Pressure::Psi).is_err());
+
+        let converter = PressureConverter::new(f64::INFINITY, Pressure::Bar);
+        assert!(converter.convert_to(Pressure::Psi).is_err());
+
+        let converter = PressureConverter::new(-1.0, Pressure::Bar);
+        assert!(converter.convert_to(Pressure::Psi).is_err());
+    }
+}
@@ -0,0 +1,99 @@
+//! Property tests: converting a value A → B → A must give back the input
+//! for every pair of units in a category
+
+use super::*;
+use proptest::prelude::*;
+use std::fmt::Debug;
+use std::ops::Range;
+
+/// Relative error allowed after a round trip. Each direction scales by one
+/// ratio, so a correct pair of factors only loses a few ulps.
+const ROUND_TRIP_TOLERANCE: f64 = 1e-12;
+
+/// Magnitudes for the factor-based categories. Extreme magnitudes are
+/// skipped: the converters reject results beyond their `MAX_*` limits, and
+/// near-subnormal results lose relative precision that has nothing to do
+/// with the factors.
+const VALUES: Range<f64> = 1e-6..1e6;
+
+/// Length spans far more orders of magnitude (nanometers to parsecs), so its
+/// inputs stay narrower to keep every pair under `MAX_LENGTH_RESULT`
+const LENGTH_VALUES: Range<f64> = 1e-3..1e3;
+
+/// Celsius inputs for temperature, from absolute zero to a furnace
+const CELSIUS_VALUES: Range<f64> = -273.15..5000.0;
+
+fn any_unit<U: ValueEnum + Debug + 'static>() -> impl Strategy<Value = U> {
+    prop::sample::select(U::value_variants())
+}
+
+fn check_round_trip(value: f64, back: f64, scale: f64) -> Result<(), TestCaseError> {
+    let error = (back - value).abs() / scale;
+    prop_assert!(
+        error <= ROUND_TRIP_TOLERANCE,
+        "{} came back as {} (relative error {:e})",
+        value,
+        back,
+        error
+    );
+    Ok(())
+}
+
+/// One round-trip property for a category whose converter has the usual
+/// `new(value, unit)` / `convert_to(unit)` shape
+macro_rules! round_trip_test {
+    ($name:ident, $unit:ty, $converter:ident, $values:expr) => {
+        proptest! {
+            #[test]
+            fn $name(value in $values, from in any_unit::<$unit>(), to in any_unit::<$unit>()) {
+                let there = $converter::new(value, from.clone()).convert_to(to.clone()).unwrap();
+                let back = $converter::new(there, to).convert_to(from).unwrap();
+                check_round_trip(value, back, value.abs())?;
+            }
+        }
+    };
+}
+
+round_trip_test!(length_round_trips, Length, LengthConverter, LENGTH_VALUES);
+round_trip_test!(mass_round_trips, Mass, MassConverter, VALUES);
+round_trip_test!(volume_round_trips, Volume, VolumeConverter, VALUES);
+round_trip_test!(area_round_trips, Area, AreaConverter, VALUES);
+round_trip_test!(speed_round_trips, Speed, SpeedConverter, VALUES);
+round_trip_test!(time_round_trips, Time, TimeConverter, VALUES);
+round_trip_test!(data_round_trips, Data, DataConverter, VALUES);
+round_trip_test!(pressure_round_trips, Pressure, PressureConverter, VALUES);
+round_trip_test!(energy_round_trips, Energy, EnergyConverter, VALUES);
+round_trip_test!(power_round_trips, Power, PowerConverter, VALUES);
+round_trip_test!(angle_round_trips, Angle, AngleConverter, VALUES);
+round_trip_test!(frequency_round_trips, Frequency, FrequencyConverter, VALUES);
+round_trip_test!(fuel_round_trips, FuelEconomy, FuelEconomyConverter, VALUES);
+
+/// Temperature units without the gas mark, whose chart only covers ovens
+fn linear_temperature_unit() -> impl Strategy<Value = Degree> {
+    any_unit::<Degree>().prop_filter("gas mark has its own test", |unit| {
+        !matches!(unit, Degree::GasMark)
+    })
+}
+
+proptest! {
+    #[test]
+    fn temperature_round_trips(
+        celsius in CELSIUS_VALUES,
+        from in linear_temperature_unit(),
+        to in linear_temperature_unit(),
+    ) {
+        let value = TemperatureConverter::new(celsius, Degree::Celsius).convert_to(from).unwrap();
+        let there = TemperatureConverter::new(value, from).convert_to(to).unwrap();
+        let back = TemperatureConverter::new(there, to).convert_to(from).unwrap();
+
+        // Offsets make values near zero meaningless as a relative scale
+        check_round_trip(value, back, value.abs().max(1.0))?;
+    }
+
+    #[test]
+    fn gas_mark_round_trips(gas_mark in 0.25..9.0f64, to in linear_temperature_unit()) {
+        let there = TemperatureConverter::new(gas_mark, Degree::GasMark).convert_to(to).unwrap();
+        let back = TemperatureConverter::new(there, to).convert_to(Degree::GasMark).unwrap();
+        check_round_trip(gas_mark, back, gas_mark)?;
+    }
+}
@@ -0,0 +1,156 @@
+use super::{ShoeGender, ShoeSystem};
+use anyhow::{anyhow, Result};
+
+/// Adult range handled, as UK sizes (US 2–16 for men and 3–17 for women,
+/// EU 33–51)
+pub const MIN_UK_SIZE: f64 = 1.0;
+pub const MAX_UK_SIZE: f64 = 15.0;
+
+/// Converts adult shoe sizes between the US, UK and EU systems with the usual
+/// last-length formulas: UK = 3 × last (in) − 25 and EU = 1.5 × last (cm),
+/// so EU = 1.27 × (UK + 25). US sizes run one above UK for men and two above
+/// for women. Sizes are discrete, so results snap to the nearest half size
+/// (US, UK) or whole size (EU); real brands vary by about half a size.
+pub struct ShoeSizeConverter {
+    pub value: f64,
+    pub system: ShoeSystem,
+    pub gender: ShoeGender,
+}
+
+impl ShoeSizeConverter {
+    pub fn new(value: f64, system: ShoeSystem, gender: ShoeGender) -> Self {
+        Self {
+            value,
+            system,
+            gender,
+        }
+    }
+
+    pub fn convert_to(&self, target: ShoeSystem) -> Result<f64> {
+        let exact = self.exact_size(&target)?;
+        Ok(snap(exact, &target))
+    }
+
+    /// Whether the converted size had to be rounded onto the target's size grid
+    pub fn snapped_to_size(&self, target: ShoeSystem) -> Result<bool> {
+        let exact = self.exact_size(&target)?;
+        Ok((exact - snap(exact, &target)).abs() > 1e-6)
+    }
+
+    fn exact_size(&self, target: &ShoeSystem) -> Result<f64> {
+        let uk = self.check_uk_size(self.uk_size())?;
+        Ok(self.size_from_uk(uk, target))
+    }
+
+    fn uk_size(&self) -> f64 {
+        match self.system {
+            ShoeSystem::Uk => self.value,
+            ShoeSystem::Us => self.value - self.us_offset(),
+            ShoeSystem::Eu => self.value / 1.27 - 25.0,
+        }
+    }
+
+    fn size_from_uk(&self, uk: f64, target: &ShoeSystem) -> f64 {
+        match target {
+            ShoeSystem::Uk => uk,
+            ShoeSystem::Us => uk + self.us_offset(),
+            ShoeSystem::Eu => 1.27 * (uk + 25.0),
+        }
+    }
+
+    /// How far US sizes run above UK sizes
+    fn us_offset(&self) -> f64 {
+        match self.gender {
+            ShoeGender::Men => 1.0,
+            ShoeGender::Women => 2.0,
+        }
+    }
+
+    fn check_uk_size(&self, uk: f64) -> Result<f64> {
+        if !uk.is_finite() || !(MIN_UK_SIZE - 0.25..=MAX_UK_SIZE + 0.25).contains(&uk) {
+            return Err(anyhow!(
+                "{} {} is not an adult {} shoe size. Use {} to {} {}.",
+                self.value,
+                self.system,
+                self.gender,
+                self.size_from_uk(MIN_UK_SIZE, &self.system).round(),
+                self.size_from_uk(MAX_UK_SIZE, &self.system).round(),
+                self.system
+            ));
+        }
+        Ok(uk)
+    }
+}
+
+/// Rounds onto the size grid: half sizes for US and UK, whole sizes for EU
+fn snap(size: f64, system: &ShoeSystem) -> f64 {
+    match system {
+        ShoeSystem::Us | ShoeSystem::Uk => (size * 2.0).round() / 2.0,
+        ShoeSystem::Eu => size.round(),
+    }
+}
+
+// Helper functions with error handling
+pub fn us_to_eu(size: f64, gender: ShoeGender) -> Result<f64> {
+    ShoeSizeConverter::new(size, ShoeSystem::Us, gender).convert_to(ShoeSystem::Eu)
+}
+
+pub fn us_to_uk(size: f64, gender: ShoeGender) -> Result<f64> {
+    ShoeSizeConverter::new(size, ShoeSystem::Us, gender).convert_to(ShoeSystem::Uk)
+}
+
+pub fn eu_to_us(size: f64, gender: ShoeGender) -> Result<f64> {
+    ShoeSizeConverter::new(size, ShoeSystem::Eu, gender).convert_to(ShoeSystem::Us)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mens_size_chart() -> Result<()> {
+        // Published men's chart: US 9 = UK 8 = EU 42, US 10 = UK 9 = EU 43
+        assert_eq!(us_to_uk(9.0, ShoeGender::Men)?, 8.0);
+        assert_eq!(us_to_eu(9.0, ShoeGender::Men)?, 42.0);
+        assert_eq!(us_to_uk(10.0, ShoeGender::Men)?, 9.0);
+        assert_eq!(us_to_eu(10.0, ShoeGender::Men)?, 43.0);
+        assert_eq!(eu_to_us(42.0, ShoeGender::Men)?, 9.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_womens_size_chart() -> Result<()> {
+        // Published women's chart: US 6 = UK 4 = EU 37, US 8 = UK 6 = EU 39
+        assert_eq!(us_to_uk(6.0, ShoeGender::Women)?, 4.0);
+        assert_eq!(us_to_eu(6.0, ShoeGender::Women)?, 37.0);
+        assert_eq!(us_to_uk(8.0, ShoeGender::Women)?, 6.0);
+        assert_eq!(us_to_eu(8.0, ShoeGender::Women)?, 39.0);
+        assert_eq!(eu_to_us(37.0, ShoeGender::Women)?, 6.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_gender_changes_the_us_size() -> Result<()> {
+        // The same foot is a men's US 8 and a women's US 9.5 or so
+        let mens = eu_to_us(41.0, ShoeGender::Men)?;
+        let womens = eu_to_us(41.0, ShoeGender::Women)?;
+        assert_eq!(womens - mens, 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_snapping_is_reported() -> Result<()> {
+        let converter = ShoeSizeConverter::new(42.0, ShoeSystem::Eu, ShoeGender::Men);
+        assert!(converter.snapped_to_size(ShoeSystem::Us)?);
+        let converter = ShoeSizeConverter::new(9.0, ShoeSystem::Us, ShoeGender::Men);
+        assert!(!converter.snapped_to_size(ShoeSystem::Uk)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_out_of_range_sizes() {
+        assert!(us_to_eu(30.0, ShoeGender::Men).is_err());
+        assert!(eu_to_us(20.0, ShoeGender::Women).is_err());
+        assert!(us_to_eu(f64::NAN, ShoeGender::Men).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use super::Speed;
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use std::fmt::{Display, Result as FmtResult};
+use std::sync::OnceLock;
+
+/// Largest speed result accepted before the input is considered unrealistic
+const MAX_SPEED_RESULT: f64 = 1e18;
+
+/// `micrometers_per_hour` for every `Speed`, indexed by variant
+static FACTOR_TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+
+pub struct SpeedConverter {
+    pub value: f64,
+    pub unit: Speed,
+}
+
+pub struct SpeedResults {
+    pub meter_per_second: f64,
+    pub kilometer_per_hour: f64,
+    pub mile_per_hour: f64,
+    pub knot: f64,
+    pub foot_per_second: f64,
+}
+
+impl SpeedConverter {
+    pub fn new(value: f64, unit: Speed) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn convert_to(&self, target_unit: Speed) -> Result<f64> {
+        let from = Self::micrometers_per_hour(&self.unit);
+        let to = Self::micrometers_per_hour(&target_unit);
+        self.scale(from, to, &target_unit)
+    }
+
+    /// Builds the factor table used by `convert_to_all` ahead of time
+    pub fn warm_up() {
+        Self::factor_table();
+    }
+
+    fn factor_table() -> &'static [f64] {
+        FACTOR_TABLE.get_or_init(|| {
+            Speed::value_variants()
+                .iter()
+                .map(Self::micrometers_per_hour)
+                .collect()
+        })
+    }
+
+    fn scale(&self, from: f64, to: f64, target_unit: &Speed) -> Result<f64> {
+        // Always scale by a ratio of at least 1 so exact relationships stay
+        // exact (1 m/s is 3.6 km/h)
+        let result = if from >= to {
+            self.value * (from / to)
+        } else {
+            self.value / (to / from)
+        };
+
+        self.check_conversion_result(result, &format!("{:?} to {:?}", self.unit, target_unit))
+    }
+
+    /// One unit of speed in micrometers per hour. Every unit is a whole
+    /// number here: 1 kn is 1.852 km/h and 1 mph is 1.609344 km/h exactly.
+    fn micrometers_per_hour(unit: &Speed) -> f64 {
+        match unit {
+            Speed::MeterPerSecond => 3_600_000_000.0,
+            Speed::KilometerPerHour => 1_000_000_000.0,
+            Speed::MilePerHour => 1_609_344_000.0,
+            Speed::Knot => 1_852_000_000.0,
+            Speed::FootPerSecond => 1_097_280_000.0,
+        }
+    }
+
+    fn check_conversion_result(&self, result: f64, conversion_type: &str) -> Result<f64> {
+        if result.is_nan() {
+            return Err(anyhow!(
+                "{} conversion resulted in NaN. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result.is_infinite() {
+            return Err(anyhow!(
+                "{} conversion resulted in infinity. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result < 0.0 {
+            return Err(anyhow!(
+                "{} conversion resulted in negative speed: {:.6}. This should not happen with positive input.",
+                conversion_type, result
+            ));
+        }
+
+        if result > MAX_SPEED_RESULT {
+            return Err(anyhow!(
+                "{} conversion resulted in an unrealistically large speed: {:.2}. Please check your input.",
+                conversion_type, result
+            ));
+        }
+
+        Ok(result)
+    }
+
+    /// Same results as calling `convert_to` for every unit, with the factors
+    /// taken from a table built once
+    pub fn convert_to_all(&self) -> Result<SpeedResults> {
+        let factors = Self::factor_table();
+        let from = factors[self.unit as usize];
+        let convert = |target: Speed| self.scale(from, factors[target as usize], &target);
+
+        Ok(SpeedResults {
+            meter_per_second: convert(Speed::MeterPerSecond)?,
+            kilometer_per_hour: convert(Speed::KilometerPerHour)?,
+            mile_per_hour: convert(Speed::MilePerHour)?,
+            knot: convert(Speed::Knot)?,
+            foot_per_second: convert(Speed::FootPerSecond)?,
+        })
+    }
+}
+
+impl SpeedResults {
+    /// Every unit with its value, in the order `convert_to_all` fills them
+    pub fn rows(&self) -> Vec<(Speed, f64)> {
+        vec![
+            (Speed::MeterPerSecond, self.meter_per_second),
+            (Speed::KilometerPerHour, self.kilometer_per_hour),
+            (Speed::MilePerHour, self.mile_per_hour),
+            (Speed::Knot, self.knot),
+            (Speed::FootPerSecond, self.foot_per_second),
+        ]
+    }
+}
+
+impl Display for SpeedResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "Meter per Second: {:.6} m/s, Kilometer per Hour: {:.6} km/h, Mile per Hour: {:.6} mph, Knot: {:.6} kn, Foot per Second: {:.6} ft/s",
+            self.meter_per_second, self.kilometer_per_hour, self.mile_per_hour, self.knot, self.foot_per_second
+        )
+    }
+}
+
+// Helper functions with error handling
+pub fn kmh_to_mph(value: f64) -> Result<f64> {
+    SpeedConverter::new(value, Speed::KilometerPerHour).convert_to(Speed::MilePerHour)
+}
+
+pub fn mph_to_kmh(value: f64) -> Result<f64> {
+    SpeedConverter::new(value, Speed::MilePerHour).convert_to(Speed::KilometerPerHour)
+}
+
+pub fn kmh_to_ms(value: f64) -> Result<f64> {
+    SpeedConverter::new(value, Speed::KilometerPerHour).convert_to(Speed::MeterPerSecond)
+}
+
+pub fn ms_to_kmh(value: f64) -> Result<f64> {
+    SpeedConverter::new(value, Speed::MeterPerSecond).convert_to(Speed::KilometerPerHour)
+}
+
+pub fn mph_to_ms(value: f64) -> Result<f64> {
+    SpeedConverter::new(value, Speed::MilePerHour).convert_to(Speed::MeterPerSecond)
+}
+
+pub fn ms_to_mph(value: f64) -> Result<f64> {
+    SpeedConverter::new(value, Speed::MeterPerSecond).convert_to(Speed::MilePerHour)
+}
+
+pub fn knot_to_kmh(value: f64) -> Result<f64> {
+    SpeedConverter::new(value, Speed::Knot).convert_to(Speed::KilometerPerHour)
+}
+
+pub fn fps_to_ms(value: f64) -> Result<f64> {
+    SpeedConverter::new(value, Speed::FootPerSecond).convert_to(Speed::MeterPerSecond)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_factors() -> Result<()> {
+        assert_eq!(knot_to_kmh(1.0)?, 1.852);
+        assert_eq!(mph_to_kmh(1.0)?, 1.609344);
+        assert_eq!(ms_to_kmh(1.0)?, 3.6);
+        assert_eq!(fps_to_ms(1.0)?, 0.3048);
+        Ok(())
+    }
+
+    #[test]
+    fn test_round_trips() -> Result<()> {
+        let mph = kmh_to_mph(100.0)?;
+        assert!((mph - 62.137119).abs() < 1e-6);
+        assert!((mph_to_kmh(mph)? - 100.0).abs() < 1e-9);
+
+        let ms = mph_to_ms(mph)?;
+        assert!((ms - 27.777778).abs() < 1e-6);
+        assert!((ms_to_mph(ms)? - mph).abs() < 1e-9);
+        assert!((ms_to_kmh(kmh_to_ms(100.0)?)? - 100.0).abs() < 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_all() -> Result<()> {
+        SpeedConverter::warm_up();
+        let converter = SpeedConverter::new(10.0, Speed::MeterPerSecond);
+        let all = converter.convert_to_all()?;
+
+        assert_eq!(all.kilometer_per_hour, 36.0);
+        assert_eq!(
+            all.knot.to_bits(),
+            converter.convert_to(Speed::Knot)?.to_bits()
+        );
+        assert!(all.to_string().contains("Meter per Second: 10.000000 m/s"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_conversions() {
+        let converter = SpeedConverter::new(f64::NAN, Speed::Knot);
+        assert!(converter.convert_to(Speed::MilePerHour).is_err());
+
+        let converter = SpeedConverter::new(f64::INFINITY, Speed::Knot);
+        assert!(converter.convert_to(Speed::MilePerHour).is_err());
+
+        let converter = SpeedConverter::new(-1.0, Speed::Knot);
+        assert!(converter.convert_to(Speed::MilePerHour).is_err());
+    }
+}
@@ -1,112 +1,330 @@
-use super::Degree;
+use super::{float, Degree};
+use crate::errors::ConversionError;
 use anyhow::{anyhow, Result};
-use std::{
-    f64,
-    fmt::{Display, Result as FmtResult},
-};
+use num_traits::Float;
+use std::fmt::{Display, LowerExp, Result as FmtResult};
+use std::num::FpCategory;
 
-pub struct TemperatureConverter {
-    pub value: f64,
+/// Published UK oven chart as (gas mark, °C) points. Conversions interpolate
+/// linearly between neighbouring points and refuse to extrapolate past either end.
+const GAS_MARK_CHART: [(f64, f64); 11] = [
+    (0.25, 110.0),
+    (0.5, 120.0),
+    (1.0, 140.0),
+    (2.0, 150.0),
+    (3.0, 170.0),
+    (4.0, 180.0),
+    (5.0, 190.0),
+    (6.0, 200.0),
+    (7.0, 220.0),
+    (8.0, 230.0),
+    (9.0, 240.0),
+];
+
+/// Converts `value` from `unit`. `T` is the float type: `f64` through
+/// `new`, or `f32` (or `f64`) through `from_float`.
+pub struct TemperatureConverter<T = f64> {
+    pub value: T,
     pub unit: Degree,
 }
 
+/// The value in every temperature unit; with the `serde` feature it
+/// serializes as `{"celsius": ..., "fahrenheit": ..., ...}`
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TemperatureResults {
     pub celsius: f64,
     pub fahrenheit: f64,
     pub kelvin: f64,
+    pub delisle: f64,
+    pub newton: f64,
 }
 
-impl TemperatureConverter {
-    pub fn new(value: f64, unit: Degree) -> Self {
+impl<T: Float + Display + LowerExp> TemperatureConverter<T> {
+    /// `new` for any float type, e.g. `TemperatureConverter::from_float(2.5f32, ...)`
+    pub fn from_float(value: T, unit: Degree) -> Self {
         Self { value, unit }
     }
 
-    pub fn convert_to(&self, target_unit: Degree) -> Result<f64> {
+    pub fn convert_to(&self, target_unit: Degree) -> Result<T> {
+        let k = |x: f64| -> T { float(x) };
         let result = match (&self.unit, &target_unit) {
             // Same unit, no conversion needed
             (Degree::Celsius, Degree::Celsius) => self.value,
             (Degree::Fahrenheit, Degree::Fahrenheit) => self.value,
             (Degree::Kelvin, Degree::Kelvin) => self.value,
+            (Degree::Delisle, Degree::Delisle) => self.value,
+            (Degree::Newton, Degree::Newton) => self.value,
+            (Degree::GasMark, Degree::GasMark) => self.value,
 
             // Celsius conversion
             (Degree::Celsius, Degree::Fahrenheit) => {
-                let result = (self.value * 9.0 / 5.0) + 32.0;
+                let result = (self.value * k(9.0) / k(5.0)) + k(32.0);
                 self.check_conversion_result(result, "Celsius to Fahrenheit")?
             }
             (Degree::Celsius, Degree::Kelvin) => {
-                let result = self.value + 273.15;
+                let result = self.value + k(273.15);
                 self.check_conversion_result(result, "Celsius to Kelvin")?
             }
 
             // Fahrenheit conversion
             (Degree::Fahrenheit, Degree::Celsius) => {
-                let result = (self.value - 32.0) * 5.0 / 9.0;
+                let result = (self.value - k(32.0)) * k(5.0) / k(9.0);
                 self.check_conversion_result(result, "Fahrenheit to Celsius")?
             }
             (Degree::Fahrenheit, Degree::Kelvin) => {
-                let result = (self.value + 459.67) * 5.0 / 9.0;
+                let result = (self.value + k(459.67)) * k(5.0) / k(9.0);
                 self.check_conversion_result(result, "Fahrenheit to Kelvin")?
             }
 
             // Kelvin conversion
             (Degree::Kelvin, Degree::Celsius) => {
-                let result = self.value - 273.15;
+                let result = self.value - k(273.15);
                 self.check_conversion_result(result, "Kelvin to Celsius")?
             }
             (Degree::Kelvin, Degree::Fahrenheit) => {
-                let result = (self.value * 9.0 / 5.0) - 459.67;
+                let result = (self.value * k(9.0) / k(5.0)) - k(459.67);
                 self.check_conversion_result(result, "Kelvin to Fahrenheit")?
             }
+
+            // Delisle conversion (inverted scale: higher numbers are colder)
+            (Degree::Celsius, Degree::Delisle) => {
+                let result = (k(100.0) - self.value) * k(3.0) / k(2.0);
+                self.check_conversion_result(result, "Celsius to Delisle")?
+            }
+            (Degree::Fahrenheit, Degree::Delisle) => {
+                let result = (k(212.0) - self.value) * k(5.0) / k(6.0);
+                self.check_conversion_result(result, "Fahrenheit to Delisle")?
+            }
+            (Degree::Kelvin, Degree::Delisle) => {
+                let result = (k(373.15) - self.value) * k(3.0) / k(2.0);
+                self.check_conversion_result(result, "Kelvin to Delisle")?
+            }
+            (Degree::Delisle, Degree::Celsius) => {
+                let result = k(100.0) - self.value * k(2.0) / k(3.0);
+                self.check_conversion_result(result, "Delisle to Celsius")?
+            }
+            (Degree::Delisle, Degree::Fahrenheit) => {
+                let result = k(212.0) - self.value * k(6.0) / k(5.0);
+                self.check_conversion_result(result, "Delisle to Fahrenheit")?
+            }
+            (Degree::Delisle, Degree::Kelvin) => {
+                let result = k(373.15) - self.value * k(2.0) / k(3.0);
+                self.check_conversion_result(result, "Delisle to Kelvin")?
+            }
+
+            // Newton conversion (water freezes at 0 °N and boils at 33 °N)
+            (Degree::Celsius, Degree::Newton) => {
+                let result = self.value * k(33.0) / k(100.0);
+                self.check_conversion_result(result, "Celsius to Newton")?
+            }
+            (Degree::Fahrenheit, Degree::Newton) => {
+                let result = (self.value - k(32.0)) * k(11.0) / k(60.0);
+                self.check_conversion_result(result, "Fahrenheit to Newton")?
+            }
+            (Degree::Kelvin, Degree::Newton) => {
+                let result = (self.value - k(273.15)) * k(33.0) / k(100.0);
+                self.check_conversion_result(result, "Kelvin to Newton")?
+            }
+            (Degree::Delisle, Degree::Newton) => {
+                let result = k(33.0) - self.value * k(11.0) / k(50.0);
+                self.check_conversion_result(result, "Delisle to Newton")?
+            }
+            (Degree::Newton, Degree::Celsius) => {
+                let result = self.value * k(100.0) / k(33.0);
+                self.check_conversion_result(result, "Newton to Celsius")?
+            }
+            (Degree::Newton, Degree::Fahrenheit) => {
+                let result = self.value * k(60.0) / k(11.0) + k(32.0);
+                self.check_conversion_result(result, "Newton to Fahrenheit")?
+            }
+            (Degree::Newton, Degree::Kelvin) => {
+                let result = self.value * k(100.0) / k(33.0) + k(273.15);
+                self.check_conversion_result(result, "Newton to Kelvin")?
+            }
+            (Degree::Newton, Degree::Delisle) => {
+                let result = (k(33.0) - self.value) * k(50.0) / k(11.0);
+                self.check_conversion_result(result, "Newton to Delisle")?
+            }
+
+            // Gas mark conversion (via Celsius, using the oven chart, which
+            // is lookup data rather than a formula and so stays in f64)
+            (Degree::GasMark, target) => {
+                let celsius = gas_mark_chart_to_celsius(self.value.to_f64().unwrap_or(f64::NAN))?;
+                TemperatureConverter::from_float(k(celsius), Degree::Celsius).convert_to(*target)?
+            }
+            (_, Degree::GasMark) => {
+                let celsius = self.convert_to(Degree::Celsius)?;
+                k(celsius_to_gas_mark_chart(
+                    celsius.to_f64().unwrap_or(f64::NAN),
+                )?)
+            }
         };
 
         Ok(result)
     }
 
-    fn check_conversion_result(&self, result: f64, conversion_type: &str) -> Result<f64> {
+    fn check_conversion_result(
+        &self,
+        result: T,
+        conversion_type: &str,
+    ) -> Result<T, ConversionError> {
         if result.is_nan() {
-            return Err(anyhow!(
+            return Err(ConversionError::InvalidResult(format!(
                 "{} conversion resulted in NaN. Input value: {} {:?}",
-                conversion_type,
-                self.value,
-                self.unit
-            ));
+                conversion_type, self.value, self.unit
+            )));
         }
 
         if result.is_infinite() {
-            return Err(anyhow!(
+            return Err(ConversionError::Overflow(format!(
                 "{} conversion resulted in infinity. Input value: {} {:?}",
-                conversion_type,
-                self.value,
-                self.unit
-            ));
+                conversion_type, self.value, self.unit
+            )));
         }
 
         // Check for reasonable temperature ranges in the result
-        if result.abs() > 1e12 {
-            return Err(anyhow!(
+        if result.abs() > float(1e12) {
+            return Err(ConversionError::Overflow(format!(
                 "{} conversion resulted in an unrealistic temperature: {:.2}. Please check your input.",
                 conversion_type, result
-            ));
+            )));
+        }
+
+        // Zero is an ordinary temperature, but a subnormal result has lost
+        // most of its significant bits
+        if result.classify() == FpCategory::Subnormal {
+            return Err(ConversionError::PrecisionLoss(format!(
+                "{} conversion resulted in {:e}, too small to represent accurately. Input value: {} {:?}",
+                conversion_type, result, self.value, self.unit
+            )));
         }
 
         Ok(result)
     }
+}
+
+impl TemperatureConverter {
+    pub fn new(value: f64, unit: Degree) -> Self {
+        Self::from_float(value, unit)
+    }
+
+    /// `convert_to` along with the arithmetic it does, written out with the
+    /// input value: `(25 × 9/5) + 32` for 25 °C to °F
+    pub fn convert_to_explained(&self, target_unit: Degree) -> Result<(f64, String)> {
+        let result = self.convert_to(target_unit)?;
+        Ok((result, self.formula(&target_unit)))
+    }
+
+    /// The formula `convert_to` applies for this pair, one arm per arm there
+    fn formula(&self, target_unit: &Degree) -> String {
+        let x = self.value;
+        if std::mem::discriminant(&self.unit) == std::mem::discriminant(target_unit) {
+            return format!("{}", x);
+        }
+
+        match (&self.unit, target_unit) {
+            (Degree::Celsius, Degree::Fahrenheit) => format!("({} × 9/5) + 32", x),
+            (Degree::Celsius, Degree::Kelvin) => format!("{} + 273.15", x),
+            (Degree::Fahrenheit, Degree::Celsius) => format!("({} − 32) × 5/9", x),
+            (Degree::Fahrenheit, Degree::Kelvin) => format!("({} + 459.67) × 5/9", x),
+            (Degree::Kelvin, Degree::Celsius) => format!("{} − 273.15", x),
+            (Degree::Kelvin, Degree::Fahrenheit) => format!("({} × 9/5) − 459.67", x),
+            (Degree::Celsius, Degree::Delisle) => format!("(100 − {}) × 3/2", x),
+            (Degree::Fahrenheit, Degree::Delisle) => format!("(212 − {}) × 5/6", x),
+            (Degree::Kelvin, Degree::Delisle) => format!("(373.15 − {}) × 3/2", x),
+            (Degree::Delisle, Degree::Celsius) => format!("100 − ({} × 2/3)", x),
+            (Degree::Delisle, Degree::Fahrenheit) => format!("212 − ({} × 6/5)", x),
+            (Degree::Delisle, Degree::Kelvin) => format!("373.15 − ({} × 2/3)", x),
+            (Degree::Celsius, Degree::Newton) => format!("{} × 33/100", x),
+            (Degree::Fahrenheit, Degree::Newton) => format!("({} − 32) × 11/60", x),
+            (Degree::Kelvin, Degree::Newton) => format!("({} − 273.15) × 33/100", x),
+            (Degree::Delisle, Degree::Newton) => format!("33 − ({} × 11/50)", x),
+            (Degree::Newton, Degree::Celsius) => format!("{} × 100/33", x),
+            (Degree::Newton, Degree::Fahrenheit) => format!("({} × 60/11) + 32", x),
+            (Degree::Newton, Degree::Kelvin) => format!("({} × 100/33) + 273.15", x),
+            (Degree::Newton, Degree::Delisle) => format!("(33 − {}) × 50/11", x),
+            (Degree::GasMark, _) | (_, Degree::GasMark) => format!(
+                "oven chart lookup of {} {} (linear between marks)",
+                x, self.unit
+            ),
+            _ => unreachable!("every pair of distinct units has a formula"),
+        }
+    }
 
     pub fn convert_to_all(&self) -> Result<TemperatureResults> {
         Ok(TemperatureResults {
             celsius: self.convert_to(Degree::Celsius)?,
             fahrenheit: self.convert_to(Degree::Fahrenheit)?,
             kelvin: self.convert_to(Degree::Kelvin)?,
+            // Gas mark is left out: it only covers oven temperatures, so most
+            // inputs would fail the whole call.
+            delisle: self.convert_to(Degree::Delisle)?,
+            newton: self.convert_to(Degree::Newton)?,
         })
     }
 }
 
+fn gas_mark_chart_to_celsius(gas_mark: f64) -> Result<f64> {
+    let (first, last) = (GAS_MARK_CHART[0], GAS_MARK_CHART[GAS_MARK_CHART.len() - 1]);
+    if gas_mark.is_nan() || gas_mark < first.0 || gas_mark > last.0 {
+        return Err(anyhow!(
+            "Gas mark {} is out of range. Oven gas marks run from 1/4 ({}) to {}.",
+            gas_mark,
+            first.0,
+            last.0
+        ));
+    }
+
+    Ok(interpolate(gas_mark, &GAS_MARK_CHART))
+}
+
+fn celsius_to_gas_mark_chart(celsius: f64) -> Result<f64> {
+    let (first, last) = (GAS_MARK_CHART[0], GAS_MARK_CHART[GAS_MARK_CHART.len() - 1]);
+    if celsius.is_nan() || celsius < first.1 || celsius > last.1 {
+        return Err(anyhow!(
+            "{:.2}°C is outside the oven gas mark range ({}°C to {}°C).",
+            celsius,
+            first.1,
+            last.1
+        ));
+    }
+
+    let inverted: Vec<(f64, f64)> = GAS_MARK_CHART.iter().map(|&(mark, c)| (c, mark)).collect();
+    Ok(interpolate(celsius, &inverted))
+}
+
+/// Piecewise-linear lookup over monotonically increasing (x, y) points.
+/// The caller guarantees `x` lies within the first and last point.
+fn interpolate(x: f64, points: &[(f64, f64)]) -> f64 {
+    for pair in points.windows(2) {
+        let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+        if x <= x1 {
+            return y0 + (x - x0) * (y1 - y0) / (x1 - x0);
+        }
+    }
+    points[points.len() - 1].1
+}
+
+impl TemperatureResults {
+    /// Every unit with its value, in the order `convert_to_all` fills them
+    /// (so without gas mark)
+    pub fn rows(&self) -> Vec<(Degree, f64)> {
+        vec![
+            (Degree::Celsius, self.celsius),
+            (Degree::Fahrenheit, self.fahrenheit),
+            (Degree::Kelvin, self.kelvin),
+            (Degree::Delisle, self.delisle),
+            (Degree::Newton, self.newton),
+        ]
+    }
+}
+
 impl Display for TemperatureResults {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> FmtResult {
         write!(
             f,
-            "Celsius: {:.2}°C, Fahrenheit: {:.2}°F, Kelvin: {:.2}K",
-            self.celsius, self.fahrenheit, self.kelvin
+            "Celsius: {:.2}°C, Fahrenheit: {:.2}°F, Kelvin: {:.2}K, Delisle: {:.2}°De, Newton: {:.2}°N",
+            self.celsius, self.fahrenheit, self.kelvin, self.delisle, self.newton
         )
     }
 }
@@ -136,9 +354,129 @@ pub fn kelvin_to_fahrenheit(kelvin: f64) -> Result<f64> {
     TemperatureConverter::new(kelvin, Degree::Kelvin).convert_to(Degree::Fahrenheit)
 }
 
+pub fn celsius_to_delisle(celsius: f64) -> Result<f64> {
+    TemperatureConverter::new(celsius, Degree::Celsius).convert_to(Degree::Delisle)
+}
+
+pub fn delisle_to_celsius(delisle: f64) -> Result<f64> {
+    TemperatureConverter::new(delisle, Degree::Delisle).convert_to(Degree::Celsius)
+}
+
+pub fn celsius_to_newton(celsius: f64) -> Result<f64> {
+    TemperatureConverter::new(celsius, Degree::Celsius).convert_to(Degree::Newton)
+}
+
+pub fn newton_to_celsius(newton: f64) -> Result<f64> {
+    TemperatureConverter::new(newton, Degree::Newton).convert_to(Degree::Celsius)
+}
+
+pub fn gas_mark_to_celsius(gas_mark: f64) -> Result<f64> {
+    TemperatureConverter::new(gas_mark, Degree::GasMark).convert_to(Degree::Celsius)
+}
+
+pub fn celsius_to_gas_mark(celsius: f64) -> Result<f64> {
+    TemperatureConverter::new(celsius, Degree::Celsius).convert_to(Degree::GasMark)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn test_f32_matches_f64() -> Result<()> {
+        for &from in Degree::all() {
+            for &to in Degree::all() {
+                // 200 °C in `from`, hot enough for every unit to have a value
+                let value = TemperatureConverter::new(200.0, Degree::Celsius).convert_to(from)?;
+                let single = TemperatureConverter::from_float(value as f32, from).convert_to(to)?;
+                let double = TemperatureConverter::new(value, from).convert_to(to)?;
+                assert!(
+                    (single as f64 - double).abs()
+                        <= double.abs().max(1.0) * f32::EPSILON as f64 * 4.0,
+                    "{:?} to {:?}: {} (f32) vs {} (f64)",
+                    from,
+                    to,
+                    single,
+                    double
+                );
+            }
+        }
+
+        let body = TemperatureConverter::from_float(37.0f32, Degree::Celsius);
+        assert_eq!(body.convert_to(Degree::Fahrenheit)?, 98.6f32);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pathological_results() {
+        let error = |value: f64, from: Degree, to: Degree| {
+            TemperatureConverter::new(value, from)
+                .convert_to(to)
+                .unwrap_err()
+                .downcast::<ConversionError>()
+                .unwrap()
+        };
+
+        assert!(matches!(
+            error(f64::NAN, Degree::Celsius, Degree::Fahrenheit),
+            ConversionError::InvalidResult(_)
+        ));
+        assert!(matches!(
+            error(1e300, Degree::Kelvin, Degree::Fahrenheit),
+            ConversionError::Overflow(_)
+        ));
+        assert!(matches!(
+            error(f64::MAX, Degree::Celsius, Degree::Fahrenheit),
+            ConversionError::Overflow(_)
+        ));
+        assert!(matches!(
+            error(1e-310, Degree::Celsius, Degree::Newton),
+            ConversionError::PrecisionLoss(_)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_results_serialize() -> Result<()> {
+        let results = TemperatureConverter::new(100.0, Degree::Celsius).convert_to_all()?;
+        let json: serde_json::Value = serde_json::to_value(&results)?;
+        let keys: Vec<&str> = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(keys.len(), 5);
+        for key in ["celsius", "fahrenheit", "kelvin", "delisle", "newton"] {
+            assert!(keys.contains(&key), "{}", key);
+        }
+        assert_eq!(json["fahrenheit"], 212.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_explained_formulas() -> Result<()> {
+        let (result, formula) = TemperatureConverter::new(25.0, Degree::Celsius)
+            .convert_to_explained(Degree::Fahrenheit)?;
+        assert_eq!(formula, "(25 × 9/5) + 32");
+        assert_eq!(result, 77.0);
+
+        let (_, formula) = TemperatureConverter::new(-40.0, Degree::Fahrenheit)
+            .convert_to_explained(Degree::Kelvin)?;
+        assert_eq!(formula, "(-40 + 459.67) × 5/9");
+
+        // Every pair is covered: `formula` must not fall through
+        for from in Degree::value_variants() {
+            for to in Degree::value_variants() {
+                let converter = TemperatureConverter::new(4.0, *from);
+                if converter.convert_to(*to).is_ok() {
+                    converter.convert_to_explained(*to)?;
+                }
+            }
+        }
+        Ok(())
+    }
 
     #[test]
     fn test_celsius_conversions() -> Result<()> {
@@ -169,6 +507,67 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_delisle_conversions() -> Result<()> {
+        // Delisle is inverted: freezing is 150 °De and boiling is 0 °De
+        assert_eq!(celsius_to_delisle(0.0)?, 150.0);
+        assert_eq!(celsius_to_delisle(100.0)?, 0.0);
+        assert_eq!(delisle_to_celsius(150.0)?, 0.0);
+        assert_eq!(delisle_to_celsius(0.0)?, 100.0);
+
+        let converter = TemperatureConverter::new(0.0, Degree::Delisle);
+        assert_eq!(converter.convert_to(Degree::Fahrenheit)?, 212.0);
+        assert!((converter.convert_to(Degree::Kelvin)? - 373.15).abs() < 1e-10);
+
+        // Negative Delisle values are hotter than boiling, not below absolute zero
+        let converter = TemperatureConverter::new(-150.0, Degree::Delisle);
+        assert_eq!(converter.convert_to(Degree::Celsius)?, 200.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_newton_conversions() -> Result<()> {
+        assert!((newton_to_celsius(0.0)? - 0.0).abs() < 1e-10);
+        assert!((newton_to_celsius(33.0)? - 100.0).abs() < 1e-10);
+        assert!((celsius_to_newton(100.0)? - 33.0).abs() < 1e-10);
+
+        let converter = TemperatureConverter::new(33.0, Degree::Newton);
+        assert!((converter.convert_to(Degree::Fahrenheit)? - 212.0).abs() < 1e-10);
+        assert!((converter.convert_to(Degree::Kelvin)? - 373.15).abs() < 1e-10);
+        assert!(converter.convert_to(Degree::Delisle)?.abs() < 1e-10);
+
+        let all = TemperatureConverter::new(0.0, Degree::Celsius).convert_to_all()?;
+        assert!(all.newton.abs() < 1e-10);
+        Ok(())
+    }
+
+    #[test]
+    fn test_gas_mark_conversions() -> Result<()> {
+        assert_eq!(gas_mark_to_celsius(4.0)?, 180.0);
+        assert_eq!(celsius_to_gas_mark(180.0)?, 4.0);
+        assert_eq!(gas_mark_to_celsius(7.0)?, 220.0);
+        assert_eq!(celsius_to_gas_mark(220.0)?, 7.0);
+
+        // Between chart points the value is interpolated
+        assert_eq!(gas_mark_to_celsius(6.5)?, 210.0);
+
+        let converter = TemperatureConverter::new(350.0, Degree::Fahrenheit);
+        let gas_mark = converter.convert_to(Degree::GasMark)?;
+        assert!((gas_mark - 3.7).abs() < 0.1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_gas_mark_out_of_range() {
+        assert!(gas_mark_to_celsius(0.1).is_err());
+        assert!(gas_mark_to_celsius(10.0).is_err());
+        assert!(celsius_to_gas_mark(20.0).is_err());
+        assert!(celsius_to_gas_mark(300.0).is_err());
+
+        let err = gas_mark_to_celsius(12.0).unwrap_err().to_string();
+        assert!(err.contains("out of range"));
+    }
+
     #[test]
     fn test_helper_functions() -> Result<()> {
         assert_eq!(celsius_to_fahrenheit(100.0)?, 212.0);
@@ -0,0 +1,275 @@
+use super::Time;
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use std::fmt::{Display, Result as FmtResult};
+use std::sync::OnceLock;
+
+/// Largest duration result accepted before the input is considered unrealistic
+const MAX_TIME_RESULT: f64 = 1e18;
+
+/// Average month and year lengths. Calendar months and years vary, so
+/// conversions involving them are approximate.
+pub const DAYS_PER_MONTH: f64 = 30.44;
+pub const DAYS_PER_YEAR: f64 = 365.25;
+
+/// `milliseconds_per_unit` for every `Time`, indexed by variant
+static FACTOR_TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+
+pub struct TimeConverter {
+    pub value: f64,
+    pub unit: Time,
+}
+
+pub struct TimeResults {
+    pub second: f64,
+    pub minute: f64,
+    pub hour: f64,
+    pub day: f64,
+    pub week: f64,
+    pub millisecond: f64,
+    pub month: f64,
+    pub year: f64,
+}
+
+impl TimeConverter {
+    pub fn new(value: f64, unit: Time) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn convert_to(&self, target_unit: Time) -> Result<f64> {
+        let from = Self::milliseconds_per_unit(&self.unit);
+        let to = Self::milliseconds_per_unit(&target_unit);
+        self.scale(from, to, &target_unit)
+    }
+
+    /// Builds the factor table used by `convert_to_all` ahead of time
+    pub fn warm_up() {
+        Self::factor_table();
+    }
+
+    fn factor_table() -> &'static [f64] {
+        FACTOR_TABLE.get_or_init(|| {
+            Time::value_variants()
+                .iter()
+                .map(Self::milliseconds_per_unit)
+                .collect()
+        })
+    }
+
+    fn scale(&self, from: f64, to: f64, target_unit: &Time) -> Result<f64> {
+        // Always scale by a ratio of at least 1 so exact relationships stay
+        // exact (1 d is 24 h, 1 wk is 7 d)
+        let result = if from >= to {
+            self.value * (from / to)
+        } else {
+            self.value / (to / from)
+        };
+
+        self.check_conversion_result(result, &format!("{:?} to {:?}", self.unit, target_unit))
+    }
+
+    /// Length of one unit in milliseconds. A day is always 86,400 s here;
+    /// leap seconds and daylight-saving shifts are calendar concerns. Months
+    /// and years are averages (see `is_approximate`).
+    fn milliseconds_per_unit(unit: &Time) -> f64 {
+        match unit {
+            Time::Second => 1_000.0,
+            Time::Minute => 60_000.0,
+            Time::Hour => 3_600_000.0,
+            Time::Day => 86_400_000.0,
+            Time::Week => 604_800_000.0,
+            Time::Millisecond => 1.0,
+            Time::Month => DAYS_PER_MONTH * 86_400_000.0,
+            Time::Year => DAYS_PER_YEAR * 86_400_000.0,
+        }
+    }
+
+    fn check_conversion_result(&self, result: f64, conversion_type: &str) -> Result<f64> {
+        if result.is_nan() {
+            return Err(anyhow!(
+                "{} conversion resulted in NaN. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result.is_infinite() {
+            return Err(anyhow!(
+                "{} conversion resulted in infinity. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result < 0.0 {
+            return Err(anyhow!(
+                "{} conversion resulted in negative duration: {:.6}. This should not happen with positive input.",
+                conversion_type, result
+            ));
+        }
+
+        if result > MAX_TIME_RESULT {
+            return Err(anyhow!(
+                "{} conversion resulted in an unrealistically long duration: {:.2}. Please check your input.",
+                conversion_type, result
+            ));
+        }
+
+        Ok(result)
+    }
+
+    /// Same results as calling `convert_to` for every unit, with the factors
+    /// taken from a table built once
+    pub fn convert_to_all(&self) -> Result<TimeResults> {
+        let factors = Self::factor_table();
+        let from = factors[self.unit as usize];
+        let convert = |target: Time| self.scale(from, factors[target as usize], &target);
+
+        Ok(TimeResults {
+            second: convert(Time::Second)?,
+            minute: convert(Time::Minute)?,
+            hour: convert(Time::Hour)?,
+            day: convert(Time::Day)?,
+            week: convert(Time::Week)?,
+            millisecond: convert(Time::Millisecond)?,
+            month: convert(Time::Month)?,
+            year: convert(Time::Year)?,
+        })
+    }
+}
+
+impl TimeResults {
+    /// Every unit with its value, in the order `convert_to_all` fills them
+    pub fn rows(&self) -> Vec<(Time, f64)> {
+        vec![
+            (Time::Second, self.second),
+            (Time::Minute, self.minute),
+            (Time::Hour, self.hour),
+            (Time::Day, self.day),
+            (Time::Week, self.week),
+            (Time::Millisecond, self.millisecond),
+            (Time::Month, self.month),
+            (Time::Year, self.year),
+        ]
+    }
+}
+
+impl Display for TimeResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "Second: {:.6} s, Minute: {:.6} min, Hour: {:.6} h, Day: {:.6} d, Week: {:.6} wk, Millisecond: {:.6} ms, Month: {:.6} mo, Year: {:.6} yr",
+            self.second, self.minute, self.hour, self.day, self.week, self.millisecond, self.month, self.year
+        )
+    }
+}
+
+/// Whether a unit stands for an average rather than a fixed length
+pub fn is_approximate(unit: &Time) -> bool {
+    matches!(unit, Time::Month | Time::Year)
+}
+
+// Helper functions with error handling
+pub fn day_to_s(value: f64) -> Result<f64> {
+    TimeConverter::new(value, Time::Day).convert_to(Time::Second)
+}
+
+pub fn week_to_day(value: f64) -> Result<f64> {
+    TimeConverter::new(value, Time::Week).convert_to(Time::Day)
+}
+
+pub fn h_to_min(value: f64) -> Result<f64> {
+    TimeConverter::new(value, Time::Hour).convert_to(Time::Minute)
+}
+
+pub fn min_to_s(value: f64) -> Result<f64> {
+    TimeConverter::new(value, Time::Minute).convert_to(Time::Second)
+}
+
+pub fn s_to_ms(value: f64) -> Result<f64> {
+    TimeConverter::new(value, Time::Second).convert_to(Time::Millisecond)
+}
+
+pub fn ms_to_s(value: f64) -> Result<f64> {
+    TimeConverter::new(value, Time::Millisecond).convert_to(Time::Second)
+}
+
+pub fn s_to_h(value: f64) -> Result<f64> {
+    TimeConverter::new(value, Time::Second).convert_to(Time::Hour)
+}
+
+pub fn day_to_month(value: f64) -> Result<f64> {
+    TimeConverter::new(value, Time::Day).convert_to(Time::Month)
+}
+
+pub fn year_to_day(value: f64) -> Result<f64> {
+    TimeConverter::new(value, Time::Year).convert_to(Time::Day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_and_week() -> Result<()> {
+        assert_eq!(day_to_s(1.0)?, 86_400.0);
+        assert_eq!(week_to_day(1.0)?, 7.0);
+        assert_eq!(h_to_min(1.5)?, 90.0);
+        assert_eq!(min_to_s(1.0)?, 60.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_calendar_units() -> Result<()> {
+        assert_eq!(week_to_day(1.0)?, 7.0);
+        assert_eq!(
+            TimeConverter::new(1.0, Time::Week).convert_to(Time::Second)?,
+            604_800.0
+        );
+        assert_eq!(year_to_day(1.0)?, 365.25);
+        assert!((day_to_month(90.0)? - 90.0 / 30.44).abs() < 1e-12);
+
+        assert!(is_approximate(&Time::Month));
+        assert!(is_approximate(&Time::Year));
+        assert!(!is_approximate(&Time::Week));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sub_second_conversions() -> Result<()> {
+        assert_eq!(s_to_ms(1.0)?, 1000.0);
+        assert_eq!(ms_to_s(250.0)?, 0.25);
+        assert_eq!(s_to_h(5400.0)?, 1.5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_all() -> Result<()> {
+        TimeConverter::warm_up();
+        let converter = TimeConverter::new(1.0, Time::Week);
+        let all = converter.convert_to_all()?;
+
+        assert_eq!(all.day, 7.0);
+        assert_eq!(all.hour, 168.0);
+        assert_eq!(
+            all.second.to_bits(),
+            converter.convert_to(Time::Second)?.to_bits()
+        );
+        assert!(all.to_string().contains("Week: 1.000000 wk"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_conversions() {
+        let converter = TimeConverter::new(f64::NAN, Time::Second);
+        assert!(converter.convert_to(Time::Minute).is_err());
+
+        let converter = TimeConverter::new(f64::INFINITY, Time::Second);
+        assert!(converter.convert_to(Time::Minute).is_err());
+
+        let converter = TimeConverter::new(-1.0, Time::Second);
+        assert!(converter.convert_to(Time::Minute).is_err());
+    }
+}
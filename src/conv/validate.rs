@@ -0,0 +1,146 @@
+use super::Degree;
+use crate::errors::ValidationError;
+
+/// Largest input magnitude accepted before `f64` precision becomes a concern
+const MAX_INPUT: f64 = 1e15;
+/// Largest length accepted, in any unit
+const MAX_LENGTH: f64 = 1e12;
+
+/// Rejects NaN, infinite and implausibly large values. `context` names the
+/// value in the message, e.g. "Temperature value".
+pub fn validate_numeric_input(value: f64, context: &str) -> Result<(), ValidationError> {
+    if value.is_nan() {
+        return Err(ValidationError::InvalidNumber(format!(
+            "{} cannot be NaN (Not a Number)",
+            context
+        )));
+    }
+
+    if value.is_infinite() {
+        return Err(ValidationError::InvalidNumber(format!(
+            "{} cannot be infinite",
+            context
+        )));
+    }
+
+    // Check for extremely large numbers that might cause precision issues
+    if value.abs() > MAX_INPUT {
+        return Err(ValidationError::ValueTooLarge(format!(
+            "{} is too large (absolute value exceeds 1e15). Please use a smaller number.",
+            context
+        )));
+    }
+
+    Ok(())
+}
+
+/// Rejects temperatures colder than absolute zero in `unit`. Gas mark has
+/// its own oven range, which its converter checks.
+pub fn check_absolute_zero(value: f64, unit: &Degree) -> Result<(), ValidationError> {
+    let message = match unit {
+        Degree::Kelvin if value < 0.0 => format!(
+            "Kelvin temperature cannot be negative ({}K). Minimum is 0 K (absolute zero).",
+            value
+        ),
+        Degree::Celsius if value < -273.15 => format!(
+            "Celsius temperature cannot be below absolute zero ({}°C < -273.15°C).",
+            value
+        ),
+        Degree::Fahrenheit if value < -459.67 => format!(
+            "Fahrenheit temperature cannot be below absolute zero ({}°F < -459.67°F).",
+            value
+        ),
+        Degree::Newton if value < -90.1395 => format!(
+            "Newton temperature cannot be below absolute zero ({}°N < -90.1395°N).",
+            value
+        ),
+        // Delisle is inverted, so absolute zero is the *largest* valid value
+        Degree::Delisle if value > 559.725 => format!(
+            "Delisle temperature cannot be colder than absolute zero ({}°De > 559.725°De).",
+            value
+        ),
+        _ => return Ok(()),
+    };
+    Err(ValidationError::PhysicallyInvalid(message))
+}
+
+/// The checks every length input goes through: a plain number that is
+/// neither negative nor astronomically large
+pub fn validate_length_input(value: f64) -> Result<(), ValidationError> {
+    validate_numeric_input(value, "Length value")?;
+
+    if value < 0.0 {
+        return Err(ValidationError::PhysicallyInvalid(format!(
+            "Length cannot be negative ({}). Please provide a positive value.",
+            value
+        )));
+    }
+
+    if value > MAX_LENGTH {
+        return Err(ValidationError::ValueTooLarge(format!(
+            "Length value {} is unrealistically large. Please check your input.",
+            value
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_input_errors() {
+        assert!(validate_numeric_input(25.0, "Value").is_ok());
+        assert!(matches!(
+            validate_numeric_input(f64::NAN, "Value"),
+            Err(ValidationError::InvalidNumber(_))
+        ));
+        assert!(matches!(
+            validate_numeric_input(f64::NEG_INFINITY, "Value"),
+            Err(ValidationError::InvalidNumber(_))
+        ));
+        assert!(matches!(
+            validate_numeric_input(1e16, "Value"),
+            Err(ValidationError::ValueTooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_absolute_zero() {
+        assert!(check_absolute_zero(-273.15, &Degree::Celsius).is_ok());
+        assert!(check_absolute_zero(559.0, &Degree::Delisle).is_ok());
+        assert!(check_absolute_zero(-5.0, &Degree::GasMark).is_ok());
+
+        for (value, unit) in [
+            (-1.0, Degree::Kelvin),
+            (-300.0, Degree::Celsius),
+            (-460.0, Degree::Fahrenheit),
+            (-91.0, Degree::Newton),
+            (600.0, Degree::Delisle),
+        ] {
+            assert!(matches!(
+                check_absolute_zero(value, &unit),
+                Err(ValidationError::PhysicallyInvalid(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_length_input_errors() {
+        assert!(validate_length_input(0.0).is_ok());
+        assert!(matches!(
+            validate_length_input(f64::NAN),
+            Err(ValidationError::InvalidNumber(_))
+        ));
+        assert!(matches!(
+            validate_length_input(-1.0),
+            Err(ValidationError::PhysicallyInvalid(_))
+        ));
+        assert!(matches!(
+            validate_length_input(1e13),
+            Err(ValidationError::ValueTooLarge(_))
+        ));
+    }
+}
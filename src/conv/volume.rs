@@ -0,0 +1,264 @@
+use super::Volume;
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use std::fmt::{Display, Result as FmtResult};
+use std::sync::OnceLock;
+
+/// Largest volume result accepted before the input is considered unrealistic
+const MAX_VOLUME_RESULT: f64 = 1e18;
+
+/// `nanoliters_per_unit` for every `Volume`, indexed by variant
+static FACTOR_TABLE: OnceLock<Vec<f64>> = OnceLock::new();
+
+pub struct VolumeConverter {
+    pub value: f64,
+    pub unit: Volume,
+}
+
+pub struct VolumeResults {
+    pub liter: f64,
+    pub milliliter: f64,
+    pub cubic_meter: f64,
+    pub gallon_us: f64,
+    pub gallon_imperial: f64,
+    pub quart: f64,
+    pub pint: f64,
+    pub fluid_ounce: f64,
+}
+
+impl VolumeConverter {
+    pub fn new(value: f64, unit: Volume) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn convert_to(&self, target_unit: Volume) -> Result<f64> {
+        let from = Self::nanoliters_per_unit(&self.unit);
+        let to = Self::nanoliters_per_unit(&target_unit);
+        self.scale(from, to, &target_unit)
+    }
+
+    /// Builds the factor table used by `convert_to_all` ahead of time
+    pub fn warm_up() {
+        Self::factor_table();
+    }
+
+    fn factor_table() -> &'static [f64] {
+        FACTOR_TABLE.get_or_init(|| {
+            Volume::value_variants()
+                .iter()
+                .map(Self::nanoliters_per_unit)
+                .collect()
+        })
+    }
+
+    fn scale(&self, from: f64, to: f64, target_unit: &Volume) -> Result<f64> {
+        // Always scale by a ratio of at least 1 so exact relationships stay
+        // exact (1 gal is 4 qt, 8 pt and 128 fl oz)
+        let result = if from >= to {
+            self.value * (from / to)
+        } else {
+            self.value / (to / from)
+        };
+
+        self.check_conversion_result(result, &format!("{:?} to {:?}", self.unit, target_unit))
+    }
+
+    /// Size of one unit in nanoliters. The quart, pint and fluid ounce are
+    /// the US customary (liquid) units, built on the US gallon of exactly
+    /// 231 in³ = 3.785411784 L; the fluid ounce (1/128 gal) is a binary-exact
+    /// 29,573,529.5625 nL. The imperial gallon is exactly 4.54609 L.
+    fn nanoliters_per_unit(unit: &Volume) -> f64 {
+        match unit {
+            Volume::Liter => 1e9,
+            Volume::Milliliter => 1e6,
+            Volume::CubicMeter => 1e12,
+            Volume::GallonUS => 3_785_411_784.0,
+            Volume::GallonImperial => 4_546_090_000.0,
+            Volume::Quart => 946_352_946.0,
+            Volume::Pint => 473_176_473.0,
+            Volume::FluidOunce => 29_573_529.562_5,
+        }
+    }
+
+    fn check_conversion_result(&self, result: f64, conversion_type: &str) -> Result<f64> {
+        if result.is_nan() {
+            return Err(anyhow!(
+                "{} conversion resulted in NaN. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result.is_infinite() {
+            return Err(anyhow!(
+                "{} conversion resulted in infinity. Input value: {} {:?}",
+                conversion_type,
+                self.value,
+                self.unit
+            ));
+        }
+
+        if result < 0.0 {
+            return Err(anyhow!(
+                "{} conversion resulted in negative volume: {:.6}. This should not happen with positive input.",
+                conversion_type, result
+            ));
+        }
+
+        if result > MAX_VOLUME_RESULT {
+            return Err(anyhow!(
+                "{} conversion resulted in an unrealistically large volume: {:.2}. Please check your input.",
+                conversion_type, result
+            ));
+        }
+
+        Ok(result)
+    }
+
+    /// Same results as calling `convert_to` for every unit, with the factors
+    /// taken from a table built once
+    pub fn convert_to_all(&self) -> Result<VolumeResults> {
+        let factors = Self::factor_table();
+        let from = factors[self.unit as usize];
+        let convert = |target: Volume| self.scale(from, factors[target as usize], &target);
+
+        Ok(VolumeResults {
+            liter: convert(Volume::Liter)?,
+            milliliter: convert(Volume::Milliliter)?,
+            cubic_meter: convert(Volume::CubicMeter)?,
+            gallon_us: convert(Volume::GallonUS)?,
+            gallon_imperial: convert(Volume::GallonImperial)?,
+            quart: convert(Volume::Quart)?,
+            pint: convert(Volume::Pint)?,
+            fluid_ounce: convert(Volume::FluidOunce)?,
+        })
+    }
+}
+
+impl VolumeResults {
+    /// Every unit with its value, in the order `convert_to_all` fills them
+    pub fn rows(&self) -> Vec<(Volume, f64)> {
+        vec![
+            (Volume::Liter, self.liter),
+            (Volume::Milliliter, self.milliliter),
+            (Volume::CubicMeter, self.cubic_meter),
+            (Volume::GallonUS, self.gallon_us),
+            (Volume::GallonImperial, self.gallon_imperial),
+            (Volume::Quart, self.quart),
+            (Volume::Pint, self.pint),
+            (Volume::FluidOunce, self.fluid_ounce),
+        ]
+    }
+}
+
+impl Display for VolumeResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "Liter: {:.6} L, Milliliter: {:.6} mL, Cubic Meter: {:.6} m³, Gallon (US): {:.6} gal, Gallon (Imperial): {:.6} imp gal, Quart: {:.6} qt, Pint: {:.6} pt, Fluid Ounce: {:.6} fl oz",
+            self.liter, self.milliliter, self.cubic_meter, self.gallon_us, self.gallon_imperial, self.quart, self.pint, self.fluid_ounce
+        )
+    }
+}
+
+// Helper functions with error handling
+pub fn l_to_ml(value: f64) -> Result<f64> {
+    VolumeConverter::new(value, Volume::Liter).convert_to(Volume::Milliliter)
+}
+
+pub fn ml_to_l(value: f64) -> Result<f64> {
+    VolumeConverter::new(value, Volume::Milliliter).convert_to(Volume::Liter)
+}
+
+pub fn gal_to_l(value: f64) -> Result<f64> {
+    VolumeConverter::new(value, Volume::GallonUS).convert_to(Volume::Liter)
+}
+
+pub fn l_to_gal(value: f64) -> Result<f64> {
+    VolumeConverter::new(value, Volume::Liter).convert_to(Volume::GallonUS)
+}
+
+pub fn impgal_to_l(value: f64) -> Result<f64> {
+    VolumeConverter::new(value, Volume::GallonImperial).convert_to(Volume::Liter)
+}
+
+pub fn impgal_to_gal(value: f64) -> Result<f64> {
+    VolumeConverter::new(value, Volume::GallonImperial).convert_to(Volume::GallonUS)
+}
+
+pub fn m3_to_l(value: f64) -> Result<f64> {
+    VolumeConverter::new(value, Volume::CubicMeter).convert_to(Volume::Liter)
+}
+
+pub fn gal_to_qt(value: f64) -> Result<f64> {
+    VolumeConverter::new(value, Volume::GallonUS).convert_to(Volume::Quart)
+}
+
+pub fn qt_to_pt(value: f64) -> Result<f64> {
+    VolumeConverter::new(value, Volume::Quart).convert_to(Volume::Pint)
+}
+
+pub fn floz_to_ml(value: f64) -> Result<f64> {
+    VolumeConverter::new(value, Volume::FluidOunce).convert_to(Volume::Milliliter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_conversions() -> Result<()> {
+        assert_eq!(l_to_ml(1.0)?, 1000.0);
+        assert_eq!(ml_to_l(250.0)?, 0.25);
+        assert_eq!(m3_to_l(1.0)?, 1000.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_us_customary_conversions() -> Result<()> {
+        assert_eq!(gal_to_l(1.0)?, 3.785411784);
+        assert_eq!(gal_to_qt(1.0)?, 4.0);
+        assert_eq!(qt_to_pt(1.0)?, 2.0);
+        assert!((floz_to_ml(1.0)? - 29.5735295625).abs() < 1e-12);
+
+        let converter = VolumeConverter::new(1.0, Volume::GallonUS);
+        assert_eq!(converter.convert_to(Volume::FluidOunce)?, 128.0);
+        assert!((l_to_gal(gal_to_l(2.5)?)? - 2.5).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_us_and_imperial_gallons_differ() -> Result<()> {
+        assert_eq!(impgal_to_l(1.0)?, 4.54609);
+        assert_ne!(impgal_to_l(1.0)?, gal_to_l(1.0)?);
+        assert!((impgal_to_gal(1.0)? - 1.20095).abs() < 1e-5);
+        assert_eq!(Volume::GallonUS.to_string(), "gal");
+        assert_eq!(Volume::GallonImperial.to_string(), "imp gal");
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_all() -> Result<()> {
+        VolumeConverter::warm_up();
+        let converter = VolumeConverter::new(1.0, Volume::GallonUS);
+        let all = converter.convert_to_all()?;
+
+        assert_eq!(all.pint, 8.0);
+        assert_eq!(all.liter.to_bits(), gal_to_l(1.0)?.to_bits());
+        assert!(all.to_string().contains("Gallon (US): 1.000000 gal"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_conversions() {
+        let converter = VolumeConverter::new(f64::NAN, Volume::Liter);
+        assert!(converter.convert_to(Volume::Milliliter).is_err());
+
+        let converter = VolumeConverter::new(f64::INFINITY, Volume::Liter);
+        assert!(converter.convert_to(Volume::Milliliter).is_err());
+
+        let converter = VolumeConverter::new(-1.0, Volume::Liter);
+        assert!(converter.convert_to(Volume::Milliliter).is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use super::WireGauge;
+use anyhow::{anyhow, Result};
+
+/// Thickest gauge handled (4/0, written as -3) and thinnest (40)
+pub const MIN_AWG: i32 = -3;
+pub const MAX_AWG: i32 = 40;
+
+/// Converts between American Wire Gauge numbers and conductor diameter in
+/// millimeters using d = 0.127 mm × 92^((36 − n) / 39). The AWG side is
+/// discrete, so converting a diameter snaps to the nearest whole gauge.
+pub struct WireConverter {
+    pub value: f64,
+    pub unit: WireGauge,
+}
+
+impl WireConverter {
+    pub fn new(value: f64, unit: WireGauge) -> Self {
+        Self { value, unit }
+    }
+
+    pub fn convert_to(&self, target_unit: WireGauge) -> Result<f64> {
+        let result = match (&self.unit, &target_unit) {
+            (WireGauge::Awg, WireGauge::Awg) => self.check_gauge(self.value)?,
+            (WireGauge::Millimeter, WireGauge::Millimeter) => self.check_diameter(self.value)?,
+            (WireGauge::Awg, WireGauge::Millimeter) => awg_to_mm(self.check_gauge(self.value)?),
+            (WireGauge::Millimeter, WireGauge::Awg) => {
+                let gauge = exact_gauge(self.check_diameter(self.value)?);
+                let nearest = gauge.round();
+                if nearest < MIN_AWG as f64 || nearest > MAX_AWG as f64 {
+                    return Err(anyhow!(
+                        "{} mm is outside the AWG range ({:.3} mm for 4/0 to {:.4} mm for AWG {}).",
+                        self.value,
+                        awg_to_mm(MIN_AWG as f64),
+                        awg_to_mm(MAX_AWG as f64),
+                        MAX_AWG
+                    ));
+                }
+                nearest
+            }
+        };
+
+        Ok(result)
+    }
+
+    /// Whether converting this diameter to AWG had to round to a whole gauge
+    pub fn snapped_to_gauge(&self) -> bool {
+        match self.unit {
+            WireGauge::Millimeter => {
+                let gauge = exact_gauge(self.value);
+                (gauge - gauge.round()).abs() > 1e-6
+            }
+            WireGauge::Awg => false,
+        }
+    }
+
+    fn check_gauge(&self, gauge: f64) -> Result<f64> {
+        if gauge.fract() != 0.0 || gauge < MIN_AWG as f64 || gauge > MAX_AWG as f64 {
+            return Err(anyhow!(
+                "AWG {} is not a valid gauge. Use a whole number from {} (4/0; 0/0 is -1) to {}.",
+                gauge,
+                MIN_AWG,
+                MAX_AWG
+            ));
+        }
+        Ok(gauge)
+    }
+
+    fn check_diameter(&self, diameter: f64) -> Result<f64> {
+        if !diameter.is_finite() || diameter <= 0.0 {
+            return Err(anyhow!(
+                "Wire diameter must be a positive number of millimeters, got {}.",
+                diameter
+            ));
+        }
+        Ok(diameter)
+    }
+}
+
+fn awg_to_mm(gauge: f64) -> f64 {
+    0.127 * 92f64.powf((36.0 - gauge) / 39.0)
+}
+
+/// The (fractional) gauge number for a diameter, before snapping
+fn exact_gauge(diameter: f64) -> f64 {
+    36.0 - 39.0 * (diameter / 0.127).ln() / 92f64.ln()
+}
+
+// Helper functions with error handling
+pub fn awg_to_diameter_mm(gauge: f64) -> Result<f64> {
+    WireConverter::new(gauge, WireGauge::Awg).convert_to(WireGauge::Millimeter)
+}
+
+pub fn diameter_mm_to_awg(diameter: f64) -> Result<f64> {
+    WireConverter::new(diameter, WireGauge::Millimeter).convert_to(WireGauge::Awg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_published_diameters() -> Result<()> {
+        assert!((awg_to_diameter_mm(0.0)? - 8.251).abs() < 0.001);
+        assert!((awg_to_diameter_mm(10.0)? - 2.588).abs() < 0.001);
+        assert!((awg_to_diameter_mm(20.0)? - 0.812).abs() < 0.001);
+        assert!((awg_to_diameter_mm(36.0)? - 0.127).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_diameter_snaps_to_nearest_gauge() -> Result<()> {
+        assert_eq!(diameter_mm_to_awg(8.251)?, 0.0);
+        assert_eq!(diameter_mm_to_awg(2.6)?, 10.0);
+        assert_eq!(diameter_mm_to_awg(0.8)?, 20.0);
+
+        assert!(WireConverter::new(2.6, WireGauge::Millimeter).snapped_to_gauge());
+        let exact = awg_to_diameter_mm(10.0)?;
+        assert!(!WireConverter::new(exact, WireGauge::Millimeter).snapped_to_gauge());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(awg_to_diameter_mm(10.5).is_err());
+        assert!(awg_to_diameter_mm(41.0).is_err());
+        assert!(awg_to_diameter_mm(f64::NAN).is_err());
+        assert!(diameter_mm_to_awg(0.0).is_err());
+        assert!(diameter_mm_to_awg(-1.0).is_err());
+        assert!(diameter_mm_to_awg(100.0).is_err());
+    }
+}
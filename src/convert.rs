@@ -0,0 +1,833 @@
+//! Conversions between units named as strings, with the same input checks
+//! and warnings as the command line
+
+use crate::conv::{
+    self, check_absolute_zero, validate_length_input, validate_numeric_input, Angle,
+    AngleConverter, Area, AreaConverter, Data, DataConverter, Degree, Energy, EnergyConverter,
+    Frequency, FrequencyConverter, FuelEconomy, FuelEconomyConverter, Length, LengthConverter,
+    Mass, MassConverter, Power, PowerConverter, Pressure, PressureConverter, Speed, SpeedConverter,
+    TemperatureConverter, TemperatureResults, Time, TimeConverter, Volume, VolumeConverter,
+};
+use crate::errors::Warnings;
+use crate::units::{parse_length_unit, parse_quantity, require_unit, RecognizedUnit};
+use anyhow::{anyhow, Context, Result};
+
+/// Pulls a temperature beyond absolute zero back onto it, returning a note
+/// describing the adjustment. Useful for noisy sensor data where slightly
+/// sub-zero Kelvin readings are measurement artifacts.
+pub fn clamp_to_absolute_zero(value: f64, unit: &Degree) -> (f64, Option<String>) {
+    let (bound, beyond) = match unit {
+        Degree::Kelvin => (0.0, value < 0.0),
+        Degree::Celsius => (-273.15, value < -273.15),
+        Degree::Fahrenheit => (-459.67, value < -459.67),
+        Degree::Newton => (-90.1395, value < -90.1395),
+        // Delisle is inverted, so absolute zero is the largest valid value
+        Degree::Delisle => (559.725, value > 559.725),
+        // Gas mark has its own oven range and no absolute-zero bound
+        Degree::GasMark => return (value, None),
+    };
+
+    if beyond {
+        let note = format!(
+            "{}{} is beyond absolute zero; clamped to {}{}.",
+            value, unit, bound, unit
+        );
+        (bound, Some(note))
+    } else {
+        (value, None)
+    }
+}
+
+pub fn convert_temperature(
+    value: f64,
+    from: Degree,
+    to: Degree,
+    clamp: bool,
+    warnings: &mut Warnings,
+) -> Result<f64> {
+    // Validate input
+    validate_numeric_input(value, "Temperature value")?;
+
+    let value = if clamp {
+        let (clamped, note) = clamp_to_absolute_zero(value, &from);
+        if let Some(note) = note {
+            warnings.push("clamped", note);
+        }
+        clamped
+    } else {
+        value
+    };
+
+    check_absolute_zero(value, &from)?;
+
+    let converter = TemperatureConverter::new(value, from);
+    let result = converter
+        .convert_to(to)
+        .context("Failed to perform temperature conversion")?;
+
+    // Validate result
+    validate_numeric_input(result, "Conversion result")?;
+
+    Ok(result)
+}
+
+/// `value` in every temperature unit but gas mark, after the same checks
+/// (and `--clamp`) as a single conversion
+pub fn convert_temperature_all(
+    value: f64,
+    from: Degree,
+    clamp: bool,
+    warnings: &mut Warnings,
+) -> Result<TemperatureResults> {
+    let celsius = convert_temperature(value, from, Degree::Celsius, clamp, warnings)?;
+    TemperatureConverter::new(celsius, Degree::Celsius).convert_to_all()
+}
+
+/// `value` in every length unit whose result is in range, after the same
+/// checks as a single conversion. Units out of range become warnings;
+/// it fails only when no unit is left.
+pub fn convert_length_each(
+    value: f64,
+    from: Length,
+    warnings: &mut Warnings,
+) -> Result<Vec<(Length, f64)>> {
+    // Validates the input the way every other length conversion does
+    convert_length(value, from, from)?;
+
+    let mut rows = Vec::new();
+    for (unit, result) in LengthConverter::new(value, from).convert_to_each() {
+        match result {
+            Ok(result) => rows.push((unit, result)),
+            Err(error) => warnings.push("skipped", format!("{:#}", error)),
+        }
+    }
+
+    if rows.is_empty() {
+        return Err(anyhow!("No length unit can hold the result"));
+    }
+    Ok(rows)
+}
+
+pub fn convert_length(value: f64, from: Length, to: Length) -> Result<f64> {
+    // Validate input
+    validate_length_input(value)?;
+
+    // The result is range-checked by the converter itself: astronomical units
+    // expressed in small units legitimately exceed the input limit here
+    let converter = LengthConverter::new(value, from);
+    let result = converter
+        .convert_to(to)
+        .context("Failed to perform length conversion")?;
+
+    Ok(result)
+}
+
+/// Expresses `value` (in `from`) as a percentage of a reference quantity such
+/// as `200cm`, converting to the reference's unit first. Returns the
+/// percentage with the parsed reference.
+pub fn length_percent_of(value: f64, from: Length, reference: &str) -> Result<(f64, f64, Length)> {
+    let (reference_value, reference_unit) = parse_quantity(reference)?;
+    let reference_unit = parse_length_unit(&reference_unit)
+        .with_context(|| format!("Reference '{}' is not a length", reference.trim()))?;
+
+    validate_numeric_input(reference_value, "Reference length")?;
+    if reference_value <= 0.0 {
+        return Err(anyhow!(
+            "Reference length must be greater than zero, got {}.",
+            reference_value
+        ));
+    }
+
+    let converted = convert_length(value, from, reference_unit)?;
+    Ok((
+        converted / reference_value * 100.0,
+        reference_value,
+        reference_unit,
+    ))
+}
+
+pub fn convert_mass(value: f64, from: Mass, to: Mass) -> Result<f64> {
+    // Validate input
+    validate_numeric_input(value, "Mass value")?;
+
+    if value < 0.0 {
+        return Err(anyhow!(
+            "Mass cannot be negative ({}). Please provide a positive value.",
+            value
+        ));
+    }
+
+    let converter = MassConverter::new(value, from);
+    let result = converter
+        .convert_to(to)
+        .context("Failed to perform mass conversion")?;
+
+    Ok(result)
+}
+
+pub fn convert_volume(value: f64, from: Volume, to: Volume) -> Result<f64> {
+    // Validate input
+    validate_numeric_input(value, "Volume value")?;
+
+    if value < 0.0 {
+        return Err(anyhow!(
+            "Volume cannot be negative ({}). Please provide a positive value.",
+            value
+        ));
+    }
+
+    let converter = VolumeConverter::new(value, from);
+    let result = converter
+        .convert_to(to)
+        .context("Failed to perform volume conversion")?;
+
+    Ok(result)
+}
+
+pub fn convert_area(value: f64, from: Area, to: Area) -> Result<f64> {
+    // Validate input
+    validate_numeric_input(value, "Area value")?;
+
+    if value < 0.0 {
+        return Err(anyhow!(
+            "Area cannot be negative ({}). Please provide a positive value.",
+            value
+        ));
+    }
+
+    let converter = AreaConverter::new(value, from);
+    let result = converter
+        .convert_to(to)
+        .context("Failed to perform area conversion")?;
+
+    Ok(result)
+}
+
+pub fn convert_speed(value: f64, from: Speed, to: Speed) -> Result<f64> {
+    // Validate input
+    validate_numeric_input(value, "Speed value")?;
+
+    if value < 0.0 {
+        return Err(anyhow!(
+            "Speed cannot be negative ({}). Please provide a positive value.",
+            value
+        ));
+    }
+
+    let converter = SpeedConverter::new(value, from);
+    let result = converter
+        .convert_to(to)
+        .context("Failed to perform speed conversion")?;
+
+    Ok(result)
+}
+
+pub fn convert_time(value: f64, from: Time, to: Time) -> Result<f64> {
+    // Validate input
+    validate_numeric_input(value, "Time value")?;
+
+    if value < 0.0 {
+        return Err(anyhow!(
+            "Time cannot be negative ({}). Please provide a positive value.",
+            value
+        ));
+    }
+
+    let converter = TimeConverter::new(value, from);
+    let result = converter
+        .convert_to(to)
+        .context("Failed to perform time conversion")?;
+
+    Ok(result)
+}
+
+pub fn convert_data(value: f64, from: Data, to: Data) -> Result<f64> {
+    // Validate input
+    validate_numeric_input(value, "Data value")?;
+
+    if value < 0.0 {
+        return Err(anyhow!(
+            "Data cannot be negative ({}). Please provide a positive value.",
+            value
+        ));
+    }
+
+    let converter = DataConverter::new(value, from);
+    let result = converter
+        .convert_to(to)
+        .context("Failed to perform data conversion")?;
+
+    Ok(result)
+}
+
+pub fn convert_pressure(value: f64, from: Pressure, to: Pressure) -> Result<f64> {
+    // Validate input
+    validate_numeric_input(value, "Pressure value")?;
+
+    if value < 0.0 {
+        return Err(anyhow!(
+            "Pressure cannot be negative ({}). Please provide a positive value.",
+            value
+        ));
+    }
+
+    let converter = PressureConverter::new(value, from);
+    let result = converter
+        .convert_to(to)
+        .context("Failed to perform pressure conversion")?;
+
+    Ok(result)
+}
+
+pub fn convert_energy(value: f64, from: Energy, to: Energy) -> Result<f64> {
+    // Validate input
+    validate_numeric_input(value, "Energy value")?;
+
+    if value < 0.0 {
+        return Err(anyhow!(
+            "Energy cannot be negative ({}). Please provide a positive value.",
+            value
+        ));
+    }
+
+    let converter = EnergyConverter::new(value, from);
+    let result = converter
+        .convert_to(to)
+        .context("Failed to perform energy conversion")?;
+
+    Ok(result)
+}
+
+pub fn convert_power(value: f64, from: Power, to: Power) -> Result<f64> {
+    // Validate input
+    validate_numeric_input(value, "Power value")?;
+
+    if value < 0.0 {
+        return Err(anyhow!(
+            "Power cannot be negative ({}). Please provide a positive value.",
+            value
+        ));
+    }
+
+    let converter = PowerConverter::new(value, from);
+    let result = converter
+        .convert_to(to)
+        .context("Failed to perform power conversion")?;
+
+    Ok(result)
+}
+
+pub fn convert_angle(value: f64, from: Angle, to: Angle) -> Result<f64> {
+    // Validate input
+    validate_numeric_input(value, "Angle value")?;
+
+    let converter = AngleConverter::new(value, from);
+    let result = converter
+        .convert_to(to)
+        .context("Failed to perform angle conversion")?;
+
+    Ok(result)
+}
+
+pub fn convert_frequency(value: f64, from: Frequency, to: Frequency) -> Result<f64> {
+    // Validate input
+    validate_numeric_input(value, "Frequency value")?;
+
+    if value < 0.0 {
+        return Err(anyhow!(
+            "Frequency cannot be negative ({}). Please provide a positive value.",
+            value
+        ));
+    }
+
+    let converter = FrequencyConverter::new(value, from);
+    let result = converter
+        .convert_to(to)
+        .context("Failed to perform frequency conversion")?;
+
+    Ok(result)
+}
+
+pub fn convert_fuel(value: f64, from: FuelEconomy, to: FuelEconomy) -> Result<f64> {
+    // Validate input
+    validate_numeric_input(value, "Fuel economy value")?;
+
+    // Zero would be an infinite consumption (or efficiency) on the other side
+    if value <= 0.0 {
+        return Err(anyhow!(
+            "Fuel economy must be positive ({}). Please provide a positive value.",
+            value
+        ));
+    }
+
+    let converter = FuelEconomyConverter::new(value, from);
+    let result = converter
+        .convert_to(to)
+        .context("Failed to perform fuel economy conversion")?;
+
+    Ok(result)
+}
+
+/// Months and years are averages (30.44 and 365.25 days), so a result
+/// involving one is an estimate; say so rather than print false precision
+pub fn note_approximate_time(units: &[&Time], warnings: &mut Warnings) {
+    if units.iter().any(|unit| conv::is_approximate(unit)) {
+        warnings.push(
+            "approximate",
+            "Months and years are averages (1 month = 30.44 days, 1 year = 365.25 days); calendar months and years vary, so this result is approximate.",
+        );
+    }
+}
+
+/// Bare `gallon`/`gal` is read as the US gallon. Say so once per conversion,
+/// since the imperial gallon is about 20% larger.
+pub fn note_assumed_gallon(units: &[&str], warnings: &mut Warnings) {
+    let ambiguous = units.iter().any(|unit| {
+        matches!(
+            unit.trim().to_lowercase().as_str(),
+            "gallon" | "gallons" | "gal" | "gals"
+        )
+    });
+
+    if ambiguous {
+        warnings.push(
+            "assumed",
+            "'gallon' was read as the US gallon (3.785411784 L); use 'impgal' for the imperial gallon (4.54609 L).",
+        );
+    }
+}
+
+/// Converts `value` from one recognized unit into another of the same category
+pub fn convert_recognized(
+    value: f64,
+    from: &RecognizedUnit,
+    to: &RecognizedUnit,
+    warnings: &mut Warnings,
+) -> Result<f64> {
+    convert_recognized_clamped(value, from, to, false, warnings)
+}
+
+/// `convert_recognized`, with `clamp` pulling a temperature beyond absolute
+/// zero back onto it as `convert_temperature` does
+pub fn convert_recognized_clamped(
+    value: f64,
+    from: &RecognizedUnit,
+    to: &RecognizedUnit,
+    clamp: bool,
+    warnings: &mut Warnings,
+) -> Result<f64> {
+    match (from, to) {
+        (RecognizedUnit::Temperature(from), RecognizedUnit::Temperature(to)) => {
+            convert_temperature(value, *from, *to, clamp, warnings)
+        }
+        (RecognizedUnit::Length(from), RecognizedUnit::Length(to)) => {
+            convert_length(value, *from, *to)
+        }
+        (RecognizedUnit::Mass(from), RecognizedUnit::Mass(to)) => convert_mass(value, *from, *to),
+        (RecognizedUnit::Volume(from), RecognizedUnit::Volume(to)) => {
+            convert_volume(value, *from, *to)
+        }
+        (RecognizedUnit::Area(from), RecognizedUnit::Area(to)) => convert_area(value, *from, *to),
+        (RecognizedUnit::Speed(from), RecognizedUnit::Speed(to)) => {
+            convert_speed(value, *from, *to)
+        }
+        (RecognizedUnit::Time(from), RecognizedUnit::Time(to)) => {
+            note_approximate_time(&[from, to], warnings);
+            convert_time(value, *from, *to)
+        }
+        (RecognizedUnit::Data(from), RecognizedUnit::Data(to)) => convert_data(value, *from, *to),
+        (RecognizedUnit::Pressure(from), RecognizedUnit::Pressure(to)) => {
+            convert_pressure(value, *from, *to)
+        }
+        (RecognizedUnit::Energy(from), RecognizedUnit::Energy(to)) => {
+            convert_energy(value, *from, *to)
+        }
+        (RecognizedUnit::Power(from), RecognizedUnit::Power(to)) => {
+            convert_power(value, *from, *to)
+        }
+        (RecognizedUnit::Angle(from), RecognizedUnit::Angle(to)) => {
+            convert_angle(value, *from, *to)
+        }
+        (RecognizedUnit::Frequency(from), RecognizedUnit::Frequency(to)) => {
+            convert_frequency(value, *from, *to)
+        }
+        (RecognizedUnit::Fuel(from), RecognizedUnit::Fuel(to)) => convert_fuel(value, *from, *to),
+        _ => Err(anyhow!(
+            "Cannot compare '{}' and '{}': they are from different categories.",
+            from.canonical_name(),
+            to.canonical_name()
+        )),
+    }
+}
+
+/// Two quantities expressed in a common unit (the unit of `b`)
+pub struct Comparison {
+    pub a_value: f64,
+    pub a_unit: RecognizedUnit,
+    pub a_converted: f64,
+    pub b_value: f64,
+    pub b_unit: RecognizedUnit,
+}
+
+/// Converts `value` between two units named in any accepted spelling, e.g.
+/// `convert("c", "f", 100.0)` is 212. The category is detected from the units.
+pub fn convert(from: &str, to: &str, value: f64) -> Result<f64> {
+    let from = require_unit(from)?;
+    let to = require_unit(to)?;
+    convert_recognized(value, &from, &to, &mut Warnings::new())
+}
+
+/// Expresses quantity `a` in the unit of quantity `b` so callers can compare
+/// like for like
+pub fn compare_quantities(a: &str, b: &str, warnings: &mut Warnings) -> Result<Comparison> {
+    let (a_value, a_unit) = parse_quantity(a)?;
+    let (b_value, b_unit) = parse_quantity(b)?;
+    note_assumed_gallon(&[&a_unit, &b_unit], warnings);
+    let a_unit = require_unit(&a_unit)?;
+    let b_unit = require_unit(&b_unit)?;
+
+    let a_converted = convert_recognized(a_value, &a_unit, &b_unit, warnings)?;
+    Ok(Comparison {
+        a_value,
+        a_unit,
+        a_converted,
+        b_value,
+        b_unit,
+    })
+}
+
+/// Checks a known equivalence (e.g. 0 °C = 32 °F) against the crate's
+/// formulas, returning whether it holds and a one-line report
+pub fn check_equivalence(
+    a: &str,
+    b: &str,
+    tolerance: f64,
+    warnings: &mut Warnings,
+) -> Result<(bool, String)> {
+    let cmp = compare_quantities(a, b, warnings)?;
+    let difference = cmp.a_converted - cmp.b_value;
+
+    if difference.abs() <= tolerance {
+        Ok((
+            true,
+            format!(
+                "Equivalent: {} {} = {} {}",
+                cmp.a_value, cmp.a_unit, cmp.b_value, cmp.b_unit
+            ),
+        ))
+    } else {
+        Ok((
+            false,
+            format!(
+                "Not equivalent: {} {} is {} {}, not {} {} (difference {} {})",
+                cmp.a_value,
+                cmp.a_unit,
+                cmp.a_converted,
+                cmp.b_unit,
+                cmp.b_value,
+                cmp.b_unit,
+                difference,
+                cmp.b_unit
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ValidationError;
+    use crate::units::{categorize_unit, normalize_unit, parse_data_unit, parse_temperature_unit};
+
+    /// Coarse performance guard, not a benchmark: runs a fixed batch of
+    /// conversions (including the typo-suggestion path) and fails only if it
+    /// blows far past a generous budget. An accidental O(n²) in unit lookup,
+    /// suggestion or conversion shows up here long before anyone notices it
+    /// interactively. The budget is deliberately lenient so slow CI machines
+    /// and debug builds don't make it flaky.
+    #[test]
+    fn test_conversion_batch_time_budget() -> Result<()> {
+        const CONVERSIONS: usize = 20_000;
+        const BUDGET: std::time::Duration = std::time::Duration::from_secs(10);
+
+        let start = std::time::Instant::now();
+        for i in 0..CONVERSIONS {
+            let value = (i % 500) as f64;
+            convert_temperature(
+                value,
+                parse_temperature_unit("c")?,
+                parse_temperature_unit("f")?,
+                false,
+                &mut Warnings::new(),
+            )?;
+            convert_length(value, parse_length_unit("km")?, parse_length_unit("mi")?)?;
+            assert!(normalize_unit("celcius").is_err());
+        }
+        let elapsed = start.elapsed();
+
+        assert!(
+            elapsed < BUDGET,
+            "{} conversions took {:?}, budget is {:?}",
+            CONVERSIONS,
+            elapsed,
+            BUDGET
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_clamp_below_absolute_zero() -> Result<()> {
+        let (value, note) = clamp_to_absolute_zero(-273.16, &Degree::Celsius);
+        assert_eq!(value, -273.15);
+        assert!(note.unwrap().contains("clamped to -273.15°C"));
+
+        let (value, note) = clamp_to_absolute_zero(-0.001, &Degree::Kelvin);
+        assert_eq!(value, 0.0);
+        assert!(note.is_some());
+
+        // In-range values pass through untouched
+        let (value, note) = clamp_to_absolute_zero(25.0, &Degree::Celsius);
+        assert_eq!(value, 25.0);
+        assert!(note.is_none());
+
+        // Without clamping the same reading is rejected, still as the typed
+        // error; with it, it converts and the clamp is reported on the
+        // warnings channel
+        let mut warnings = Warnings::new();
+        let error = convert_temperature(
+            -0.001,
+            Degree::Kelvin,
+            Degree::Celsius,
+            false,
+            &mut warnings,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<ValidationError>(),
+            Some(ValidationError::PhysicallyInvalid(_))
+        ));
+        let result =
+            convert_temperature(-0.001, Degree::Kelvin, Degree::Celsius, true, &mut warnings)?;
+        assert_eq!(result, -273.15);
+        let codes: Vec<_> = warnings.iter().map(|w| w.code).collect();
+        assert_eq!(codes, ["clamped"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_equivalence() -> Result<()> {
+        let mut warnings = Warnings::new();
+        let (equivalent, report) = check_equivalence("0c", "32f", 1e-6, &mut warnings)?;
+        assert!(equivalent, "{}", report);
+        assert!(check_equivalence("100c", "373.15k", 1e-6, &mut warnings)?.0);
+        assert!(check_equivalence("1in", "2.54cm", 1e-9, &mut warnings)?.0);
+
+        let (equivalent, report) = check_equivalence("0c", "33f", 1e-6, &mut warnings)?;
+        assert!(!equivalent);
+        assert!(report.contains("Not equivalent"));
+
+        // Mixed categories are an error, not a false equivalence
+        assert!(check_equivalence("0c", "0cm", 1e-6, &mut warnings).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_length_percent_of_reference() -> Result<()> {
+        let (percent, reference, unit) = length_percent_of(50.0, Length::Centimeter, "200cm")?;
+        assert_eq!(percent, 25.0);
+        assert_eq!(reference, 200.0);
+        assert!(matches!(unit, Length::Centimeter));
+
+        // The value is converted to the reference's unit first
+        assert_eq!(length_percent_of(1.0, Length::Meter, "200cm")?.0, 50.0);
+        assert_eq!(length_percent_of(6.0, Length::Inch, "1ft")?.0, 50.0);
+        assert!((length_percent_of(1.0, Length::Kilometer, "1mi")?.0 - 62.137).abs() < 0.001);
+
+        assert!(length_percent_of(1.0, Length::Meter, "0m").is_err());
+        assert!(length_percent_of(1.0, Length::Meter, "-2m").is_err());
+        assert!(length_percent_of(1.0, Length::Meter, "5kg").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_bare_gallon_is_us_with_a_note() -> Result<()> {
+        let mut warnings = Warnings::new();
+        assert!(check_equivalence("1impgal", "4.54609l", 1e-9, &mut warnings)?.0);
+        assert!(warnings.is_empty());
+
+        assert!(check_equivalence("1gal", "3.785411784l", 1e-9, &mut warnings)?.0);
+        assert!(!check_equivalence("1gal", "4.54609l", 1e-9, &mut warnings)?.0);
+        assert_eq!(warnings.iter().filter(|w| w.code == "assumed").count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_volume_units_are_recognized() -> Result<()> {
+        assert_eq!(normalize_unit("Litres")?, "liter");
+        assert_eq!(normalize_unit("pints")?, "pint");
+
+        let mut warnings = Warnings::new();
+        assert!(check_equivalence("1gal", "4qt", 1e-9, &mut warnings)?.0);
+        assert!(check_equivalence("1l", "1000ml", 1e-9, &mut warnings)?.0);
+        assert_eq!(categorize_unit("floz")?, "volume");
+        assert!(check_equivalence("1l", "1kg", 1e-9, &mut warnings).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_area_units_are_recognized() -> Result<()> {
+        assert_eq!(normalize_unit("hectares")?, "hectare");
+        assert_eq!(normalize_unit("sqft")?, "square-foot");
+
+        let mut warnings = Warnings::new();
+        assert!(check_equivalence("1ha", "10000sqm", 1e-9, &mut warnings)?.0);
+        assert!(check_equivalence("640ac", "1sqmi", 1e-9, &mut warnings)?.0);
+        assert_eq!(categorize_unit("km²")?, "area");
+        assert!(check_equivalence("1ha", "1l", 1e-9, &mut warnings).is_err());
+        assert!(normalize_unit("hectre").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_speed_units_are_recognized() -> Result<()> {
+        assert_eq!(normalize_unit("km/h")?, "kilometer-per-hour");
+        assert_eq!(normalize_unit("kph")?, normalize_unit("kmh")?);
+        assert_eq!(normalize_unit("knots")?, "knot");
+
+        let mut warnings = Warnings::new();
+        assert!(check_equivalence("1kn", "1.852km/h", 1e-9, &mut warnings)?.0);
+        assert!(check_equivalence("10m/s", "36kph", 1e-9, &mut warnings)?.0);
+        assert_eq!(categorize_unit("mph")?, "speed");
+        assert!(check_equivalence("1mph", "1mi", 1e-9, &mut warnings).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_time_units_are_recognized() -> Result<()> {
+        assert_eq!(normalize_unit("mins")?, "minute");
+        assert_eq!(normalize_unit("hrs")?, "hour");
+        assert_eq!(categorize_unit("min")?, "time");
+        assert_eq!(categorize_unit("ms")?, "time");
+
+        let mut warnings = Warnings::new();
+        assert!(check_equivalence("1d", "86400s", 1e-9, &mut warnings)?.0);
+        assert!(check_equivalence("1w", "7d", 1e-9, &mut warnings)?.0);
+        assert!(check_equivalence("1min", "1mi", 1e-9, &mut warnings).is_err());
+        assert!(warnings.is_empty());
+
+        // 90 days in months is an estimate, and the output must say so
+        assert_eq!(normalize_unit("months")?, "month");
+        let months = convert_recognized(
+            90.0,
+            &require_unit("days")?,
+            &require_unit("months")?,
+            &mut warnings,
+        )?;
+        assert!((months - 2.956_636).abs() < 1e-6);
+        assert!(warnings
+            .iter()
+            .any(|w| w.to_string().contains("approximate")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_data_units_are_recognized() -> Result<()> {
+        assert_eq!(normalize_unit("bytes")?, "byte");
+        assert_eq!(normalize_unit("MiB")?, "mebibyte");
+        assert_eq!(categorize_unit("kb")?, "data");
+
+        let mut warnings = Warnings::new();
+        assert!(check_equivalence("1mib", "1048576byte", 1e-9, &mut warnings)?.0);
+        assert!(check_equivalence("8bit", "1byte", 1e-9, &mut warnings)?.0);
+        assert!(!check_equivalence("1kb", "1kib", 1e-9, &mut warnings)?.0);
+        assert!(parse_data_unit("kbyte")
+            .unwrap_err()
+            .to_string()
+            .contains("use 'kib' for 1024 B"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_pressure_units_are_recognized() -> Result<()> {
+        assert_eq!(normalize_unit("kPa")?, "kilopascal");
+        assert_eq!(normalize_unit("mmHg")?, "mm-hg");
+        assert_eq!(categorize_unit("psi")?, "pressure");
+
+        let mut warnings = Warnings::new();
+        assert!(check_equivalence("1bar", "100kpa", 1e-9, &mut warnings)?.0);
+        assert!(check_equivalence("1atm", "14.696psi", 1e-3, &mut warnings)?.0);
+        assert!(check_equivalence("1atm", "1kg", 1e-9, &mut warnings).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_energy_units_are_recognized() -> Result<()> {
+        assert_eq!(normalize_unit("kWh")?, "kilowatt-hour");
+        assert_eq!(normalize_unit("calories")?, "calorie");
+        assert_eq!(normalize_unit("Cal")?, "kilocalorie");
+        assert_eq!(normalize_unit("cal")?, "calorie");
+        assert_eq!(categorize_unit("btu")?, "energy");
+
+        let mut warnings = Warnings::new();
+        assert!(check_equivalence("1kwh", "3600000j", 1e-9, &mut warnings)?.0);
+        assert!(check_equivalence("1kcal", "1000cal", 1e-9, &mut warnings)?.0);
+        assert!(check_equivalence("1kcal", "1kg", 1e-9, &mut warnings).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_power_units_are_recognized() -> Result<()> {
+        assert_eq!(normalize_unit("HP")?, "horsepower-mechanical");
+        assert_eq!(normalize_unit("ps")?, "horsepower-metric");
+        assert_eq!(normalize_unit("metric-hp")?, "horsepower-metric");
+        assert_eq!(normalize_unit("watts")?, "watt");
+        assert_eq!(categorize_unit("kw")?, "power");
+
+        let mut warnings = Warnings::new();
+        assert!(check_equivalence("1mw", "1000kw", 1e-9, &mut warnings)?.0);
+        assert!(!check_equivalence("100hp", "100ps", 1e-9, &mut warnings)?.0);
+        assert!(check_equivalence("1kw", "1kwh", 1e-9, &mut warnings).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_angle_units_are_recognized() -> Result<()> {
+        assert_eq!(normalize_unit("Degrees")?, "degree");
+        assert_eq!(normalize_unit("rad")?, "radian");
+        assert_eq!(normalize_unit("°")?, "degree");
+        assert_eq!(categorize_unit("arcsec")?, "angle");
+
+        let mut warnings = Warnings::new();
+        assert!(check_equivalence("1turn", "360deg", 1e-9, &mut warnings)?.0);
+        assert!(check_equivalence("180deg", "3.141592653589793rad", 1e-9, &mut warnings)?.0);
+        assert!(check_equivalence("90deg", "90c", 1e-9, &mut warnings).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_frequency_units_are_recognized() -> Result<()> {
+        assert_eq!(normalize_unit("GHz")?, "gigahertz");
+        assert_eq!(normalize_unit("RPM")?, "rpm");
+        assert_eq!(categorize_unit("khz")?, "frequency");
+
+        let mut warnings = Warnings::new();
+        assert!(check_equivalence("1ghz", "1000000000hz", 1e-9, &mut warnings)?.0);
+        assert!(check_equivalence("3000rpm", "50hz", 1e-9, &mut warnings)?.0);
+        assert!(check_equivalence("1hz", "1s", 1e-9, &mut warnings).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_fuel_units_are_recognized() -> Result<()> {
+        assert_eq!(normalize_unit("MPG")?, "mpg-us");
+        assert_eq!(normalize_unit("L/100km")?, "l100km");
+        assert_eq!(normalize_unit("kmpl")?, "km-per-liter");
+        assert_eq!(categorize_unit("mpg-uk")?, "fuel");
+
+        let mut warnings = Warnings::new();
+        assert!(check_equivalence("5l/100km", "20km/l", 1e-9, &mut warnings)?.0);
+        assert!(check_equivalence("1mpg", "1km/l", 1e-9, &mut warnings).is_ok());
+        assert!(check_equivalence("30mpg", "30km", 1e-9, &mut warnings).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,225 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::Path;
+
+/// One unit defined in a custom units file, as written. Every field is
+/// optional here so that a missing field is reported alongside every other
+/// problem instead of stopping the parse.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomUnit {
+    pub name: Option<String>,
+    /// How many `unit`s one of this unit is
+    pub factor: Option<f64>,
+    /// The built-in unit `factor` is expressed in, e.g. `m`
+    pub unit: Option<String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+impl CustomUnit {
+    /// The name and aliases, each of which selects the unit
+    fn spellings(&self) -> impl Iterator<Item = &str> {
+        self.name.iter().chain(&self.aliases).map(String::as_str)
+    }
+}
+
+/// Units defined by the user, one `[[unit]]` table each:
+///
+/// ```toml
+/// [[unit]]
+/// name = "smoot"
+/// factor = 1.7018
+/// unit = "m"
+/// aliases = ["smoots"]
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomUnitsFile {
+    #[serde(rename = "unit", default)]
+    pub units: Vec<CustomUnit>,
+}
+
+/// A problem with one unit of a custom units file
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitIssue {
+    /// `unit #2 'smoot'`, or just `unit #2` when the name is missing
+    pub unit: String,
+    pub message: String,
+}
+
+impl Display for UnitIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}: {}", self.unit, self.message)
+    }
+}
+
+impl CustomUnitsFile {
+    /// Reads and parses the file without checking the definitions
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read units file '{}'", path.display()))?;
+        Self::parse(&contents).with_context(|| format!("Invalid units file '{}'", path.display()))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    /// Every problem in the definitions, in file order: missing fields,
+    /// factors that are not positive finite numbers, base units that are not
+    /// built in, and spellings used twice or already taken by a built-in unit
+    pub fn lint(&self, is_builtin: impl Fn(&str) -> bool) -> Vec<UnitIssue> {
+        let mut issues = Vec::new();
+        if self.units.is_empty() {
+            issues.push(UnitIssue {
+                unit: "file".to_string(),
+                message: "defines no units".to_string(),
+            });
+        }
+
+        // Which unit first used each spelling, compared case-insensitively
+        // like the built-in names
+        let mut first_use: HashMap<String, usize> = HashMap::new();
+
+        for (index, unit) in self.units.iter().enumerate() {
+            let label = match &unit.name {
+                Some(name) => format!("unit #{} '{}'", index + 1, name),
+                None => format!("unit #{}", index + 1),
+            };
+            let mut report = |message: String| {
+                issues.push(UnitIssue {
+                    unit: label.clone(),
+                    message,
+                })
+            };
+
+            if unit
+                .name
+                .as_deref()
+                .is_none_or(|name| name.trim().is_empty())
+            {
+                report("missing required field 'name'".to_string());
+            }
+            match unit.factor {
+                None => report("missing required field 'factor'".to_string()),
+                Some(factor) if !factor.is_finite() || factor <= 0.0 => report(format!(
+                    "factor must be a positive finite number, got {}",
+                    factor
+                )),
+                Some(_) => {}
+            }
+            match unit.unit.as_deref() {
+                None => report("missing required field 'unit'".to_string()),
+                Some(base) if !is_builtin(base) => {
+                    report(format!("'{}' is not a built-in unit", base))
+                }
+                Some(_) => {}
+            }
+
+            for spelling in unit.spellings() {
+                let key = spelling.trim().to_lowercase();
+                if key.is_empty() {
+                    continue;
+                }
+                if is_builtin(spelling) {
+                    report(format!("'{}' is already a built-in unit", spelling));
+                }
+                match first_use.get(&key) {
+                    Some(&first) if first == index => {
+                        report(format!("'{}' is listed twice", spelling))
+                    }
+                    Some(&first) => report(format!(
+                        "'{}' is already used by unit #{}",
+                        spelling,
+                        first + 1
+                    )),
+                    None => {
+                        first_use.insert(key, index);
+                    }
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_builtin(unit: &str) -> bool {
+        ["m", "meter", "ft", "kg"].contains(&unit.to_lowercase().as_str())
+    }
+
+    #[test]
+    fn test_valid_file_has_no_issues() -> Result<()> {
+        let file = CustomUnitsFile::parse(
+            "[[unit]]\nname = \"smoot\"\nfactor = 1.7018\nunit = \"m\"\naliases = [\"smoots\"]\n\n\
+             [[unit]]\nname = \"stone-age\"\nfactor = 6.35\nunit = \"kg\"\n",
+        )?;
+        assert_eq!(file.units.len(), 2);
+        assert_eq!(file.lint(is_builtin), vec![]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_broken_file_reports_every_issue() -> Result<()> {
+        let file = CustomUnitsFile::parse(
+            r#"
+[[unit]]
+name = "smoot"
+factor = 1.7018
+unit = "m"
+aliases = ["sm", "SM"]
+
+[[unit]]
+name = "span"
+factor = -0.2286
+unit = "parsecs-ish"
+aliases = ["sm"]
+
+[[unit]]
+factor = nan
+unit = "m"
+
+[[unit]]
+name = "Meter"
+unit = "ft"
+"#,
+        )?;
+
+        let issues: Vec<String> = file
+            .lint(is_builtin)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                "unit #1 'smoot': 'SM' is listed twice",
+                "unit #2 'span': factor must be a positive finite number, got -0.2286",
+                "unit #2 'span': 'parsecs-ish' is not a built-in unit",
+                "unit #2 'span': 'sm' is already used by unit #1",
+                "unit #3: missing required field 'name'",
+                "unit #3: factor must be a positive finite number, got NaN",
+                "unit #4 'Meter': missing required field 'factor'",
+                "unit #4 'Meter': 'Meter' is already a built-in unit",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unreadable_files() {
+        assert!(CustomUnitsFile::parse("[[unit]]\nname = \"x\"\nsize = 2\n").is_err());
+        assert!(CustomUnitsFile::parse("not toml").is_err());
+        assert_eq!(
+            CustomUnitsFile::default().lint(is_builtin)[0].to_string(),
+            "file: defines no units"
+        );
+    }
+}
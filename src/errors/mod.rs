@@ -1,3 +1,5 @@
 mod error;
+mod warning;
 
 pub use error::*;
+pub use warning::*;
@@ -0,0 +1,92 @@
+use std::fmt;
+
+/// A non-fatal notice raised during a conversion (clamping, precision, ...).
+/// `code` is a stable machine-readable identifier; `message` is for humans.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Warning {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Warning ({}): {}", self.code, self.message)
+    }
+}
+
+/// Warnings accumulated over a single invocation. Text output writes them to
+/// stderr; JSON output serializes them as a `warnings` array.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
+pub struct Warnings(Vec<Warning>);
+
+impl Warnings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, code: &'static str, message: impl Into<String>) {
+        self.0.push(Warning {
+            code,
+            message: message.into(),
+        });
+    }
+
+    /// Moves every warning of `other` onto the end of these
+    pub fn append(&mut self, other: &mut Warnings) {
+        self.0.append(&mut other.0);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Warning> {
+        self.0.iter()
+    }
+
+    /// Text channel: one line per warning, meant for stderr
+    pub fn write_text(&self, writer: &mut impl std::io::Write) -> std::io::Result<()> {
+        for warning in &self.0 {
+            writeln!(writer, "{}", warning)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_channel() {
+        let mut warnings = Warnings::new();
+        warnings.push("clamped", "-1K is beyond absolute zero; clamped to 0K.");
+
+        let mut out = Vec::new();
+        warnings.write_text(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Warning (clamped): -1K is beyond absolute zero; clamped to 0K.\n"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_json_channel() {
+        let mut warnings = Warnings::new();
+        assert!(warnings.is_empty());
+        warnings.push("clamped", "clamped to 0K");
+        warnings.push("precision", "result rounded");
+
+        let json = serde_json::to_value(&warnings).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"code": "clamped", "message": "clamped to 0K"},
+                {"code": "precision", "message": "result rounded"}
+            ])
+        );
+    }
+}
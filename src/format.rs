@@ -0,0 +1,407 @@
+//! How results are printed: rounding, `--precision`, `--sig-figs`,
+//! `--scientific`, `--fraction`, digit grouping and the precision-loss check
+
+use crate::conv::UnitOrder;
+use crate::errors::ConversionError;
+use crate::output::{self, DecimalSeparator, OutputFormat, UnitLabel, UnitNames};
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::fmt::Display;
+
+/// How results are printed, shared by every conversion command
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutputOptions {
+    pub rounding: Rounding,
+    pub fail_on_precision_loss: bool,
+    pub unit_order: UnitOrder,
+    /// `--color-temp`, already gated on `--no-color` and a terminal
+    pub color_temperature: bool,
+    pub format: OutputFormat,
+    pub csv_header: bool,
+    /// `--precision`: decimals in printed results; when `None`, the
+    /// `category` default
+    pub precision: Option<u32>,
+    /// `--sig-figs`: significant figures in printed results, instead of
+    /// `precision`
+    pub sig_figs: Option<u32>,
+    /// `--scientific`: results as `<mantissa>e<exponent>`
+    pub scientific: bool,
+    /// `--fraction`: the denominator inch and foot results are rounded to
+    pub fraction: Option<u32>,
+    /// `--raw`: print the result value alone
+    pub raw: bool,
+    /// `--explain`: follow temperature and length results with their formula
+    pub explain: bool,
+    /// `--group-digits`: thousands separators in printed results
+    pub group_digits: bool,
+    /// `--decimal-separator`: the decimal mark in text results
+    pub decimal_separator: DecimalSeparator,
+    pub unit_names: UnitNames,
+    /// The category being converted, set with `in_category`; its
+    /// `default_decimals` apply when `precision` isn't given
+    pub category: Option<&'static str>,
+}
+
+impl OutputOptions {
+    /// These options for results in `category`, which pick up its
+    /// `default_decimals` unless `--precision` was given
+    pub fn in_category(self, category: &'static str) -> Self {
+        OutputOptions {
+            category: Some(category),
+            ..self
+        }
+    }
+
+    /// Decimals results are printed with: `--precision`, otherwise the
+    /// category's default
+    pub fn decimals(self) -> u32 {
+        self.precision
+            .unwrap_or_else(|| self.category.map_or(DEFAULT_DECIMALS, default_decimals))
+    }
+}
+
+/// Decimals results get without `--precision`, unless their category has
+/// its own default: six places, down to a micrometer from meters
+const DEFAULT_DECIMALS: u32 = 6;
+
+/// Decimals a category's results get without `--precision`: hundredths of a
+/// degree for temperatures, `DEFAULT_DECIMALS` for everything else
+pub fn default_decimals(category: &str) -> u32 {
+    match category {
+        "temperature" => 2,
+        _ => DEFAULT_DECIMALS,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rounding {
+    /// Round ties to the nearest even number (banker's rounding)
+    HalfEven,
+    /// Round ties away from zero, the "school" rounding most people expect
+    HalfUp,
+}
+
+pub fn format_number(value: f64, rounding: Rounding) -> String {
+    let rounded = match rounding {
+        Rounding::HalfEven => value.round_ties_even(),
+        Rounding::HalfUp => value.round(),
+    };
+    // Format the float directly: casting to i64 would saturate astronomical
+    // results (1 pc ≈ 3.1e25 nm). `+ 0.0` folds -0 into 0.
+    format!("{}", rounded + 0.0)
+}
+
+/// A result as printed: in scientific notation under `--scientific`,
+/// otherwise `--sig-figs` significant figures or `format_value`; grouped in thousands under
+/// `--group-digits`, with the `--decimal-separator` mark
+pub fn format_result(value: f64, output: OutputOptions) -> String {
+    let mut printed = format_plain_result(value, output);
+    if output.group_digits {
+        printed = group_digits(&printed);
+    }
+    output.decimal_separator.localize(&printed)
+}
+
+/// Separator `group_digits` puts between groups of three digits
+const DIGIT_GROUP_SEPARATOR: char = ',';
+
+/// Inserts thousands separators into the integer part of an already
+/// formatted number, leaving its sign, decimals and exponent alone:
+/// `-160934.4` → `-160,934.4`
+pub fn group_digits(number: &str) -> String {
+    let unsigned = number.trim_start_matches('-');
+    let sign = &number[..number.len() - unsigned.len()];
+    let digits_end = unsigned
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(unsigned.len());
+    let (integer, rest) = unsigned.split_at(digits_end);
+
+    let mut grouped = String::with_capacity(number.len() + integer.len() / 3);
+    grouped.push_str(sign);
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            grouped.push(DIGIT_GROUP_SEPARATOR);
+        }
+        grouped.push(digit);
+    }
+    grouped.push_str(rest);
+    grouped
+}
+
+pub fn format_plain_result(value: f64, output: OutputOptions) -> String {
+    if output.scientific {
+        let decimals = match (output.sig_figs, output.precision) {
+            (Some(figures), _) => Some(figures.saturating_sub(1)),
+            (None, decimals) => decimals,
+        };
+        return format_scientific(value, decimals);
+    }
+    if let Some(figures) = output.sig_figs {
+        return format_sig_figs(value, figures as usize);
+    }
+    format_value(value, output)
+}
+
+/// A number at the precision results are printed with: exactly
+/// `--precision` decimals, or up to the default `decimals` with trailing
+/// zeros dropped (`212.00` → `212`). Echoes the input in result sentences
+/// as well.
+pub fn format_value(value: f64, output: OutputOptions) -> String {
+    let printed = format_decimals(value, output.decimals(), output.rounding);
+    if output.precision.is_some() || !printed.contains('.') {
+        return printed;
+    }
+    printed
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+/// Mantissa decimals under `--scientific` without `--precision`
+const SCIENTIFIC_DECIMALS: usize = 5;
+
+/// `value` as `<mantissa>e<exponent>` via `{:e}`: `0.000621371` →
+/// `6.21371e-4`. With `decimals` the mantissa keeps exactly that many;
+/// without, it is rounded to `SCIENTIFIC_DECIMALS` and trailing zeros are
+/// dropped (`1000` → `1e3`). NaN and infinity are rejected by
+/// `validate_numeric_input` long before this, but print as themselves.
+pub fn format_scientific(value: f64, decimals: Option<u32>) -> String {
+    if !value.is_finite() {
+        return format!("{}", value);
+    }
+
+    match decimals {
+        Some(decimals) => format!("{:.*e}", decimals as usize, value + 0.0),
+        None => {
+            let scientific = format!("{:.*e}", SCIENTIFIC_DECIMALS, value + 0.0);
+            let (mantissa, exponent) = scientific
+                .split_once('e')
+                .expect("`{:e}` output always has an exponent");
+            let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+            format!("{}e{}", mantissa, exponent)
+        }
+    }
+}
+
+/// Most significant figures `--sig-figs` accepts; an `f64` carries 17
+pub const MAX_SIG_FIGS: u32 = 17;
+
+/// `value` rounded to `figures` significant figures, written out in plain
+/// decimal: `0.000621371` → `0.000621`, `1234.5` at 2 → `1200`. Rounding
+/// goes through `{:e}`, so a carry into the next power of ten (`9.99` at 2
+/// → `10`) keeps the right number of figures.
+pub fn format_sig_figs(value: f64, figures: usize) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return format!("{}", value + 0.0);
+    }
+
+    let figures = figures.max(1);
+    let scientific = format!("{:.*e}", figures - 1, value);
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("`{:e}` output always has an exponent");
+    let exponent: i32 = exponent.parse().expect("`{:e}` exponents are integers");
+    let rounded: f64 = format!("{}e{}", mantissa, exponent)
+        .parse()
+        .expect("`{:e}` output parses back");
+
+    let decimals = figures as i32 - 1 - exponent;
+    if decimals > 0 {
+        format!("{:.*}", decimals as usize, rounded)
+    } else {
+        format!("{}", rounded)
+    }
+}
+
+/// Like `format_number`, but keeps `decimals` digits after the point
+pub fn format_decimals(value: f64, decimals: u32, rounding: Rounding) -> String {
+    let scale = 10f64.powi(decimals as i32);
+    let scaled = value * scale;
+    if !scaled.is_finite() {
+        return format!("{:.*}", decimals as usize, value);
+    }
+
+    let rounded = match rounding {
+        Rounding::HalfEven => scaled.round_ties_even(),
+        Rounding::HalfUp => scaled.round(),
+    } / scale;
+    format!("{:.*}", decimals as usize, rounded + 0.0)
+}
+
+/// Relative difference (absolute below 1) allowed between the input and the
+/// printed result converted back, under `--fail-on-precision-loss`
+const PRECISION_LOSS_TOLERANCE: f64 = 1e-9;
+
+/// Errors with `ConversionError::PrecisionLoss` when the printed result,
+/// parsed and converted back to the source unit, no longer matches `value`
+pub fn ensure_round_trips(
+    value: f64,
+    printed: &str,
+    convert_back: impl FnOnce(f64) -> Result<f64>,
+) -> Result<()> {
+    let printed_value = parse_printed(printed)
+        .ok_or_else(|| anyhow!("cannot read the printed result '{}' back", printed))?;
+    let round_trip = convert_back(printed_value)?;
+    let difference = (round_trip - value).abs();
+
+    if difference > PRECISION_LOSS_TOLERANCE * value.abs().max(1.0) {
+        return Err(ConversionError::PrecisionLoss(format!(
+            "the printed result {} converts back to {} instead of {}",
+            printed, round_trip, value
+        ))
+        .into());
+    }
+
+    Ok(())
+}
+
+/// The unit as `--unit-names` asks for it: the symbol, or the full name
+/// pluralized for the amount printed next to it
+pub fn unit_label(unit: &dyn UnitLabel, amount: f64, output: OutputOptions) -> String {
+    match output.unit_names {
+        UnitNames::Short => unit.to_string(),
+        UnitNames::Long => output::pluralize(&unit.long_name(), amount),
+    }
+}
+
+/// The number a rounded result prints as, so `0.6 in` rounded to `1` reads
+/// `1 inch`
+pub fn printed_value(printed: &str, result: f64, output: OutputOptions) -> f64 {
+    parse_printed(&output.decimal_separator.delocalize(printed)).unwrap_or(result)
+}
+
+/// Reads a delocalized printed result back: a plain number, or a
+/// `--fraction` mixed number like `3 1/2` or `-1/16`
+pub fn parse_printed(printed: &str) -> Option<f64> {
+    if let Ok(number) = printed.parse() {
+        return Some(number);
+    }
+
+    let (sign, unsigned) = match printed.strip_prefix('-') {
+        Some(rest) => (-1.0, rest),
+        None => (1.0, printed),
+    };
+    let (whole, fraction) = match unsigned.split_once(' ') {
+        Some((whole, fraction)) => (whole.parse::<f64>().ok()?, fraction),
+        None => (0.0, unsigned),
+    };
+    let (numerator, denominator) = fraction.split_once('/')?;
+    let fraction = numerator.parse::<f64>().ok()? / denominator.parse::<f64>().ok()?;
+    Some(sign * (whole + fraction))
+}
+
+/// Whether `--fraction` applies to results in this unit
+pub fn takes_fraction(unit: &dyn UnitLabel) -> bool {
+    matches!(unit.long_name().as_str(), "inch" | "foot")
+}
+
+/// `value` rounded to the nearest `1/max_denom` and written as a reduced
+/// mixed number: `3.5` → `3 1/2`, `0.0625` → `1/16`, `0.99` → `1`
+pub fn to_fraction(value: f64, max_denom: u32) -> String {
+    let denominator = u64::from(max_denom.max(1));
+    let steps = (value.abs() * denominator as f64).round();
+    if !steps.is_finite() || steps >= u64::MAX as f64 {
+        return format!("{}", value);
+    }
+
+    let steps = steps as u64;
+    let sign = if value < 0.0 && steps > 0 { "-" } else { "" };
+    let whole = steps / denominator;
+    let remainder = steps % denominator;
+    if remainder == 0 {
+        return format!("{}{}", sign, whole);
+    }
+
+    let divisor = gcd(remainder, denominator);
+    let fraction = format!("{}/{}", remainder / divisor, denominator / divisor);
+    if whole == 0 {
+        format!("{}{}", sign, fraction)
+    } else {
+        format!("{}{} {}", sign, whole, fraction)
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Whether `--explain` formulas are printed: only under the result sentence
+/// of text output, never with `--raw`
+pub fn explains(output: OutputOptions) -> bool {
+    output.explain && output.format == OutputFormat::Text && !output.raw
+}
+
+/// The result as it will be printed, after the `--fail-on-precision-loss`
+/// round-trip check
+pub fn checked_result(
+    value: f64,
+    from: &dyn Display,
+    result: f64,
+    to: &dyn UnitLabel,
+    output: OutputOptions,
+    convert_back: impl FnOnce(f64) -> Result<f64>,
+) -> Result<String> {
+    let printed = match output.fraction {
+        Some(denominator) if takes_fraction(to) => to_fraction(result, denominator),
+        _ => format_result(result, output),
+    };
+
+    if output.fail_on_precision_loss {
+        let plain = output.decimal_separator.delocalize(&printed);
+        ensure_round_trips(value, &plain, convert_back).with_context(|| {
+            format!(
+                "{} {} = {} {} loses precision",
+                format_value(value, output),
+                from,
+                printed,
+                to
+            )
+        })?;
+    }
+
+    Ok(printed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_decimals() {
+        assert_eq!(format_decimals(98.6, 1, Rounding::HalfEven), "98.6");
+        assert_eq!(format_decimals(2.0, 2, Rounding::HalfEven), "2.00");
+        assert_eq!(format_decimals(0.125, 2, Rounding::HalfEven), "0.12");
+        assert_eq!(format_decimals(0.125, 2, Rounding::HalfUp), "0.13");
+    }
+
+    #[test]
+    fn test_rounding_modes_on_ties() {
+        // Ties are where the two modes disagree
+        assert_eq!(format_number(0.5, Rounding::HalfEven), "0");
+        assert_eq!(format_number(0.5, Rounding::HalfUp), "1");
+        assert_eq!(format_number(2.5, Rounding::HalfEven), "2");
+        assert_eq!(format_number(2.5, Rounding::HalfUp), "3");
+        assert_eq!(format_number(-2.5, Rounding::HalfEven), "-2");
+        assert_eq!(format_number(-2.5, Rounding::HalfUp), "-3");
+
+        // ... and agree everywhere else
+        for mode in [Rounding::HalfEven, Rounding::HalfUp] {
+            assert_eq!(format_number(1.5, mode), "2");
+            assert_eq!(format_number(1.4, mode), "1");
+            assert_eq!(format_number(1.6, mode), "2");
+            assert_eq!(format_number(-0.4, mode), "0");
+        }
+
+        // Results beyond i64 range are printed in full rather than saturated
+        assert_eq!(
+            format_number(3.0e25, Rounding::HalfEven),
+            "30000000000000000000000000"
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use crate::convert::{convert_recognized, note_assumed_gallon};
+use crate::errors::Warnings;
+use crate::format::{format_decimals, format_number, Rounding};
+use crate::units::require_unit;
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+use std::path::Path;
+
+/// Most decimals a job may ask for; beyond this `f64` output is noise
+pub const MAX_PRECISION: u32 = 15;
+
+/// One named conversion in a job file
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Job {
+    pub name: String,
+    pub from: String,
+    pub to: String,
+    pub value: f64,
+    /// Decimals to print; whole numbers when omitted
+    #[serde(default)]
+    pub precision: Option<u32>,
+}
+
+/// A list of conversions run together by `uniconv run`. In TOML each job is
+/// a `[[job]]` table; in JSON the jobs are a `"job"` array.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JobFile {
+    #[serde(rename = "job")]
+    pub jobs: Vec<Job>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JobFormat {
+    Toml,
+    Json,
+}
+
+impl JobFormat {
+    /// Picks the format from the file extension
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("toml") => Ok(JobFormat::Toml),
+            Some("json") => Ok(JobFormat::Json),
+            _ => Err(anyhow!(
+                "Cannot tell the format of '{}'. Job files must end in .toml or .json.",
+                path.display()
+            )),
+        }
+    }
+}
+
+impl JobFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let format = JobFormat::from_path(path)?;
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read job file '{}'", path.display()))?;
+        Self::parse(&contents, format)
+            .with_context(|| format!("Invalid job file '{}'", path.display()))
+    }
+
+    pub fn parse(contents: &str, format: JobFormat) -> Result<Self> {
+        let file: JobFile = match format {
+            JobFormat::Toml => toml::from_str(contents)?,
+            JobFormat::Json => serde_json::from_str(contents)?,
+        };
+        file.validate()?;
+        Ok(file)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.jobs.is_empty() {
+            return Err(anyhow!("The job file lists no jobs."));
+        }
+
+        let mut names = HashSet::new();
+        for (index, job) in self.jobs.iter().enumerate() {
+            let name = job.name.trim();
+            if name.is_empty() {
+                return Err(anyhow!("Job #{} has an empty name.", index + 1));
+            }
+            if !names.insert(name) {
+                return Err(anyhow!("Job name '{}' is used more than once.", name));
+            }
+            if let Some(precision) = job.precision {
+                if precision > MAX_PRECISION {
+                    return Err(anyhow!(
+                        "Job '{}' asks for {} decimals; at most {} are supported.",
+                        name,
+                        precision,
+                        MAX_PRECISION
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs one job and describes its result, e.g. `37 c = 98.6 °F`
+pub fn run_job(job: &Job, rounding: Rounding, warnings: &mut Warnings) -> Result<String> {
+    note_assumed_gallon(&[&job.from, &job.to], warnings);
+    let from = require_unit(&job.from)?;
+    let to = require_unit(&job.to)?;
+    let result = convert_recognized(job.value, &from, &to, warnings)?;
+
+    let printed = match job.precision {
+        Some(decimals) => format_decimals(result, decimals, rounding),
+        None => format_number(result, rounding),
+    };
+    Ok(format!("{} {} = {} {}", job.value, from, printed, to))
+}
+
+/// What `run_jobs` did: one labeled line per job that ran, how many of
+/// them failed, and whether `--max-errors` stopped the run early
+#[derive(Debug)]
+pub struct JobsReport {
+    pub lines: Vec<String>,
+    pub failures: usize,
+    pub aborted: bool,
+}
+
+/// Runs every job in order, one labeled line each. A failing job is reported
+/// on its own line without stopping the rest, unless `max_errors` failures
+/// have been seen, which ends the run.
+pub fn run_jobs(
+    file: &JobFile,
+    rounding: Rounding,
+    max_errors: Option<NonZeroUsize>,
+    warnings: &mut Warnings,
+) -> JobsReport {
+    let mut report = JobsReport {
+        lines: Vec::new(),
+        failures: 0,
+        aborted: false,
+    };
+
+    for job in &file.jobs {
+        let name = job.name.trim();
+        match run_job(job, rounding, warnings) {
+            Ok(line) => report.lines.push(format!("{}: {}", name, line)),
+            Err(e) => {
+                report.failures += 1;
+                // The first line names the problem; unit lists would drown the report
+                let message = e.to_string();
+                let reason = message.lines().next().unwrap_or_default();
+                report.lines.push(format!("{}: error: {}", name, reason));
+
+                if max_errors.is_some_and(|limit| report.failures >= limit.get()) {
+                    report.aborted = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_two_job_file() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("uniconv-jobs-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "[[job]]\nname = \"Body temperature\"\nfrom = \"c\"\nto = \"f\"\nvalue = 37\nprecision = 1\n\n\
+             [[job]]\nname = \"Marathon\"\nfrom = \"km\"\nto = \"mi\"\nvalue = 42.195\nprecision = 2\n",
+        )?;
+        let job_file = JobFile::load(&path);
+        std::fs::remove_file(&path)?;
+
+        let report = run_jobs(&job_file?, Rounding::HalfEven, None, &mut Warnings::new());
+        assert_eq!(report.failures, 0);
+        assert_eq!(
+            report.lines,
+            [
+                "Body temperature: 37 °C = 98.6 °F",
+                "Marathon: 42.195 km = 26.22 mi"
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_failing_job_does_not_stop_the_rest() -> Result<()> {
+        let job_file = JobFile::parse(
+            r#"{"job": [{"name": "typo", "from": "celcius", "to": "f", "value": 1},
+                        {"name": "ok", "from": "kg", "to": "g", "value": 1}]}"#,
+            JobFormat::Json,
+        )?;
+        let report = run_jobs(&job_file, Rounding::HalfEven, None, &mut Warnings::new());
+        assert_eq!(report.failures, 1);
+        assert!(!report.aborted);
+        assert_eq!(report.lines[0], "typo: error: Unrecognized unit: 'celcius'");
+        assert_eq!(report.lines[1], "ok: 1 kg = 1000 g");
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_errors_aborts_the_run() -> Result<()> {
+        let job_file = JobFile::parse(
+            r#"{"job": [{"name": "a", "from": "c", "to": "kg", "value": 1},
+                        {"name": "b", "from": "kg", "to": "g", "value": 1},
+                        {"name": "c", "from": "furlongs", "to": "psi", "value": 1},
+                        {"name": "d", "from": "bogus", "to": "g", "value": 1},
+                        {"name": "e", "from": "m", "to": "cm", "value": 1}]}"#,
+            JobFormat::Json,
+        )?;
+
+        // Three bad jobs with a limit of two: the run stops at the second one
+        let report = run_jobs(
+            &job_file,
+            Rounding::HalfEven,
+            NonZeroUsize::new(2),
+            &mut Warnings::new(),
+        );
+        assert!(report.aborted);
+        assert_eq!(report.failures, 2);
+        assert_eq!(report.lines.len(), 3);
+        assert!(report.lines[2].starts_with("c: error:"));
+
+        // The same file without a limit runs to the end
+        let report = run_jobs(&job_file, Rounding::HalfEven, None, &mut Warnings::new());
+        assert!(!report.aborted);
+        assert_eq!(report.failures, 3);
+        assert_eq!(report.lines.len(), 5);
+        Ok(())
+    }
+
+    const TWO_JOBS_TOML: &str = r#"
+[[job]]
+name = "Body temperature"
+from = "c"
+to = "f"
+value = 37
+precision = 1
+
+[[job]]
+name = "Marathon"
+from = "km"
+to = "mi"
+value = 42.195
+"#;
+
+    #[test]
+    fn test_parse_toml_and_json() -> Result<()> {
+        let toml = JobFile::parse(TWO_JOBS_TOML, JobFormat::Toml)?;
+        assert_eq!(toml.jobs.len(), 2);
+        assert_eq!(toml.jobs[0].precision, Some(1));
+        assert_eq!(toml.jobs[1].precision, None);
+
+        let json = JobFile::parse(
+            r#"{"job": [{"name": "Body temperature", "from": "c", "to": "f", "value": 37, "precision": 1},
+                        {"name": "Marathon", "from": "km", "to": "mi", "value": 42.195}]}"#,
+            JobFormat::Json,
+        )?;
+        assert_eq!(json.jobs, toml.jobs);
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(
+            JobFormat::from_path(Path::new("jobs.toml")).unwrap(),
+            JobFormat::Toml
+        );
+        assert_eq!(
+            JobFormat::from_path(Path::new("JOBS.JSON")).unwrap(),
+            JobFormat::Json
+        );
+        assert!(JobFormat::from_path(Path::new("jobs.yaml")).is_err());
+    }
+
+    #[test]
+    fn test_invalid_job_files() {
+        let parse = |contents: &str| JobFile::parse(contents, JobFormat::Toml);
+
+        assert!(parse("").is_err());
+        assert!(parse("[[job]]\nname = \"a\"\nfrom = \"c\"\nto = \"f\"\n").is_err());
+        assert!(parse(
+            "[[job]]\nname = \"a\"\nfrom = \"c\"\nto = \"f\"\nvalue = 1\nunit = \"x\"\n"
+        )
+        .is_err());
+
+        let duplicate = "[[job]]\nname = \"a\"\nfrom = \"c\"\nto = \"f\"\nvalue = 1\n".repeat(2);
+        assert!(parse(&duplicate)
+            .unwrap_err()
+            .to_string()
+            .contains("used more than once"));
+
+        let too_precise =
+            "[[job]]\nname = \"a\"\nfrom = \"c\"\nto = \"f\"\nvalue = 1\nprecision = 40\n";
+        assert!(parse(too_precise).is_err());
+    }
+}
@@ -0,0 +1,49 @@
+//! The unit conversions behind the `uniconv` command line tool, for use from
+//! other Rust code. Each category has a unit enum and a converter:
+//!
+//! ```
+//! use uniconv::{Degree, Length, LengthConverter, TemperatureConverter};
+//!
+//! let boiling = TemperatureConverter::new(100.0, Degree::Celsius).convert_to(Degree::Fahrenheit)?;
+//! assert_eq!(boiling, 212.0);
+//!
+//! let inches = LengthConverter::new(2.54, Length::Centimeter).convert_to(Length::Inch)?;
+//! assert!((inches - 1.0).abs() < 1e-12);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! Everything in [`conv`] and [`errors`] is also re-exported here.
+//!
+//! Unit names as typed on the command line are handled by [`units`], and
+//! [`convert`] converts between them with the same checks as the CLI.
+//!
+//! The `serde` feature, on by default, adds serde support to the unit enums
+//! and results and the modules the command line reads and writes with:
+//! `format`, `output`, `report`, `batch`, `jobs` and `custom`. Without it
+//! the crate doesn't depend on serde at all.
+
+#[cfg(feature = "serde")]
+pub mod batch;
+pub mod conv;
+pub mod convert;
+#[cfg(feature = "serde")]
+pub mod custom;
+pub mod errors;
+#[cfg(feature = "serde")]
+pub mod format;
+#[cfg(feature = "serde")]
+pub mod jobs;
+#[cfg(feature = "serde")]
+pub mod output;
+#[cfg(feature = "serde")]
+pub mod report;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod testing;
+pub mod units;
+pub mod util;
+
+pub use conv::*;
+pub use convert::convert;
+pub use errors::*;
+#[cfg(any(test, feature = "test-utils"))]
+pub use testing::assert_converts;
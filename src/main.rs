@@ -1,12 +1,49 @@
 use anyhow::{anyhow, Context, Result};
-use clap::{Parser, Subcommand};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 
-mod conv;
-mod errors;
+mod color;
+mod config;
 
-use conv::{Degree, Length, LengthConverter, TemperatureConverter};
+use color::ColorChoice;
+use config::Config;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::io::{BufRead, IsTerminal, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use uniconv::batch::{run_batch, run_json_batch, starts_json_array, watch_stdin, UnitColumn};
+use uniconv::conv::{
+    self, validate_numeric_input, Angle, Area, AspectConverter, AspectTarget, Coordinate, Data,
+    Degree, Energy, Frequency, FuelEconomy, GeoDistance, IdealGas, Length, LengthConverter, Mass,
+    MediaValue, Pace, PaceConverter, Power, Pressure, ShoeGender, ShoeSizeConverter, ShoeSystem,
+    Speed, TemperatureConverter, Time, UnitOrder, Volume, WireConverter, WireGauge,
+};
+use uniconv::convert::{
+    check_equivalence, clamp_to_absolute_zero, convert_length_each, convert_recognized,
+    convert_recognized_clamped, convert_temperature_all, length_percent_of, note_assumed_gallon,
+};
+use uniconv::custom::CustomUnitsFile;
+use uniconv::errors::Warnings;
+use uniconv::format::{
+    checked_result, explains, format_number, format_result, format_value, printed_value,
+    unit_label, OutputOptions, Rounding, MAX_SIG_FIGS,
+};
+use uniconv::jobs::{self, run_jobs, JobFile};
+use uniconv::output::{
+    self, list_units, render_unit_list, table_rows, write_lines, DecimalSeparator, OutputFormat,
+    UnitLabel, UnitNames,
+};
+use uniconv::report::{self, ErrorOutput, UnitCategory};
+use uniconv::units::{
+    categorize_unit, normalize_unit, parse_length_unit, parse_positional, parse_pressure_unit,
+    parse_temperature_unit, parse_volume_unit, recognize_unit, require_unit, suggest_unit,
+    RecognizedUnit, SUPPORTED_UNITS_HELP, UNIT_CATEGORIES,
+};
 
-#[derive(Parser)]
+#[derive(Parser, Clone)]
 #[command(name = "uniconv")]
 #[command(about = "A universal unit converter (based on temperature and length")]
 #[command(version = "1.0")]
@@ -21,411 +58,2530 @@ Examples:
   Dedicated commands:
     uniconv temperature --from celsius --to fahrenheit --value 25
     uniconv length --from cm --to inch --value 188
+    uniconv geodistance --from "40.7128,-74.0060" --to "34.0522,-118.2437" --unit km
+    uniconv mass --from kilogram --to pound --value 70
+    uniconv volume --from gallon-us --to liter --value 5
+    uniconv area --from acre --to square-meter --value 2
+    uniconv speed --from kilometer-per-hour --to mile-per-hour --value 100
+    uniconv time --from week --to hour --value 2
+    uniconv data --from gibibyte --to megabyte --value 4
+    uniconv pressure --from bar --to psi --value 2.2
+    uniconv energy --from kilowatt-hour --to joule --value 1
+    uniconv power --from horsepower-mechanical --to kilowatt --value 150
+    uniconv angle --from degree --to radian --value 180
+    uniconv frequency --from rpm --to hertz --value 3000
+    uniconv fuel --from mpg-us --to l100km --value 30
 "#)]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = Rounding::HalfEven,
+        env = "UNICONV_ROUNDING",
+        help = "How to round x.5 results: half-even (banker's, 2.5 → 2) or half-up (2.5 → 3)"
+    )]
+    round: Rounding,
+    #[arg(
+        long,
+        global = true,
+        help = "Fail instead of printing a rounded result that doesn't convert back to the input"
+    )]
+    fail_on_precision_loss: bool,
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = UnitOrder::Declaration,
+        help = "Order of the units in convert-to-all listings: declaration or symbol (alphabetical)"
+    )]
+    deterministic_order: UnitOrder,
+    #[arg(
+        long,
+        global = true,
+        help = "Color temperature results by how hot or cold they are (only on a terminal)"
+    )]
+    color_temp: bool,
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_name = "WHEN",
+        default_value_t = ColorChoice::Auto,
+        help = "When to color output: auto (terminals, unless NO_COLOR is set), always or never"
+    )]
+    color: ColorChoice,
+    #[arg(
+        long,
+        global = true,
+        help = "Never print colored output (same as --color never)"
+    )]
+    no_color: bool,
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = OutputFormat::Text,
+        env = "UNICONV_FORMAT",
+        help = "Print results as text lines, JSON objects (errors go to stderr as JSON too), CSV records, or a table of every unit in the category"
+    )]
+    format: OutputFormat,
+    #[arg(
+        long,
+        global = true,
+        help = "With --format csv, print a from_value,from_unit,to_value,to_unit header first"
+    )]
+    csv_header: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Byte-stable output for golden files: fixed rounding, order and number style, no color"
+    )]
+    canonical: bool,
+    #[arg(
+        long,
+        global = true,
+        value_name = "CATEGORY,...",
+        value_delimiter = ',',
+        value_parser = clap::builder::PossibleValuesParser::new(
+            UNIT_CATEGORIES.iter().map(|(category, _)| *category)
+        ),
+        help = "Restrict list-units and --format table to these categories, e.g. temperature,length"
+    )]
+    only: Vec<String>,
+    #[arg(
+        long,
+        global = true,
+        value_parser = clap::value_parser!(u32).range(0..=jobs::MAX_PRECISION as i64),
+        help = "Decimal places in printed results (0-15); by default 2 for temperatures and 6 otherwise, trailing zeros trimmed [env: UNICONV_PRECISION]"
+    )]
+    precision: Option<u32>,
+    #[arg(
+        long,
+        global = true,
+        conflicts_with = "precision",
+        value_parser = clap::value_parser!(u32).range(1..=MAX_SIG_FIGS as i64),
+        help = "Significant figures in printed results (1-17), instead of --precision"
+    )]
+    sig_figs: Option<u32>,
+    #[arg(
+        long,
+        global = true,
+        help = "Print results in scientific notation (6.21371e-4); --precision sets the mantissa decimals"
+    )]
+    scientific: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Print inch and foot results as the nearest fraction, like 74 1/16 in"
+    )]
+    fraction: bool,
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        default_value_t = 16,
+        value_parser = clap::value_parser!(u32).range(1..=1024),
+        help = "Largest denominator --fraction rounds to"
+    )]
+    fraction_denominator: u32,
+    #[arg(
+        long,
+        global = true,
+        alias = "quiet",
+        help = "Print only the result value, without units or text (alias --quiet)"
+    )]
+    raw: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Also print the formula behind temperature and length results"
+    )]
+    explain: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Group the digits of results in thousands, like 160,934.4"
+    )]
+    group_digits: bool,
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = DecimalSeparator::Dot,
+        help = "Decimal mark in results: dot (3.107) or comma (3,107, grouped with dots)"
+    )]
+    decimal_separator: DecimalSeparator,
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = UnitNames::Short,
+        help = "Write units as symbols (short: 2 cm) or pluralized names (long: 2 centimeters)"
+    )]
+    unit_names: UnitNames,
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Write results to this file (created or truncated) instead of stdout"
+    )]
+    output: Option<PathBuf>,
+    #[arg(
+        long,
+        global = true,
+        help = "With --output, add to the end of the file instead of truncating it"
+    )]
+    append: bool,
+    #[arg(
+        long,
+        global = true,
+        value_name = "START..END:STEP",
+        allow_hyphen_values = true,
+        value_parser = parse_value_range,
+        help = "Convert every value from START to END in steps of STEP, instead of --value"
+    )]
+    range: Option<ValueRange>,
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Config file of defaults (default: ~/.config/uniconv/config.toml, or $UNICONV_CONFIG)"
+    )]
+    config: Option<PathBuf>,
 }
 
-#[derive(Subcommand)]
+/// Options from the global flags. `--canonical` overrides every setting
+/// that could make the same conversion print differently: rounding is
+/// half-even to whole numbers (`--precision`, `--sig-figs`,
+/// `--scientific` and `--fraction` are ignored), listings follow
+/// declaration order, color is off whether or not stdout is a terminal,
+/// and numbers use the C locale style (`.` decimal point, no grouping, so
+/// `--group-digits` and `--decimal-separator` are ignored). The output
+/// format itself (text, JSON, CSV), `--raw`, `--explain` and
+/// `--unit-names` are kept, since each is already stable.
+fn output_options(cli: &Cli, stdout_is_terminal: bool) -> OutputOptions {
+    if cli.canonical {
+        return OutputOptions {
+            rounding: Rounding::HalfEven,
+            fail_on_precision_loss: cli.fail_on_precision_loss,
+            unit_order: UnitOrder::Declaration,
+            color_temperature: false,
+            format: cli.format,
+            csv_header: cli.csv_header,
+            precision: Some(0),
+            sig_figs: None,
+            scientific: false,
+            fraction: None,
+            raw: cli.raw,
+            explain: cli.explain,
+            group_digits: false,
+            decimal_separator: DecimalSeparator::Dot,
+            unit_names: cli.unit_names,
+            category: None,
+        };
+    }
+
+    OutputOptions {
+        rounding: cli.round,
+        fail_on_precision_loss: cli.fail_on_precision_loss,
+        unit_order: cli.deterministic_order,
+        color_temperature: color::color_enabled(
+            cli.color_temp,
+            if cli.no_color {
+                ColorChoice::Never
+            } else {
+                cli.color
+            },
+            color::no_color_requested(),
+            stdout_is_terminal,
+        ),
+        format: cli.format,
+        csv_header: cli.csv_header,
+        precision: cli.precision,
+        sig_figs: cli.sig_figs,
+        scientific: cli.scientific,
+        fraction: cli.fraction.then_some(cli.fraction_denominator),
+        raw: cli.raw,
+        explain: cli.explain,
+        group_digits: cli.group_digits,
+        decimal_separator: cli.decimal_separator,
+        unit_names: cli.unit_names,
+        category: cli.command.category(),
+    }
+}
+
+/// Most values one `--range` may sweep, so a typo like `0..1e9:1` fails
+/// instead of printing for hours
+const MAX_RANGE_STEPS: usize = 10_000;
+
+/// `--range START..END:STEP`, a sweep of values for building tables
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ValueRange {
+    start: f64,
+    end: f64,
+    step: f64,
+}
+
+impl ValueRange {
+    /// START, START + STEP, ... up to and including END when a step lands
+    /// on it
+    fn values(&self) -> Vec<f64> {
+        (0..self.len())
+            .map(|index| self.start + index as f64 * self.step)
+            .collect()
+    }
+
+    fn len(&self) -> usize {
+        // The tolerance keeps END in when STEP doesn't divide exactly in
+        // binary, as with 0..1:0.1
+        ((self.end - self.start) / self.step + 1e-9).floor() as usize + 1
+    }
+}
+
+fn parse_value_range(range: &str) -> Result<ValueRange, String> {
+    let usage = || {
+        format!(
+            "Invalid range '{}'. Expected START..END:STEP, e.g. 0..100:10",
+            range
+        )
+    };
+    let (bounds, step) = range.rsplit_once(':').ok_or_else(usage)?;
+    let (start, end) = bounds.split_once("..").ok_or_else(usage)?;
+    let number = |text: &str| text.trim().parse::<f64>().map_err(|_| usage());
+    let (start, end, step) = (number(start)?, number(end)?, number(step)?);
+
+    if !(start.is_finite() && end.is_finite() && step.is_finite()) {
+        return Err(usage());
+    }
+    if step <= 0.0 {
+        return Err(format!("The range step must be positive, got {}", step));
+    }
+    if end < start {
+        return Err(format!(
+            "The range end ({}) is below its start ({})",
+            end, start
+        ));
+    }
+    let steps = (end - start) / step + 1.0;
+    if steps > MAX_RANGE_STEPS as f64 {
+        return Err(format!(
+            "Range '{}' has {} values; at most {} are allowed. Use a larger step.",
+            range,
+            steps.floor(),
+            MAX_RANGE_STEPS
+        ));
+    }
+
+    Ok(ValueRange { start, end, step })
+}
+
+/// What the `length` command converts into: another unit, or a percentage
+/// of a reference length
+#[derive(Debug, Clone)]
+enum LengthTarget {
+    Unit(Length),
+    Percent,
+}
+
+fn parse_length_target(target: &str) -> Result<LengthTarget, String> {
+    match target.to_lowercase().as_str() {
+        "percent" | "%" => Ok(LengthTarget::Percent),
+        _ => Length::from_str(target, true).map(LengthTarget::Unit),
+    }
+}
+
+#[derive(Subcommand, Clone)]
 enum Commands {
     #[command(about = "Convert between temperature units")]
     Temperature {
-        #[arg(long, help = "Source temperature unit")]
+        #[arg(short, long, help = "Source temperature unit")]
         from: Degree,
-        #[arg(long, help = "Target temperature unit")]
-        to: Degree,
-        #[arg(long, help = "Temperature value to convert")]
-        value: f64,
+        #[arg(short, long, help = "Target temperature unit (or --all)")]
+        to: Option<Degree>,
+        #[arg(
+            short,
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Temperature value to convert (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+        #[arg(
+            long,
+            conflicts_with = "to",
+            help = "Convert to every temperature unit instead of just --to"
+        )]
+        all: bool,
+        #[arg(
+            long,
+            help = "Clamp temperatures beyond absolute zero to absolute zero instead of failing"
+        )]
+        clamp: bool,
     },
     #[command(about = "Convert between length units")]
     Length {
-        #[arg(long, help = "Source length unit")]
+        #[arg(short, long, help = "Source length unit")]
         from: Length,
-        #[arg(long, help = "Target length unit")]
-        to: Length,
-        #[arg(long, help = "Length value to convert")]
-        value: f64,
+        #[arg(
+            short,
+            long,
+            value_parser = parse_length_target,
+            help = "Target length unit, or 'percent' to compare against --reference (or --all)"
+        )]
+        to: Option<LengthTarget>,
+        #[arg(
+            short,
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Length value to convert (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+        #[arg(
+            long,
+            conflicts_with_all = ["to", "reference"],
+            help = "Convert to every length unit instead of just --to"
+        )]
+        all: bool,
+        #[arg(
+            long,
+            help = "Reference quantity for '--to percent' (e.g., '200cm', '1.5m')"
+        )]
+        reference: Option<String>,
     },
-    #[command(about = "Convert between units (automatically detects unit type)")]
-    Convert {
-        #[arg(long, help = "Source unit (e.g., 'celsius', 'cm', 'c', 'centimeter')")]
+    #[command(about = "Convert between mass/weight units")]
+    Mass {
+        #[arg(long, help = "Source mass unit")]
+        from: Mass,
+        #[arg(long, help = "Target mass unit")]
+        to: Mass,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Mass value to convert (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+    },
+    #[command(about = "Convert between volume units")]
+    Volume {
+        #[arg(long, help = "Source volume unit")]
+        from: Volume,
+        #[arg(long, help = "Target volume unit")]
+        to: Volume,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Volume value to convert (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+    },
+    #[command(about = "Convert between area units")]
+    Area {
+        #[arg(long, help = "Source area unit")]
+        from: Area,
+        #[arg(long, help = "Target area unit")]
+        to: Area,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Area value to convert (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+    },
+    #[command(about = "Convert between speed units")]
+    Speed {
+        #[arg(long, help = "Source speed unit")]
+        from: Speed,
+        #[arg(long, help = "Target speed unit")]
+        to: Speed,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Speed value to convert (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+    },
+    #[command(about = "Convert between time-duration units")]
+    Time {
+        #[arg(long, help = "Source time unit")]
+        from: Time,
+        #[arg(long, help = "Target time unit")]
+        to: Time,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Time value to convert (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+    },
+    #[command(about = "Convert between digital storage units")]
+    Data {
+        #[arg(long, help = "Source data unit")]
+        from: Data,
+        #[arg(long, help = "Target data unit")]
+        to: Data,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Data value to convert (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+    },
+    #[command(about = "Convert between pressure units")]
+    Pressure {
+        #[arg(long, help = "Source pressure unit")]
+        from: Pressure,
+        #[arg(long, help = "Target pressure unit")]
+        to: Pressure,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Pressure value to convert (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+    },
+    #[command(about = "Convert between energy units")]
+    Energy {
+        #[arg(long, help = "Source energy unit")]
+        from: Energy,
+        #[arg(long, help = "Target energy unit")]
+        to: Energy,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Energy value to convert (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+    },
+    #[command(about = "Convert between power units")]
+    Power {
+        #[arg(long, help = "Source power unit")]
+        from: Power,
+        #[arg(long, help = "Target power unit")]
+        to: Power,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Power value to convert (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+    },
+    #[command(about = "Convert between angle units")]
+    Angle {
+        #[arg(long, help = "Source angle unit")]
+        from: Angle,
+        #[arg(long, help = "Target angle unit")]
+        to: Angle,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Angle value to convert (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+    },
+    #[command(about = "Convert between frequency units")]
+    Frequency {
+        #[arg(long, help = "Source frequency unit")]
+        from: Frequency,
+        #[arg(long, help = "Target frequency unit")]
+        to: Frequency,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Frequency value to convert (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+    },
+    #[command(about = "Convert between fuel economy units (mpg ↔ L/100km)")]
+    Fuel {
+        #[arg(long, help = "Source fuel economy unit")]
+        from: FuelEconomy,
+        #[arg(long, help = "Target fuel economy unit")]
+        to: FuelEconomy,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Fuel economy value to convert (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+    },
+    #[command(about = "Convert between AWG wire gauge and diameter in millimeters")]
+    Wire {
+        #[arg(long, help = "Source unit (awg or millimeter)")]
+        from: WireGauge,
+        #[arg(long, help = "Target unit (awg or millimeter)")]
+        to: WireGauge,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Gauge number or diameter to convert (4/0 is -3) (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+    },
+    #[command(about = "Convert running paces (min/km, min/mi) and speeds (km/h, mph)")]
+    Pace {
+        #[arg(
+            long,
+            help = "Source unit (min-per-km, min-per-mile, km-per-hour or mile-per-hour)"
+        )]
+        from: Pace,
+        #[arg(
+            long,
+            help = "Target unit (min-per-km, min-per-mile, km-per-hour or mile-per-hour)"
+        )]
+        to: Pace,
+        #[arg(
+            long,
+            help = "Pace as minutes:seconds (e.g., '5:30') or a speed (e.g., '12')"
+        )]
+        value: Option<String>,
+    },
+    #[command(about = "Convert adult shoe sizes between the US, UK and EU systems")]
+    Shoesize {
+        #[arg(long, help = "Source size system (us, uk or eu)")]
+        from: ShoeSystem,
+        #[arg(long, help = "Target size system (us, uk or eu)")]
+        to: ShoeSystem,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Shoe size to convert (half sizes allowed, e.g. 9.5) (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+        #[arg(long, help = "Size chart to use: men or women (US sizes differ)")]
+        gender: ShoeGender,
+    },
+    #[command(about = "Reduce a resolution to its aspect ratio, or convert pixels ↔ inches")]
+    Aspect {
+        #[arg(
+            long,
+            help = "Resolution, ratio, pixels or inches (e.g., '1920x1080', '1920px', '20in')"
+        )]
+        from: String,
+        #[arg(long, help = "Target (ratio, inch or px)")]
+        to: AspectTarget,
+        #[arg(long, help = "Pixel density, needed for pixel ↔ inch conversions")]
+        dpi: Option<f64>,
+    },
+    #[command(about = "Great-circle distance between two latitude,longitude points")]
+    Geodistance {
+        #[arg(
+            long,
+            allow_hyphen_values = true,
+            help = "Start point in decimal degrees (e.g., '40.7128,-74.0060')"
+        )]
         from: String,
-        #[arg(long, help = "Target unit (e.g., 'fahrenheit', 'inch', 'f', 'in')")]
+        #[arg(
+            long,
+            allow_hyphen_values = true,
+            help = "End point in decimal degrees (e.g., '34.0522,-118.2437')"
+        )]
         to: String,
-        #[arg(long, help = "Value to convert")]
-        value: f64,
+        #[arg(
+            long,
+            default_value = "km",
+            value_parser = parse_length_unit,
+            help = "Length unit of the result"
+        )]
+        unit: Length,
+    },
+    #[command(about = "Volume of an ideal gas from moles, temperature and pressure (PV = nRT)")]
+    GasVolume {
+        #[arg(long, help = "Amount of gas in moles")]
+        moles: f64,
+        #[arg(long, allow_negative_numbers = true, help = "Gas temperature")]
+        temp: f64,
+        #[arg(
+            long,
+            default_value = "k",
+            value_parser = parse_temperature_unit,
+            help = "Unit of --temp"
+        )]
+        temp_unit: Degree,
+        #[arg(long, help = "Gas pressure")]
+        pressure: f64,
+        #[arg(
+            long,
+            default_value = "pa",
+            value_parser = parse_pressure_unit,
+            help = "Unit of --pressure"
+        )]
+        pressure_unit: Pressure,
+        #[arg(
+            long,
+            default_value = "l",
+            value_parser = parse_volume_unit,
+            help = "Volume unit of the result"
+        )]
+        to: Volume,
+    },
+    #[command(about = "Convert between units (automatically detects unit type)")]
+    Convert {
+        #[arg(
+            short,
+            long,
+            help = "Source unit (e.g., 'celsius', 'cm', 'c', 'centimeter')"
+        )]
+        from: Option<String>,
+        #[arg(
+            short,
+            long,
+            help = "Target unit (e.g., 'fahrenheit', 'inch', 'f', 'in')"
+        )]
+        to: Option<String>,
+        #[arg(
+            short,
+            long,
+            value_delimiter = ',',
+            allow_negative_numbers = true,
+            help = "Value to convert (repeat or comma-separate for several)"
+        )]
+        value: Vec<f64>,
+        #[arg(
+            value_name = "ARGS",
+            num_args = 0..=3,
+            allow_negative_numbers = true,
+            help = "Quick form: <VALUE> <FROM> <TO> (the value may appear in any position)"
+        )]
+        positional: Vec<String>,
+        #[arg(
+            long,
+            help = "Clamp temperatures beyond absolute zero to absolute zero instead of failing"
+        )]
+        clamp: bool,
+        #[arg(
+            long,
+            alias = "from-stdin-loop",
+            help = "Read '<VALUE> <FROM> <TO>' requests from stdin, one per line, answering each as it arrives"
+        )]
+        watch: bool,
+    },
+    #[command(
+        about = "Convert 'from,to,value' lines from stdin or a file, one answer per line",
+        long_about = "Reads conversion requests from stdin (or --file), one per line (e.g. \
+                      'cm,inch,188' or '188 cm inch'; the first line decides which), and prints \
+                      one answer per line. A failed line is answered with an error marker instead \
+                      of stopping the run; a summary of how many requests converted and failed \
+                      goes to stderr. Input starting with '[' is read as a JSON array of \
+                      {\"from\",\"to\",\"value\"} objects and answered with a JSON array."
+    )]
+    Batch {
+        #[arg(long, help = "Read requests from this file instead of stdin")]
+        file: Option<PathBuf>,
+        #[arg(
+            long,
+            value_name = "N",
+            help = "Stop once N requests have failed instead of converting the whole input"
+        )]
+        max_errors: Option<NonZeroUsize>,
+        #[arg(
+            long,
+            value_name = "N",
+            requires = "to",
+            help = "Read each row's source unit from field N (counting from 1) and convert it to --to"
+        )]
+        from_column: Option<NonZeroUsize>,
+        #[arg(
+            long,
+            value_name = "UNIT",
+            requires = "from_column",
+            help = "Target unit for every row read with --from-column"
+        )]
+        to: Option<String>,
+    },
+    #[command(about = "List every supported unit with its symbol and aliases, by category")]
+    ListUnits {
+        #[arg(
+            long,
+            value_parser = clap::builder::PossibleValuesParser::new(
+                UNIT_CATEGORIES.iter().map(|(category, _)| *category)
+            ),
+            value_delimiter = ',',
+            help = "Only list these categories (comma-separated)"
+        )]
+        category: Vec<String>,
+    },
+    #[command(about = "Print the category a unit belongs to (temperature, length, ...)")]
+    Category {
+        #[arg(long, help = "Unit to look up (e.g., 'cm', 'Fahrenheit')")]
+        unit: String,
     },
+    #[command(about = "Print the canonical name of a messy unit string")]
+    Normalize {
+        #[arg(help = "Unit to normalize (e.g., 'CentiMeters ', 'Inches')")]
+        unit: String,
+    },
+    #[command(about = "Check that two quantities are equivalent (e.g., --a 0c --b 32f)")]
+    CheckEquiv {
+        #[arg(long, help = "First quantity, value fused with its unit (e.g., '0c')")]
+        a: String,
+        #[arg(
+            long,
+            help = "Second quantity, value fused with its unit (e.g., '32f')"
+        )]
+        b: String,
+        #[arg(
+            long,
+            default_value_t = 1e-6,
+            help = "Allowed absolute difference, in the unit of --b"
+        )]
+        tolerance: f64,
+    },
+    #[command(about = "Print the JSON Schema of conversion requests and results")]
+    EmitSchema,
+    #[command(about = "Run the named conversions listed in a TOML or JSON job file")]
+    Run {
+        #[arg(help = "Job file ending in .toml or .json")]
+        file: PathBuf,
+        #[arg(
+            long,
+            value_name = "N",
+            help = "Stop once N jobs have failed instead of running the whole file"
+        )]
+        max_errors: Option<NonZeroUsize>,
+    },
+    #[command(about = "Check a custom units file and list every problem in it")]
+    LintUnits {
+        #[arg(help = "Custom units file (TOML)")]
+        file: PathBuf,
+    },
+    #[command(about = "Print a shell completion script, e.g. uniconv completions bash")]
+    Completions {
+        #[arg(value_enum, help = "Shell to complete for")]
+        shell: Shell,
+    },
+    /// Print the uniconv(1) man page in roff, for packagers to ship as
+    /// uniconv.1
+    #[command(hide = true)]
+    Man,
 }
 
-fn format_number(value: f64) -> String {
-    println!("{}", value);
-    format!("{}", value.round_ties_even() as i64)
+/// Prints `<value> <from> = <result> <to>`, first checking the round trip
+/// when `--fail-on-precision-loss` is set
+fn report_conversion(
+    out: &mut dyn Write,
+    value: f64,
+    from: &dyn UnitLabel,
+    result: f64,
+    to: &dyn UnitLabel,
+    output: OutputOptions,
+    convert_back: impl FnOnce(f64) -> Result<f64>,
+) -> Result<()> {
+    let printed = checked_result(value, from, result, to, output, convert_back)?;
+
+    print_conversion(
+        out,
+        format!(
+            "{} {} = {} {}",
+            format_value(value, output),
+            unit_label(from, value, output),
+            printed,
+            unit_label(to, printed_value(&printed, result, output), output)
+        ),
+        &printed,
+        output,
+    )?;
+    Ok(())
 }
 
-fn validate_numeric_input(value: f64, context: &str) -> Result<()> {
-    if value.is_nan() {
-        return Err(anyhow!("{} cannot be NaN (Not a Number)", context));
+/// Prints a finished conversion: the whole sentence, or under `--raw` the
+/// printed result alone
+fn print_conversion(
+    out: &mut dyn Write,
+    sentence: String,
+    result: &str,
+    output: OutputOptions,
+) -> Result<()> {
+    if output.raw {
+        writeln!(out, "{}", result)?;
+    } else {
+        writeln!(out, "{}", sentence)?;
     }
+    Ok(())
+}
 
-    if value.is_infinite() {
-        return Err(anyhow!("{} cannot be infinite", context));
+/// Under `--explain`, prints the formula behind a temperature result, e.g.
+/// `(25 × 9/5) + 32 = 77`
+fn explain_temperature(
+    out: &mut dyn Write,
+    value: f64,
+    from: &Degree,
+    to: &Degree,
+    clamp: bool,
+    output: OutputOptions,
+) -> Result<()> {
+    if !explains(output) {
+        return Ok(());
     }
 
-    // Check for extremely large numbers that might cause precision issues
-    if value.abs() > 1e15 {
-        return Err(anyhow!(
-            "{} is too large (absolute value exceeds 1e15). Please use a smaller number.",
-            context
-        ));
+    let value = if clamp {
+        clamp_to_absolute_zero(value, from).0
+    } else {
+        value
+    };
+    let (result, formula) = TemperatureConverter::new(value, *from).convert_to_explained(*to)?;
+    writeln!(out, "{} = {}", formula, format_result(result, output))?;
+    Ok(())
+}
+
+/// Under `--explain`, prints the scaling behind a length result, e.g.
+/// `5 ÷ 1.609344 = 3`
+fn explain_length(
+    out: &mut dyn Write,
+    value: f64,
+    from: &Length,
+    to: &Length,
+    output: OutputOptions,
+) -> Result<()> {
+    if !explains(output) {
+        return Ok(());
     }
 
+    let (result, formula) = LengthConverter::new(value, *from).convert_to_explained(*to)?;
+    writeln!(out, "{} = {}", formula, format_result(result, output))?;
     Ok(())
 }
 
-fn convert_temperature(value: f64, from: Degree, to: Degree) -> Result<f64> {
-    // Validate input
-    validate_numeric_input(value, "Temperature value")?;
+/// `report_conversion` for temperatures: under `--color-temp` the result is
+/// colored by how hot it is
+fn report_temperature(
+    out: &mut dyn Write,
+    value: f64,
+    from: &Degree,
+    result: f64,
+    to: &Degree,
+    output: OutputOptions,
+    convert_back: impl FnOnce(f64) -> Result<f64>,
+) -> Result<()> {
+    let printed = checked_result(value, from, result, to, output, convert_back)?;
+    if output.raw {
+        writeln!(out, "{}", printed)?;
+        return Ok(());
+    }
 
-    match from {
-        Degree::Kelvin if value < 0.0 => {
-            return Err(anyhow!(
-                "Kelvin temperature cannot be negative ({}K). Minimum is 0 K (absolute zero).",
-                value
-            ));
-        }
-        Degree::Celsius if value < -273.15 => {
-            return Err(anyhow!(
-                "Celsius temperature cannot be below absolute zero ({}°C < -273.15°C).",
-                value
-            ));
-        }
-        Degree::Fahrenheit if value < -459.67 => {
-            return Err(anyhow!(
-                "Fahrenheit temperature cannot be below absolute zero ({}°F < -459.67°F).",
-                value
-            ));
-        }
-        _ => {}
+    let mut result_text = format!(
+        "{} {}",
+        printed,
+        unit_label(to, printed_value(&printed, result, output), output)
+    );
+
+    if output.color_temperature {
+        let celsius = TemperatureConverter::new(result, *to).convert_to(Degree::Celsius)?;
+        result_text = color::paint_temperature(&result_text, celsius, true);
     }
 
-    let converter = TemperatureConverter::new(value, from.into());
-    let result = converter
-        .convert_to(to.into())
-        .context("Failed to perform temperature conversion")?;
+    writeln!(
+        out,
+        "{} {} = {}",
+        format_value(value, output),
+        unit_label(from, value, output),
+        result_text
+    )?;
+    Ok(())
+}
 
-    // Validate result
-    validate_numeric_input(result, "Conversion result")?;
+/// Prints the conversion as one JSON object or CSV record (after the header,
+/// if asked for). The result is not rounded, so there is nothing for
+/// `--fail-on-precision-loss` to check. The JSON object takes `warnings`
+/// along, leaving none for stderr.
+fn print_machine_result(
+    out: &mut dyn Write,
+    value: f64,
+    from: &dyn Display,
+    result: f64,
+    to: &dyn Display,
+    output: OutputOptions,
+    warnings: &mut Warnings,
+) -> Result<()> {
+    if output.format == OutputFormat::Csv && output.csv_header {
+        writeln!(out, "{}", output::CSV_HEADER)?;
+    }
+    let warnings = if output.format == OutputFormat::Json {
+        std::mem::take(warnings)
+    } else {
+        Warnings::new()
+    };
+    if let Some(line) = output::machine_line(output.format, value, from, result, to, &warnings)? {
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// The results of `--all`: one conversion sentence per unit as text, the
+/// `results` struct as one JSON object, or one CSV record per unit
+fn print_all_results<U: UnitLabel, R: Serialize>(
+    out: &mut dyn Write,
+    value: f64,
+    from: &dyn UnitLabel,
+    rows: &[(U, f64)],
+    results: &R,
+    output: OutputOptions,
+) -> Result<()> {
+    if output.format == OutputFormat::Text {
+        for (unit, result) in rows {
+            let printed = format_result(*result, output);
+            let sentence = format!(
+                "{} {} = {} {}",
+                format_value(value, output),
+                unit_label(from, value, output),
+                printed,
+                unit_label(unit, printed_value(&printed, *result, output), output)
+            );
+            print_conversion(out, sentence, &printed, output)?;
+        }
+        return Ok(());
+    }
+    if output.format == OutputFormat::Json {
+        writeln!(out, "{}", output::all_results_json(results, rows)?)?;
+        return Ok(());
+    }
 
-    Ok(result)
+    if output.format == OutputFormat::Csv && output.csv_header {
+        writeln!(out, "{}", output::CSV_HEADER)?;
+    }
+    for (unit, result) in rows {
+        let line =
+            output::machine_line(output.format, value, from, *result, unit, &Warnings::new())?;
+        if let Some(line) = line {
+            writeln!(out, "{}", line)?;
+        }
+    }
+    Ok(())
 }
 
-fn convert_length(value: f64, from: Length, to: Length) -> Result<f64> {
-    // Validate input
-    validate_numeric_input(value, "Length value")?;
+/// `--value`, or when it was left out, the number piped into stdin (as in
+/// `echo 25 | uniconv convert --from c --to f`). Several values never get
+/// here: `execute_to` runs the command once for each.
+fn value_or_stdin(values: &[f64]) -> Result<f64> {
+    match values.first() {
+        Some(value) => Ok(*value),
+        None => parse_stdin_value(&value_text_or_stdin(None)?),
+    }
+}
 
-    if value < 0.0 {
-        return Err(anyhow!(
-            "Length cannot be negative ({}). Please provide a positive value.",
-            value
-        ));
+/// `value_or_stdin` for values that aren't plain numbers, like paces. With
+/// a terminal on stdin nothing was piped, so that's an error rather than a
+/// wait for typing; the batch and watch modes, which read stdin themselves,
+/// never get here.
+fn value_text_or_stdin(value: Option<String>) -> Result<String> {
+    if let Some(value) = value {
+        return Ok(value);
     }
 
-    if value > 1e12 {
+    let stdin = std::io::stdin();
+    if stdin.is_terminal() {
         return Err(anyhow!(
-            "Length value {} is unrealistically large. Please check your input.",
-            value
+            "Missing --value. Pass it, or pipe it in: echo 25 | uniconv convert --from c --to f"
         ));
     }
+    read_stdin_value(stdin.lock())
+}
 
-    let converter = LengthConverter::new(value, from.into());
-    let result = converter
-        .convert_to(to.into())
-        .context("Failed to perform length conversion")?;
-
-    // Validate result
-    validate_numeric_input(result, "Conversion result")?;
+/// The single value piped into `input`, e.g. `25` from `echo 25`
+fn read_stdin_value(mut input: impl std::io::Read) -> Result<String> {
+    let mut text = String::new();
+    input
+        .read_to_string(&mut text)
+        .context("Failed to read the value from stdin")?;
 
-    Ok(result)
+    let mut tokens = text.split_whitespace();
+    match (tokens.next(), tokens.next()) {
+        (Some(value), None) => Ok(value.to_string()),
+        (None, _) => Err(anyhow!(
+            "No value on stdin. Pass --value or pipe a number in, e.g. echo 25 | uniconv ..."
+        )),
+        (Some(_), Some(_)) => Err(anyhow!(
+            "Expected a single value on stdin, got '{}'. Use 'uniconv batch' for many conversions.",
+            text.trim()
+        )),
+    }
 }
 
-fn find_closest_match(input: &str, valid_units: &[&str]) -> Option<String> {
-    let input_lower = input.to_lowercase();
+/// A value read from stdin, held to the same limits as `--value`
+fn parse_stdin_value(text: &str) -> Result<f64> {
+    let value: f64 = text
+        .parse()
+        .map_err(|_| anyhow!("'{}' on stdin is not a number", text))?;
+    validate_numeric_input(value, "Value from stdin")?;
+    Ok(value)
+}
 
-    // First, try exact matches or partial matches
-    for unit in valid_units {
-        if unit.to_lowercase().contains(&input_lower) || input_lower.contains(&unit.to_lowercase())
-        {
-            return Some(unit.to_string());
+impl Commands {
+    /// The category a command's results are in, for their default decimals:
+    /// a category command's own, or the quantity a calculator prints (gas
+    /// volumes, distances, speeds, wire diameters). `convert` and `batch`
+    /// only know theirs once the units are read.
+    fn category(&self) -> Option<&'static str> {
+        match self {
+            Commands::Temperature { .. } => Some("temperature"),
+            Commands::Length { .. } => Some("length"),
+            Commands::Mass { .. } => Some("mass"),
+            Commands::Volume { .. } => Some("volume"),
+            Commands::Area { .. } => Some("area"),
+            Commands::Speed { .. } => Some("speed"),
+            Commands::Time { .. } => Some("time"),
+            Commands::Data { .. } => Some("data"),
+            Commands::Pressure { .. } => Some("pressure"),
+            Commands::Energy { .. } => Some("energy"),
+            Commands::Power { .. } => Some("power"),
+            Commands::Angle { .. } => Some("angle"),
+            Commands::Frequency { .. } => Some("frequency"),
+            Commands::Fuel { .. } => Some("fuel"),
+            Commands::GasVolume { .. } => Some("volume"),
+            Commands::Geodistance { .. } | Commands::Wire { .. } => Some("length"),
+            Commands::Pace { .. } => Some("speed"),
+            _ => None,
         }
     }
 
-    // If no partial match, find the unit with minimum edit distance
-    let mut best_match = None;
-    let mut min_distance = usize::MAX;
-
-    for unit in valid_units {
-        let distance = levenshtein_distance(&input_lower, &unit.to_lowercase());
-        if distance < min_distance && distance <= 3 {
-            // Only suggest if distance is reasonable
-            min_distance = distance;
-            best_match = Some(unit.to_string());
+    /// The `--value`s of a command that converts numbers
+    fn values(&self) -> Option<&[f64]> {
+        match self {
+            Commands::Temperature { value, .. }
+            | Commands::Length { value, .. }
+            | Commands::Mass { value, .. }
+            | Commands::Volume { value, .. }
+            | Commands::Area { value, .. }
+            | Commands::Speed { value, .. }
+            | Commands::Time { value, .. }
+            | Commands::Data { value, .. }
+            | Commands::Pressure { value, .. }
+            | Commands::Energy { value, .. }
+            | Commands::Power { value, .. }
+            | Commands::Angle { value, .. }
+            | Commands::Frequency { value, .. }
+            | Commands::Fuel { value, .. }
+            | Commands::Wire { value, .. }
+            | Commands::Shoesize { value, .. }
+            | Commands::Convert { value, .. } => Some(value),
+            _ => None,
         }
     }
 
-    best_match
+    fn values_mut(&mut self) -> Option<&mut Vec<f64>> {
+        match self {
+            Commands::Temperature { value, .. }
+            | Commands::Length { value, .. }
+            | Commands::Mass { value, .. }
+            | Commands::Volume { value, .. }
+            | Commands::Area { value, .. }
+            | Commands::Speed { value, .. }
+            | Commands::Time { value, .. }
+            | Commands::Data { value, .. }
+            | Commands::Pressure { value, .. }
+            | Commands::Energy { value, .. }
+            | Commands::Power { value, .. }
+            | Commands::Angle { value, .. }
+            | Commands::Frequency { value, .. }
+            | Commands::Fuel { value, .. }
+            | Commands::Wire { value, .. }
+            | Commands::Shoesize { value, .. }
+            | Commands::Convert { value, .. } => Some(value),
+            _ => None,
+        }
+    }
 }
 
-fn levenshtein_distance(s1: &str, s2: &str) -> usize {
-    let len1 = s1.len();
-    let len2 = s2.len();
-    let mut matrix = vec![vec![0; len2 + 1]; len1 + 1];
+/// The conversion a command asks for: its value and both units. The simple
+/// category commands (mass to fuel) are converted from this, and so is every
+/// command under `--format table`, which lists every unit of the category
+/// rather than just the target.
+fn conversion_request(command: &Commands) -> Result<(f64, RecognizedUnit, RecognizedUnit)> {
+    let (value, from, to): (&[f64], RecognizedUnit, RecognizedUnit) = match command {
+        Commands::Temperature {
+            from, to, value, ..
+        } => (value, (*from).into(), to.unwrap_or(*from).into()),
+        Commands::Length {
+            from,
+            to: Some(LengthTarget::Unit(to)),
+            value,
+            ..
+        } => (value, (*from).into(), (*to).into()),
+        Commands::Length { from, value, .. } => (value, (*from).into(), (*from).into()),
+        Commands::Mass { from, to, value } => (value, (*from).into(), (*to).into()),
+        Commands::Volume { from, to, value } => (value, (*from).into(), (*to).into()),
+        Commands::Area { from, to, value } => (value, (*from).into(), (*to).into()),
+        Commands::Speed { from, to, value } => (value, (*from).into(), (*to).into()),
+        Commands::Time { from, to, value } => (value, (*from).into(), (*to).into()),
+        Commands::Data { from, to, value } => (value, (*from).into(), (*to).into()),
+        Commands::Pressure { from, to, value } => (value, (*from).into(), (*to).into()),
+        Commands::Energy { from, to, value } => (value, (*from).into(), (*to).into()),
+        Commands::Power { from, to, value } => (value, (*from).into(), (*to).into()),
+        Commands::Angle { from, to, value } => (value, (*from).into(), (*to).into()),
+        Commands::Frequency { from, to, value } => (value, (*from).into(), (*to).into()),
+        Commands::Fuel { from, to, value } => (value, (*from).into(), (*to).into()),
+        Commands::Convert {
+            from,
+            to,
+            value,
+            positional,
+            watch: false,
+            ..
+        } => {
+            let (from, to, value) =
+                resolve_convert_args(from.clone(), to.clone(), value.first().copied(), positional)?;
+            return Ok((value, require_unit(&from)?, require_unit(&to)?));
+        }
+        _ => {
+            return Err(anyhow!(
+                "--format table only applies to 'convert' and the unit category commands."
+            ))
+        }
+    };
+    Ok((value_or_stdin(value)?, from, to))
+}
 
-    for i in 0..=len1 {
-        matrix[i][0] = i;
-    }
-    for j in 0..=len2 {
-        matrix[0][j] = j;
+fn resolve_convert_args(
+    from: Option<String>,
+    to: Option<String>,
+    value: Option<f64>,
+    positional: &[String],
+) -> Result<(String, String, f64)> {
+    if !positional.is_empty() {
+        if from.is_some() || to.is_some() || value.is_some() {
+            return Err(anyhow!(
+                "Cannot mix positional arguments with --from/--to/--value. Use one form or the other."
+            ));
+        }
+        return parse_positional(positional);
     }
 
-    for (i, c1) in s1.chars().enumerate() {
-        for (j, c2) in s2.chars().enumerate() {
-            let cost = if c1 == c2 { 0 } else { 1 };
-            matrix[i + 1][j + 1] = (matrix[i][j + 1] + 1)
-                .min(matrix[i + 1][j] + 1)
-                .min(matrix[i][j] + cost);
+    match (from, to, value) {
+        (Some(from), Some(to), Some(value)) => Ok((from, to, value)),
+        (Some(from), Some(to), None) if !std::io::stdin().is_terminal() => {
+            Ok((from, to, value_or_stdin(&[])?))
+        }
+        (from, to, value) => {
+            let mut missing = Vec::new();
+            if from.is_none() {
+                missing.push("--from");
+            }
+            if to.is_none() {
+                missing.push("--to");
+            }
+            if value.is_none() {
+                missing.push("--value");
+            }
+            Err(anyhow!(
+                "Missing {}. Use --from/--to/--value or the quick form: uniconv convert 25 c f",
+                missing.join(", ")
+            ))
         }
     }
-
-    matrix[len1][len2]
 }
 
-fn parse_temperature_unit(unit: &str) -> Result<Degree> {
-    match unit.to_lowercase().as_str() {
-        "celsius" | "c" => Ok(Degree::Celsius),
-        "fahrenheit" | "f" => Ok(Degree::Fahrenheit),
-        "kelvin" | "k" => Ok(Degree::Kelvin),
-        _ => {
-            let valid_units = &["celsius", "c", "fahrenheit", "f", "kelvin", "k"];
-            let mut error_msg = format!("Invalid temperature unit: '{}'.\n", unit);
-            error_msg.push_str("Valid temperature units are:\n");
-            error_msg.push_str("  • celsius (or 'c')\n");
-            error_msg.push_str("  • fahrenheit (or 'f')\n");
-            error_msg.push_str("  • kelvin (or 'k')\n");
-
-            if let Some(suggestion) = find_closest_match(unit, valid_units) {
-                error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
+/// Converts `value` between two units named as text, detecting their
+/// category from the spellings
+fn detect_and_convert(
+    out: &mut dyn Write,
+    from: &str,
+    to: &str,
+    value: f64,
+    clamp: bool,
+    output: OutputOptions,
+    warnings: &mut Warnings,
+) -> Result<()> {
+    match (recognize_unit(from), recognize_unit(to)) {
+        (Some(from_unit), Some(to_unit)) if from_unit.category() == to_unit.category() => {
+            note_assumed_gallon(&[from, to], warnings);
+            report_recognized(out, value, &from_unit, &to_unit, clamp, output, warnings)
+        }
+        (Some(from_unit), Some(to_unit)) => Err(anyhow!(
+            "Cannot convert between different unit types. '{}' and '{}' are from different categories ({} vs {}).",
+            from, to, from_unit.category(), to_unit.category()
+        )),
+        (from_unit, to_unit) => {
+            let mut error_msg = String::new();
+            if from_unit.is_none() {
+                error_msg.push_str(&format!("Invalid source unit: '{}'\n", from));
+            }
+            if to_unit.is_none() {
+                error_msg.push_str(&format!("Invalid target unit: '{}'\n", to));
             }
+            error_msg.push_str(SUPPORTED_UNITS_HELP);
 
+            for (unit, recognized, role) in [(from, &from_unit, "source"), (to, &to_unit, "target")] {
+                if let (None, Some(suggestion)) = (recognized, suggest_unit(unit)) {
+                    error_msg.push_str(&format!(
+                        "\nDid you mean '{}' for the {} unit?",
+                        suggestion, role
+                    ));
+                }
+            }
             Err(anyhow!(error_msg))
         }
     }
 }
 
-fn parse_length_unit(unit: &str) -> Result<Length> {
-    match unit.to_lowercase().as_str() {
-        "centimeter" | "cm" => Ok(Length::Centimeter),
-        "inch" | "in" => Ok(Length::Inch),
-        "kilometer" | "km" => Ok(Length::Kilometer),
-        "miles" | "mi" => Ok(Length::Miles),
-        _ => {
-            let valid_units = &[
-                "centimeter",
-                "cm",
-                "inch",
-                "in",
-                "kilometer",
-                "km",
-                "miles",
-                "mi",
-            ];
-            let mut error_msg = format!("Invalid length unit: '{}'.\n", unit);
-            error_msg.push_str("Valid length units are:\n");
-            error_msg.push_str("  • centimeter (or 'cm')\n");
-            error_msg.push_str("  • inch (or 'in')\n");
-            error_msg.push_str("  • kilometer (or 'km')\n");
-            error_msg.push_str("  • miles (or 'mi')\n");
-
-            if let Some(suggestion) = find_closest_match(unit, valid_units) {
-                error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
-            }
+/// Converts and prints one conversion between units of the same category,
+/// with the formula under `--explain` for temperatures and lengths
+fn report_recognized(
+    out: &mut dyn Write,
+    value: f64,
+    from: &RecognizedUnit,
+    to: &RecognizedUnit,
+    clamp: bool,
+    output: OutputOptions,
+    warnings: &mut Warnings,
+) -> Result<()> {
+    let output = output.in_category(from.category());
+    let result =
+        convert_recognized_clamped(value, from, to, clamp, warnings).with_context(|| {
+            format!(
+                "Failed to convert {} {} to {}",
+                format_number(value, output.rounding),
+                from,
+                to
+            )
+        })?;
+    if output.format != OutputFormat::Text {
+        return print_machine_result(out, value, from, result, to, output, warnings);
+    }
+    let convert_back = |printed| convert_recognized(printed, to, from, &mut Warnings::new());
 
-            Err(anyhow!(error_msg))
+    match (from, to) {
+        (RecognizedUnit::Temperature(from), RecognizedUnit::Temperature(to)) => {
+            report_temperature(out, value, from, result, to, output, convert_back)?;
+            explain_temperature(out, value, from, to, clamp, output)
+        }
+        (RecognizedUnit::Length(from), RecognizedUnit::Length(to)) => {
+            report_conversion(out, value, from, result, to, output, convert_back)?;
+            explain_length(out, value, from, to, output)
         }
+        _ => report_conversion(out, value, from, result, to, output, convert_back),
     }
 }
 
-fn detect_and_convert(from: &str, to: &str, value: f64) -> Result<()> {
-    // First, try to parse both units as temperature units
-    let temp_from = parse_temperature_unit(from);
-    let temp_to = parse_temperature_unit(to);
+fn main() -> Result<()> {
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+    let config = Config::load(cli.config.as_deref())?;
+    apply_defaults(&mut cli, &config, &matches)?;
+    let format = cli.format;
 
-    if let (Ok(from_unit), Ok(to_unit)) = (temp_from, temp_to) {
-        // Both units are valid temperature units
-        let conversion_result = convert_temperature(value, from_unit.clone(), to_unit.clone())
-            .with_context(|| {
-                format!(
-                    "Failed to convert {} {} to {}",
-                    format_number(value),
-                    from_unit,
-                    to_unit
-                )
-            })?;
+    match execute(cli) {
+        Err(e) if format == OutputFormat::Json => {
+            let error = ErrorOutput {
+                error: format!("{:#}", e),
+            };
+            eprintln!("{}", serde_json::to_string(&error)?);
+            std::process::exit(1);
+        }
+        result => result,
+    }
+}
 
-        println!(
-            "{} {} = {} {}",
-            format_number(value),
-            from_unit,
-            format_number(conversion_result),
-            to_unit
-        );
-        return Ok(());
+/// Environment variable read as `--precision`
+const PRECISION_ENV: &str = "UNICONV_PRECISION";
+
+/// Fills in the settings that weren't given as flags, from their `UNICONV_*`
+/// environment variable, then the config file
+fn apply_defaults(cli: &mut Cli, config: &Config, matches: &ArgMatches) -> Result<()> {
+    let already_chosen = |id: &str| {
+        matches!(
+            matches.value_source(id),
+            Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+        )
+    };
+
+    // Read here rather than with clap's `env`, which would take the variable
+    // for a --precision flag and reject it next to --sig-figs. Either way
+    // --sig-figs replaces precision altogether.
+    if !already_chosen("precision") && cli.sig_figs.is_none() {
+        cli.precision = match precision_from_env()? {
+            Some(precision) => Some(precision),
+            None => config.precision,
+        };
+    }
+    if let (Some(format), false) = (config.format, already_chosen("format")) {
+        cli.format = format;
     }
+    if let (Some(rounding), false) = (config.rounding, already_chosen("round")) {
+        cli.round = rounding;
+    }
+    if let (Some(color), false) = (config.color, already_chosen("color")) {
+        cli.color = color;
+    }
+    Ok(())
+}
 
-    // If temperature parsing failed, try length units
-    let length_from = parse_length_unit(from);
-    let length_to = parse_length_unit(to);
+/// `UNICONV_PRECISION`, held to the same 0-15 range as `--precision`
+fn precision_from_env() -> Result<Option<u32>> {
+    let Some(text) = std::env::var_os(PRECISION_ENV) else {
+        return Ok(None);
+    };
+    let text = text.to_string_lossy();
+    match text.trim().parse::<u32>() {
+        Ok(precision) if precision <= jobs::MAX_PRECISION => Ok(Some(precision)),
+        _ => Err(anyhow!(
+            "{} must be a number of decimals from 0 to {}, got '{}'",
+            PRECISION_ENV,
+            jobs::MAX_PRECISION,
+            text
+        )),
+    }
+}
 
-    if let (Ok(from_unit), Ok(to_unit)) = (length_from, length_to) {
-        // Both units are valid length units
-        let conversion_result = convert_length(value, from_unit.clone(), to_unit.clone())
-            .with_context(|| {
-                format!(
-                    "Failed to convert {} {} to {}",
-                    format_number(value),
-                    from_unit,
-                    to_unit
-                )
-            })?;
+fn execute(cli: Cli) -> Result<()> {
+    let mut out = open_output(cli.output.as_deref(), cli.append)?;
+    let stdout_is_terminal = cli.output.is_none() && std::io::stdout().is_terminal();
+    let result = execute_to(cli, stdout_is_terminal, &mut *out);
+    // Flush what was written even if the command then failed; a closed
+    // pipe is not an error
+    write_lines(&mut *out, std::iter::empty::<&str>())?;
+    result
+}
 
-        println!(
-            "{} {} = {} {}",
-            format_number(value),
-            from_unit,
-            format_number(conversion_result),
-            to_unit
-        );
-        return Ok(());
+/// Where results go: the `--output` file, truncated unless `--append`, or
+/// stdout
+fn open_output(path: Option<&Path>, append: bool) -> Result<Box<dyn Write>> {
+    // Checked here rather than with clap's `requires`, which misses global
+    // flags given after the subcommand
+    let Some(path) = path else {
+        if append {
+            return Err(anyhow!("--append only applies with --output <PATH>"));
+        }
+        return Ok(Box::new(std::io::stdout().lock()));
+    };
+
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)
+        .with_context(|| format!("Failed to open output file '{}'", path.display()))?;
+    Ok(Box::new(std::io::BufWriter::new(file)))
+}
+
+/// Runs the command, writing its results to `out`. A command given several
+/// `--value`s, or a `--range`, runs once per value, so one bad value doesn't
+/// stop the rest.
+fn execute_to(mut cli: Cli, stdout_is_terminal: bool, out: &mut dyn Write) -> Result<()> {
+    if let Some(range) = cli.range {
+        match cli.command.values_mut() {
+            None => {
+                return Err(anyhow!(
+                    "--range only applies to commands that take a numeric --value"
+                ))
+            }
+            Some(values) if !values.is_empty() => {
+                return Err(anyhow!("--range replaces --value; pass one or the other"))
+            }
+            Some(values) => *values = range.values(),
+        }
+    }
+
+    let values = cli
+        .command
+        .values()
+        .map(<[f64]>::to_vec)
+        .unwrap_or_default();
+    if values.len() < 2 {
+        return execute_value(cli, stdout_is_terminal, out);
     }
 
-    // If we get here, neither temperature nor length parsing worked for both units
-    // Check if it's a mixed unit type error
-    let temp_from_ok = parse_temperature_unit(from).is_ok();
-    let temp_to_ok = parse_temperature_unit(to).is_ok();
-    let length_from_ok = parse_length_unit(from).is_ok();
-    let length_to_ok = parse_length_unit(to).is_ok();
+    let mut failed = 0;
+    for value in &values {
+        let mut single = cli.clone();
+        if let Some(slot) = single.command.values_mut() {
+            *slot = vec![*value];
+        }
+        if let Err(error) = execute_value(single, stdout_is_terminal, out) {
+            failed += 1;
+            report_value_error(&error, cli.format)?;
+        }
+    }
 
-    if (temp_from_ok && length_to_ok) || (length_from_ok && temp_to_ok) {
+    if failed > 0 {
         return Err(anyhow!(
-            "Cannot convert between different unit types. '{}' and '{}' are from different categories (temperature vs length).",
-            from, to
+            "{} of {} values failed to convert",
+            failed,
+            values.len()
         ));
     }
+    Ok(())
+}
 
-    // If both units are invalid, provide helpful error message
-    let mut error_msg = String::new();
+/// The error `run` and `batch` end with when `--max-errors` stopped them,
+/// counting `what` (jobs or requests)
+fn max_errors_reached(failed: usize, skipped: usize, total: usize, what: &str) -> anyhow::Error {
+    anyhow!(
+        "Aborted: --max-errors reached after {} failed {}(s); {} of {} {}s were not run",
+        failed,
+        what,
+        skipped,
+        total,
+        what
+    )
+}
 
-    if !temp_from_ok && !length_from_ok {
-        error_msg.push_str(&format!("Invalid source unit: '{}'\n", from));
-    }
-    if !temp_to_ok && !length_to_ok {
-        error_msg.push_str(&format!("Invalid target unit: '{}'\n", to));
+/// Prints the error for one of several `--value`s the way `main` prints a
+/// command's error, and carries on
+fn report_value_error(error: &anyhow::Error, format: OutputFormat) -> Result<()> {
+    if format == OutputFormat::Json {
+        let error = ErrorOutput {
+            error: format!("{:#}", error),
+        };
+        eprintln!("{}", serde_json::to_string(&error)?);
+    } else {
+        eprintln!("Error: {:?}", error);
     }
+    Ok(())
+}
 
-    error_msg.push_str("\nSupported units:\n");
-    error_msg.push_str("Temperature: celsius (c), fahrenheit (f), kelvin (k)\n");
-    error_msg.push_str("Length: centimeter (cm), inch (in), kilometer (km), miles (mi)\n");
-
-    // Try to provide suggestions
-    let temp_units = &["celsius", "c", "fahrenheit", "f", "kelvin", "k"];
-    let length_units = &[
-        "centimeter",
-        "cm",
-        "inch",
-        "in",
-        "kilometer",
-        "km",
-        "miles",
-        "mi",
-    ];
+/// Runs the command for its single `--value`
+fn execute_value(cli: Cli, stdout_is_terminal: bool, out: &mut dyn Write) -> Result<()> {
+    let output = output_options(&cli, stdout_is_terminal);
+    let rounding = output.rounding;
+    colored::control::set_override(output.color_temperature);
+    let mut warnings = Warnings::new();
 
-    if !temp_from_ok && !length_from_ok {
-        if let Some(suggestion) = find_closest_match(from, temp_units) {
-            error_msg.push_str(&format!(
-                "\nDid you mean '{}' for the source unit?",
-                suggestion
-            ));
-        } else if let Some(suggestion) = find_closest_match(from, length_units) {
-            error_msg.push_str(&format!(
-                "\nDid you mean '{}' for the source unit?",
-                suggestion
+    if output.format == OutputFormat::Table {
+        let (value, from, to) = conversion_request(&cli.command)?;
+        if !cli.only.is_empty() && !cli.only.iter().any(|only| only == from.category()) {
+            return Err(anyhow!(
+                "--format table: {} is not among --only {}",
+                from.category(),
+                cli.only.join(",")
             ));
         }
+        // Converting to the requested unit first validates the input
+        convert_recognized(value, &from, &to, &mut warnings)?;
+        let output = output.in_category(from.category());
+        let table = output::render_table(
+            &table_rows(value, &from, output.unit_order)?,
+            output.decimals(),
+            rounding,
+        );
+        if write_lines(out, table.lines())? {
+            warnings.write_text(&mut std::io::stderr())?;
+        }
+        return Ok(());
     }
 
-    if !temp_to_ok && !length_to_ok {
-        if let Some(suggestion) = find_closest_match(to, temp_units) {
-            error_msg.push_str(&format!(
-                "\nDid you mean '{}' for the target unit?",
-                suggestion
-            ));
-        } else if let Some(suggestion) = find_closest_match(to, length_units) {
-            error_msg.push_str(&format!(
-                "\nDid you mean '{}' for the target unit?",
-                suggestion
+    match cli.command {
+        Commands::Temperature {
+            to: None,
+            all: false,
+            ..
+        } => {
+            return Err(anyhow!(
+                "Missing target unit: pass --to <UNIT>, or --all to convert to every temperature unit"
             ));
         }
-    }
+        Commands::Temperature {
+            from,
+            to: None,
+            value,
+            clamp,
+            ..
+        } => {
+            let value = value_or_stdin(&value)?;
+            let results =
+                convert_temperature_all(value, from, clamp, &mut warnings).with_context(|| {
+                    format!(
+                        "Failed to convert {} {} to every temperature unit",
+                        format_number(value, rounding),
+                        from
+                    )
+                })?;
 
-    Err(anyhow!(error_msg))
-}
+            let rows = conv::in_unit_order(results.rows(), output.unit_order);
+            print_all_results(out, value, &from, &rows, &results, output)?;
+        }
+        Commands::Temperature {
+            from,
+            to: Some(to),
+            value,
+            clamp,
+            ..
+        } => {
+            let value = value_or_stdin(&value)?;
+            report_recognized(
+                out,
+                value,
+                &from.into(),
+                &to.into(),
+                clamp,
+                output,
+                &mut warnings,
+            )?;
+        }
+        Commands::Length {
+            to: None,
+            all: false,
+            ..
+        } => {
+            return Err(anyhow!(
+                "Missing target unit: pass --to <UNIT>, or --all to convert to every length unit"
+            ));
+        }
+        Commands::Length {
+            from,
+            to: None,
+            value,
+            ..
+        } => {
+            let value = value_or_stdin(&value)?;
+            let rows = convert_length_each(value, from, &mut warnings).with_context(|| {
+                format!(
+                    "Failed to convert {} {} to every length unit",
+                    format_number(value, rounding),
+                    from
+                )
+            })?;
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+            let rows = conv::in_unit_order(rows, output.unit_order);
+            match LengthConverter::new(value, from).convert_to_all() {
+                Ok(results) => print_all_results(out, value, &from, &rows, &results, output)?,
+                // Without a result for every unit there is no `LengthResults`;
+                // the JSON object has fields for the units that did convert
+                Err(_) => {
+                    let converted: BTreeMap<String, f64> = rows
+                        .iter()
+                        .map(|(unit, result)| (output::results_field(unit), *result))
+                        .collect();
+                    print_all_results(out, value, &from, &rows, &converted, output)?
+                }
+            }
+        }
+        Commands::Length {
+            from,
+            to: Some(LengthTarget::Percent),
+            value,
+            reference,
+            ..
+        } => {
+            let value = value_or_stdin(&value)?;
+            let reference = reference.ok_or_else(|| {
+                anyhow!("'--to percent' needs a reference length, e.g. --reference 200cm")
+            })?;
+            let (percent, reference_value, reference_unit) =
+                length_percent_of(value, from, &reference)?;
 
-    match cli.command {
-        Commands::Temperature { from, to, value } => {
-            let conversion_result = convert_temperature(value, from.clone(), to.clone())
-                .with_context(|| {
+            if output.format != OutputFormat::Text {
+                print_machine_result(out, value, &from, percent, &"%", output, &mut warnings)?;
+            } else {
+                let printed = format_result(percent, output);
+                print_conversion(
+                    out,
                     format!(
-                        "Failed to convert {} {} to {}",
-                        format_number(value),
+                        "{} {} = {}% of {} {}",
+                        format_value(value, output),
                         from,
-                        to
-                    )
+                        printed,
+                        reference_value,
+                        reference_unit
+                    ),
+                    &printed,
+                    output,
+                )?;
+            }
+        }
+        Commands::Length {
+            from,
+            to: Some(LengthTarget::Unit(to)),
+            value,
+            reference,
+            ..
+        } => {
+            let value = value_or_stdin(&value)?;
+            if reference.is_some() {
+                return Err(anyhow!("--reference only applies to '--to percent'"));
+            }
+            report_recognized(
+                out,
+                value,
+                &from.into(),
+                &to.into(),
+                false,
+                output,
+                &mut warnings,
+            )?;
+        }
+        command @ (Commands::Mass { .. }
+        | Commands::Volume { .. }
+        | Commands::Area { .. }
+        | Commands::Speed { .. }
+        | Commands::Time { .. }
+        | Commands::Data { .. }
+        | Commands::Pressure { .. }
+        | Commands::Energy { .. }
+        | Commands::Power { .. }
+        | Commands::Angle { .. }
+        | Commands::Frequency { .. }
+        | Commands::Fuel { .. }) => {
+            let (value, from, to) = conversion_request(&command)?;
+            report_recognized(out, value, &from, &to, false, output, &mut warnings)?;
+        }
+        Commands::Wire { from, to, value } => {
+            let value = value_or_stdin(&value)?;
+            let converter = WireConverter::new(value, from);
+            let conversion_result = converter
+                .convert_to(to)
+                .with_context(|| format!("Failed to convert {} {} to {}", value, from, to))?;
+
+            if converter.snapped_to_gauge() {
+                warnings.push(
+                    "rounded",
+                    format!(
+                        "{} mm falls between gauges; snapped to the nearest AWG {}.",
+                        value, conversion_result
+                    ),
+                );
+            }
+
+            // Diameters need decimals to be useful; gauges are whole numbers
+            match to {
+                WireGauge::Millimeter => {
+                    let printed = format_result(conversion_result, output);
+                    print_conversion(
+                        out,
+                        format!("{} {} = {} {}", value, from, printed, to),
+                        &printed,
+                        output,
+                    )?;
+                }
+                WireGauge::Awg => {
+                    let printed = conversion_result.to_string();
+                    print_conversion(
+                        out,
+                        format!("{} {} = {} {}", value, from, printed, to),
+                        &printed,
+                        output,
+                    )?;
+                }
+            }
+        }
+        Commands::Pace { from, to, value } => {
+            let value = value_text_or_stdin(value)?;
+            let minutes_or_speed = conv::parse_pace_value(&value)?;
+            let conversion_result = PaceConverter::new(minutes_or_speed, from)
+                .convert_to(to)
+                .with_context(|| {
+                    format!("Failed to convert {} {} to {}", value.trim(), from, to)
                 })?;
 
-            println!(
-                "{} {} = {} {}",
-                format_number(value),
-                from,
-                format_number(conversion_result),
-                to
-            );
+            // Paces print as clock times, speeds as numbers
+            let printed = if conv::is_pace(&to) {
+                conv::format_pace(conversion_result)
+            } else {
+                format_result(conversion_result, output)
+            };
+            print_conversion(
+                out,
+                format!("{} {} = {} {}", value.trim(), from, printed, to),
+                &printed,
+                output,
+            )?;
         }
-        Commands::Length { from, to, value } => {
-            let conversion_result =
-                convert_length(value, from.clone(), to.clone()).with_context(|| {
+        Commands::Shoesize {
+            from,
+            to,
+            value,
+            gender,
+        } => {
+            let value = value_or_stdin(&value)?;
+            let converter = ShoeSizeConverter::new(value, from, gender);
+            let conversion_result = converter.convert_to(to).with_context(|| {
+                format!(
+                    "Failed to convert {} size {} {} to {}",
+                    gender, value, from, to
+                )
+            })?;
+
+            if converter.snapped_to_size(to)? {
+                warnings.push(
+                    "rounded",
                     format!(
-                        "Failed to convert {} {} to {}",
-                        format_number(value),
-                        from,
-                        to
-                    )
-                })?;
+                        "{} {} falls between {} sizes; snapped to the nearest {} {}.",
+                        value, from, to, to, conversion_result
+                    ),
+                );
+            }
 
-            println!(
-                "{} {} = {} {}",
-                format_number(value),
-                from,
-                format_number(conversion_result),
-                to
-            );
+            print_conversion(
+                out,
+                format!(
+                    "{} {} = {} {} ({} sizes)",
+                    value, from, conversion_result, to, gender
+                ),
+                &conversion_result.to_string(),
+                output,
+            )?;
+        }
+        Commands::Aspect { from, to, dpi } => {
+            let value = MediaValue::parse(&from)?;
+            let result = AspectConverter::new(value.clone(), dpi)
+                .convert_to(to)
+                .with_context(|| format!("Failed to convert {}", value))?;
+            print_conversion(
+                out,
+                format!("{} = {}", value, result),
+                &result.magnitude(),
+                output,
+            )?;
+        }
+        Commands::GasVolume {
+            moles,
+            temp,
+            temp_unit,
+            pressure,
+            pressure_unit,
+            to,
+        } => {
+            let volume = IdealGas::new(moles, temp, temp_unit, pressure, pressure_unit)
+                .convert_to(to)
+                .context("Failed to compute the gas volume")?;
+            let printed = format_result(volume, output);
+            print_conversion(
+                out,
+                format!(
+                    "{} mol at {} {} and {} {} = {} {}",
+                    moles, temp, temp_unit, pressure, pressure_unit, printed, to
+                ),
+                &printed,
+                output,
+            )?;
         }
-        Commands::Convert { from, to, value } => {
-            detect_and_convert(&from, &to, value)?;
+        Commands::Geodistance { from, to, unit } => {
+            let from = Coordinate::parse(&from)?;
+            let to = Coordinate::parse(&to)?;
+            let distance = GeoDistance::new(from, to)
+                .convert_to(unit)
+                .with_context(|| format!("Failed to measure {} to {}", from, to))?;
+            let printed = format_result(distance, output);
+            print_conversion(
+                out,
+                format!("{} to {} = {} {}", from, to, printed, unit),
+                &printed,
+                output,
+            )?;
+        }
+        Commands::Convert {
+            from,
+            to,
+            value,
+            positional,
+            clamp,
+            watch: true,
+        } => {
+            if from.is_some()
+                || to.is_some()
+                || !value.is_empty()
+                || !positional.is_empty()
+                || clamp
+            {
+                return Err(anyhow!(
+                    "--watch reads every request from stdin; it takes no other convert arguments."
+                ));
+            }
+            watch_stdin(std::io::stdin().lock(), out, output)?;
+        }
+        Commands::Convert {
+            from,
+            to,
+            value,
+            positional,
+            clamp,
+            watch: false,
+        } => {
+            let (from, to, value) =
+                resolve_convert_args(from, to, value.first().copied(), &positional)?;
+            detect_and_convert(out, &from, &to, value, clamp, output, &mut warnings)?;
+        }
+        Commands::Batch {
+            file,
+            max_errors,
+            from_column,
+            to,
+        } => {
+            let unit_column = from_column.zip(to).map(|(column, to)| UnitColumn {
+                from: column.get() - 1,
+                to,
+            });
+            let mut input: Box<dyn BufRead> = match &file {
+                Some(path) => Box::new(std::io::BufReader::new(
+                    std::fs::File::open(path)
+                        .with_context(|| format!("Failed to open '{}'", path.display()))?,
+                )),
+                None => Box::new(std::io::stdin().lock()),
+            };
+            let report = if starts_json_array(&mut input)? {
+                if unit_column.is_some() {
+                    return Err(anyhow!(
+                        "--from-column reads request lines; JSON requests name their own units"
+                    ));
+                }
+                run_json_batch(input, out, max_errors)?
+            } else {
+                run_batch(
+                    input,
+                    out,
+                    output,
+                    unit_column.as_ref(),
+                    max_errors,
+                    &mut warnings,
+                )?
+            };
+            // Whoever closed the pipe has all the output they wanted
+            if report.closed {
+                return Ok(());
+            }
+            warnings.write_text(&mut std::io::stderr())?;
+
+            let total = report.converted + report.failed;
+            if report.aborted {
+                return Err(max_errors_reached(
+                    report.failed,
+                    report.skipped,
+                    total + report.skipped,
+                    "request",
+                ));
+            }
+            if report.failed > 0 {
+                return Err(anyhow!(
+                    "{} of {} requests converted, {} failed",
+                    report.converted,
+                    total,
+                    report.failed
+                ));
+            }
+            eprintln!("{} of {} requests converted", report.converted, total);
+        }
+        Commands::ListUnits { category } => {
+            let categories: Vec<&str> = category
+                .iter()
+                .chain(&cli.only)
+                .map(String::as_str)
+                .collect();
+            let listings = list_units(&categories);
+            let lines = if output.format == OutputFormat::Json {
+                vec![serde_json::to_string_pretty(&listings)?]
+            } else {
+                render_unit_list(&listings)
+            };
+            write_lines(out, &lines)?;
+        }
+        Commands::Category { unit } => {
+            let category = categorize_unit(&unit)?;
+            if output.format == OutputFormat::Json {
+                let json = UnitCategory {
+                    unit: unit.trim().to_string(),
+                    category: category.to_string(),
+                };
+                writeln!(out, "{}", serde_json::to_string(&json)?)?;
+            } else {
+                writeln!(out, "{}", category)?;
+            }
+        }
+        Commands::Normalize { unit } => {
+            writeln!(out, "{}", normalize_unit(&unit)?)?;
+        }
+        Commands::CheckEquiv { a, b, tolerance } => {
+            let (equivalent, report) = check_equivalence(&a, &b, tolerance, &mut warnings)?;
+            if !equivalent {
+                return Err(anyhow!(report));
+            }
+            writeln!(out, "{}", report)?;
+        }
+        Commands::EmitSchema => {
+            let schema = serde_json::to_string_pretty(&report::schema())?;
+            if !write_lines(out, schema.lines())? {
+                return Ok(());
+            }
+        }
+        Commands::Run { file, max_errors } => {
+            let mut job_file = JobFile::load(&file)?;
+            // A job's own precision wins over --precision
+            if let Some(precision) = output.precision {
+                for job in &mut job_file.jobs {
+                    job.precision.get_or_insert(precision);
+                }
+            }
+            let report = run_jobs(&job_file, rounding, max_errors, &mut warnings);
+            // Whoever closed the pipe has all the output they wanted
+            if !write_lines(out, &report.lines)? {
+                return Ok(());
+            }
+
+            if report.aborted {
+                warnings.write_text(&mut std::io::stderr())?;
+                return Err(max_errors_reached(
+                    report.failures,
+                    job_file.jobs.len() - report.lines.len(),
+                    job_file.jobs.len(),
+                    "job",
+                ));
+            }
+
+            if report.failures > 0 {
+                warnings.write_text(&mut std::io::stderr())?;
+                return Err(anyhow!(
+                    "{} of {} jobs failed",
+                    report.failures,
+                    job_file.jobs.len()
+                ));
+            }
+        }
+        Commands::LintUnits { file } => {
+            let units = CustomUnitsFile::read(&file)?;
+            let issues = units.lint(|unit| recognize_unit(unit).is_some());
+            if issues.is_empty() {
+                writeln!(
+                    out,
+                    "{}: {} unit(s), no problems found",
+                    file.display(),
+                    units.units.len()
+                )?;
+            } else {
+                let lines: Vec<String> = issues
+                    .iter()
+                    .map(|issue| format!("{}: {}", file.display(), issue))
+                    .collect();
+                write_lines(out, &lines)?;
+                return Err(anyhow!(
+                    "{} problem(s) in '{}'",
+                    issues.len(),
+                    file.display()
+                ));
+            }
+        }
+        Commands::Completions { shell } => {
+            // Unit enums are `ValueEnum`s, so the script completes their
+            // names after --from and --to
+            clap_complete::generate(shell, &mut Cli::command(), "uniconv", out);
+        }
+        Commands::Man => {
+            // The page's DESCRIPTION is `Cli`'s long_about, examples included
+            clap_mangen::Man::new(Cli::command())
+                .render(out)
+                .context("Failed to write the man page")?;
         }
     }
 
+    warnings.write_text(&mut std::io::stderr())?;
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uniconv::convert::{convert_length, convert_temperature};
+    use uniconv::errors::ConversionError;
+    use uniconv::format::{
+        ensure_round_trips, format_scientific, format_sig_figs, group_digits, parse_printed,
+        to_fraction,
+    };
+
+    #[test]
+    fn test_canonical_output_ignores_environment() -> Result<()> {
+        let flag_sets: [&[&str]; 4] = [
+            &[],
+            &["--round", "half-up"],
+            &["--color-temp"],
+            &[
+                "--deterministic-order",
+                "symbol",
+                "--color-temp",
+                "--round",
+                "half-up",
+            ],
+        ];
+
+        let mut rendered = Vec::new();
+        for flags in flag_sets {
+            for stdout_is_terminal in [false, true] {
+                let args = ["uniconv", "--canonical"]
+                    .iter()
+                    .chain(flags)
+                    .chain(&["convert", "2.5", "c", "f"]);
+                let cli = Cli::try_parse_from(args)?;
+                let output = output_options(&cli, stdout_is_terminal);
+
+                let printed =
+                    checked_result(2.5, &Degree::Celsius, 36.5, &Degree::Fahrenheit, output, Ok)?;
+                let text = color::paint_temperature(
+                    &format!("{} °F", printed),
+                    2.5,
+                    output.color_temperature,
+                );
+                let order: Vec<String> = conv::ordered_units::<Mass>(output.unit_order)
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect();
+                rendered.push((output, text, order));
+            }
+        }
+
+        assert!(rendered.iter().all(|r| r == &rendered[0]));
+        assert_eq!(rendered[0].1, "36 °F");
+
+        // Without --canonical the same flags do change the output
+        let cli = Cli::try_parse_from(["uniconv", "--round", "half-up", "convert", "1", "c", "f"])?;
+        assert_ne!(output_options(&cli, false), rendered[0].0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_value_from_stdin() -> Result<()> {
+        assert_eq!(
+            parse_stdin_value(&read_stdin_value("25\n".as_bytes())?)?,
+            25.0
+        );
+        assert_eq!(read_stdin_value("  5:30 \n".as_bytes())?, "5:30");
+
+        let error = |input: &str| {
+            read_stdin_value(input.as_bytes())
+                .and_then(|text| parse_stdin_value(&text))
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(error("").starts_with("No value on stdin"));
+        assert!(error("\n \n").starts_with("No value on stdin"));
+        assert!(error("25 30\n").starts_with("Expected a single value"));
+        assert_eq!(error("hot\n"), "'hot' on stdin is not a number");
+        assert!(error("NaN").contains("cannot be NaN"));
+        assert!(error("1e300").contains("too large"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_value_range() {
+        let range = parse_value_range("0..100:10").unwrap();
+        assert_eq!(range.values().len(), 11);
+        assert_eq!(range.values().last(), Some(&100.0));
+
+        // The end is kept despite 0.1 not being exact in binary
+        assert_eq!(parse_value_range("0..1:0.1").unwrap().values().len(), 11);
+        assert_eq!(
+            parse_value_range("-40..-30:4").unwrap().values(),
+            vec![-40.0, -36.0, -32.0]
+        );
+
+        assert!(parse_value_range("0..100").is_err());
+        assert!(parse_value_range("0-100:10").is_err());
+        assert!(parse_value_range("0..100:-5").is_err());
+        assert!(parse_value_range("100..0:5").is_err());
+        assert!(parse_value_range("0..1e9:1")
+            .unwrap_err()
+            .contains("at most 10000"));
+    }
+
+    #[test]
+    fn test_list_units_rejects_unknown_categories() -> Result<()> {
+        assert!(Cli::try_parse_from(["uniconv", "list-units", "--category", "colour"]).is_err());
+        assert!(Cli::try_parse_from(["uniconv", "list-units", "--category", "mass"]).is_ok());
+        assert!(Cli::try_parse_from(["uniconv", "list-units", "--only", "mass,colour"]).is_err());
+
+        let cli = Cli::try_parse_from(["uniconv", "list-units", "--only", "mass,length"])?;
+        assert_eq!(cli.only, ["mass", "length"]);
+        let cli = Cli::try_parse_from(["uniconv", "list-units", "--category", "time,data"])?;
+        assert!(
+            matches!(cli.command, Commands::ListUnits { category } if category == ["time", "data"])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_positional_conflicts_with_flags() {
+        let result = resolve_convert_args(
+            Some("c".into()),
+            None,
+            None,
+            &["25", "c", "f"].map(String::from),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_precision_flag() -> Result<()> {
+        let cli = Cli::try_parse_from([
+            "uniconv",
+            "length",
+            "--from",
+            "kilometer",
+            "--to",
+            "miles",
+            "--value",
+            "5",
+            "--precision",
+            "3",
+        ])?;
+        let output = output_options(&cli, false);
+        let miles = convert_length(5.0, Length::Kilometer, Length::Miles)?;
+        assert_eq!(
+            checked_result(5.0, &Length::Kilometer, miles, &Length::Miles, output, Ok)?,
+            "3.107"
+        );
+
+        let with = |precision| OutputOptions {
+            precision,
+            ..output
+        };
+        assert_eq!(format_result(miles, with(Some(0))), "3");
+        assert_eq!(format_result(miles, with(Some(10))), "3.1068559612");
+        assert_eq!(format_result(0.5, with(Some(0))), "0");
+
+        // Without --precision each category has its own default, with
+        // trailing zeros dropped
+        assert_eq!(format_result(miles, with(None)), "3.106856");
+        let temperature = with(None).in_category("temperature");
+        assert_eq!(format_result(97.7, temperature), "97.7");
+        assert_eq!(format_result(212.0, temperature), "212");
+        assert_eq!(format_value(36.5, temperature), "36.5");
+        assert_eq!(format_value(1609.344, with(Some(2))), "1609.34");
+
+        assert!(
+            Cli::try_parse_from(["uniconv", "--precision", "16", "convert", "1", "c", "f"])
+                .is_err()
+        );
+        // --canonical pins whole numbers
+        let cli = Cli::try_parse_from([
+            "uniconv",
+            "--canonical",
+            "--precision",
+            "3",
+            "convert",
+            "1",
+            "c",
+            "f",
+        ])?;
+        assert_eq!(output_options(&cli, false).precision, Some(0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sig_figs() -> Result<()> {
+        assert_eq!(format_sig_figs(1234.5, 2), "1200");
+        assert_eq!(format_sig_figs(0.00098765, 3), "0.000988");
+        assert_eq!(format_sig_figs(0.000621371, 3), "0.000621");
+        assert_eq!(format_sig_figs(3.10686, 4), "3.107");
+        assert_eq!(format_sig_figs(-1234.5, 2), "-1200");
+        assert_eq!(format_sig_figs(9.99, 2), "10");
+        assert_eq!(format_sig_figs(0.0, 3), "0");
+        assert_eq!(format_sig_figs(-0.0, 3), "0");
+        assert_eq!(format_sig_figs(3.0857e25, 3), "30900000000000000000000000");
+        assert_eq!(format_sig_figs(1.5e-12, 2), "0.0000000000015");
+
+        let cli = Cli::try_parse_from(["uniconv", "--sig-figs", "3", "convert", "1", "m", "mi"])?;
+        let output = output_options(&cli, false);
+        let miles = convert_length(1.0, Length::Meter, Length::Miles)?;
+        assert_eq!(format_result(miles, output), "0.000621");
+
+        // Decimals and significant figures are two answers to one question
+        assert!(Cli::try_parse_from([
+            "uniconv",
+            "--sig-figs",
+            "3",
+            "--precision",
+            "2",
+            "convert",
+            "1",
+            "m",
+            "mi"
+        ])
+        .is_err());
+        assert!(
+            Cli::try_parse_from(["uniconv", "--sig-figs", "0", "convert", "1", "m", "mi"]).is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_scientific_notation() -> Result<()> {
+        assert_eq!(format_scientific(0.000621371, None), "6.21371e-4");
+        assert_eq!(format_scientific(149597870.7, None), "1.49598e8");
+        assert_eq!(format_scientific(1000.0, None), "1e3");
+        assert_eq!(format_scientific(-0.0, None), "0e0");
+        assert_eq!(format_scientific(-2.5e-30, None), "-2.5e-30");
+        assert_eq!(format_scientific(149597870.7, Some(2)), "1.50e8");
+        assert_eq!(format_scientific(f64::INFINITY, None), "inf");
+
+        let cli = Cli::try_parse_from(["uniconv", "--scientific", "convert", "1", "m", "mi"])?;
+        let output = output_options(&cli, false);
+        let miles = convert_length(1.0, Length::Meter, Length::Miles)?;
+        assert_eq!(format_result(miles, output), "6.21371e-4");
+        // --precision and --sig-figs set the mantissa digits
+        let with = |precision, sig_figs| OutputOptions {
+            precision,
+            sig_figs,
+            ..output
+        };
+        assert_eq!(format_result(miles, with(Some(1), None)), "6.2e-4");
+        assert_eq!(format_result(miles, with(None, Some(3))), "6.21e-4");
+        // The printed result still parses, for --fail-on-precision-loss
+        assert!((printed_value("6.21371e-4", 0.0, output) - 0.000621371).abs() < 1e-15);
+
+        // NaN and infinity never get as far as formatting
+        assert!(convert_length(f64::NAN, Length::Meter, Length::Miles).is_err());
+        assert!(convert_length(f64::INFINITY, Length::Meter, Length::Miles).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_fraction_output() -> Result<()> {
+        let inches = convert_length(2.54, Length::Centimeter, Length::Inch)?;
+        assert_eq!(to_fraction(inches, 16), "1");
+        assert_eq!(to_fraction(3.5, 16), "3 1/2");
+        // 2.1 in is 33.6 sixteenths, nearest 34/16 = 2 1/8
+        assert_eq!(to_fraction(2.1, 16), "2 1/8");
+        assert_eq!(to_fraction(0.0625, 16), "1/16");
+        assert_eq!(to_fraction(2.1, 4), "2");
+        assert_eq!(to_fraction(-1.75, 16), "-1 3/4");
+        assert_eq!(to_fraction(-0.01, 16), "0");
+
+        assert_eq!(parse_printed("3 1/2"), Some(3.5));
+        assert_eq!(parse_printed("-1/16"), Some(-0.0625));
+        assert_eq!(parse_printed("74"), Some(74.0));
+        assert_eq!(parse_printed("1/0 in"), None);
+
+        // Only inch and foot results become fractions
+        let cli = Cli::try_parse_from(["uniconv", "--fraction", "convert", "190", "cm", "in"])?;
+        let output = output_options(&cli, false);
+        let inches = convert_length(190.0, Length::Centimeter, Length::Inch)?;
+        assert_eq!(
+            checked_result(
+                190.0,
+                &Length::Centimeter,
+                inches,
+                &Length::Inch,
+                output,
+                Ok
+            )?,
+            "74 13/16"
+        );
+        assert_eq!(
+            checked_result(1.0, &Length::Inch, 2.54, &Length::Centimeter, output, Ok)?,
+            "2.54"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_group_digits() -> Result<()> {
+        assert_eq!(group_digits("1000000"), "1,000,000");
+        assert_eq!(group_digits("160934.4"), "160,934.4");
+        assert_eq!(group_digits("-1234.5678"), "-1,234.5678");
+        assert_eq!(group_digits("999"), "999");
+        assert_eq!(group_digits("-100"), "-100");
+        assert_eq!(group_digits("0.00012"), "0.00012");
+        assert_eq!(group_digits("1.60934e5"), "1.60934e5");
+
+        // Composes with --precision, and still reads back for the precision gate
+        let cli = Cli::try_parse_from([
+            "uniconv",
+            "--group-digits",
+            "--precision",
+            "1",
+            "convert",
+            "1",
+            "mi",
+            "cm",
+        ])?;
+        let output = output_options(&cli, false);
+        let centimeters = convert_length(1.0, Length::Miles, Length::Centimeter)?;
+        assert_eq!(format_result(centimeters, output), "160,934.4");
+        assert_eq!(printed_value("160,934.4", 0.0, output), 160934.4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decimal_comma() -> Result<()> {
+        let cli = Cli::try_parse_from([
+            "uniconv",
+            "--decimal-separator",
+            "comma",
+            "--precision",
+            "3",
+            "--fail-on-precision-loss",
+            "convert",
+            "1",
+            "nmi",
+            "km",
+        ])?;
+        let output = output_options(&cli, false);
+        let km = convert_length(1.0, Length::NauticalMile, Length::Kilometer)?;
+        assert_eq!(
+            checked_result(
+                1.0,
+                &Length::NauticalMile,
+                km,
+                &Length::Kilometer,
+                output,
+                |km| { convert_length(km, Length::Kilometer, Length::NauticalMile) }
+            )?,
+            "1,852"
+        );
+
+        let grouped = OutputOptions {
+            group_digits: true,
+            precision: Some(1),
+            ..output
+        };
+        let centimeters = convert_length(1.0, Length::Miles, Length::Centimeter)?;
+        assert_eq!(format_result(centimeters, grouped), "160.934,4");
+        assert_eq!(printed_value("160.934,4", 0.0, grouped), 160934.4);
+
+        // Machine output keeps `.`, whatever the text output uses
+        let line =
+            output::machine_line(OutputFormat::Json, 1.0, &"nmi", km, &"km", &Warnings::new())?;
+        assert_eq!(
+            line.as_deref(),
+            Some(r#"{"value":1.0,"from":"nmi","to":"km","result":1.852,"warnings":[]}"#)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_precision_loss_gate() -> Result<()> {
+        // 1 in is 2.54 cm, printed as 3 cm, which is 1.18 in: lossy
+        let printed = format_number(2.54, Rounding::HalfEven);
+        let err = ensure_round_trips(1.0, &printed, |cm| {
+            convert_length(cm, Length::Centimeter, Length::Inch)
+        })
+        .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ConversionError>(),
+            Some(ConversionError::PrecisionLoss(_))
+        ));
+
+        // 100 cm is exactly 1 m, which converts back to 100 cm: lossless
+        let printed = format_number(
+            convert_length(100.0, Length::Centimeter, Length::Meter)?,
+            Rounding::HalfEven,
+        );
+        ensure_round_trips(100.0, &printed, |m| {
+            convert_length(m, Length::Meter, Length::Centimeter)
+        })?;
+
+        // Temperatures round-trip through the offset formulas too
+        let printed = format_number(212.0, Rounding::HalfEven);
+        ensure_round_trips(100.0, &printed, |f| {
+            convert_temperature(
+                f,
+                Degree::Fahrenheit,
+                Degree::Celsius,
+                false,
+                &mut Warnings::new(),
+            )
+        })?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_color_temp_disabled_keeps_the_value() -> Result<()> {
+        let output = OutputOptions {
+            rounding: Rounding::HalfEven,
+            fail_on_precision_loss: true,
+            unit_order: UnitOrder::Declaration,
+            color_temperature: color::color_enabled(true, ColorChoice::Never, false, true),
+            format: OutputFormat::Text,
+            csv_header: false,
+            precision: None,
+            sig_figs: None,
+            scientific: false,
+            fraction: None,
+            raw: false,
+            explain: false,
+            group_digits: false,
+            decimal_separator: DecimalSeparator::Dot,
+            unit_names: UnitNames::Short,
+            category: None,
+        };
+        assert!(!output.color_temperature);
+
+        let result = convert_temperature(
+            25.0,
+            Degree::Celsius,
+            Degree::Fahrenheit,
+            false,
+            &mut Warnings::new(),
+        )?;
+        let printed = checked_result(
+            25.0,
+            &Degree::Celsius,
+            result,
+            &Degree::Fahrenheit,
+            output,
+            |f| {
+                convert_temperature(
+                    f,
+                    Degree::Fahrenheit,
+                    Degree::Celsius,
+                    false,
+                    &mut Warnings::new(),
+                )
+            },
+        )?;
+        assert_eq!(printed, "77");
+        assert_eq!(
+            color::paint_temperature(&format!("{} {}", printed, Degree::Fahrenheit), 25.0, false),
+            "77 °F"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_mass_units_are_recognized() -> Result<()> {
+        assert_eq!(normalize_unit("Pounds")?, "pound");
+        assert_eq!(normalize_unit("kilos")?, "kilogram");
+        assert_eq!(normalize_unit("ounces")?, "ounce");
+
+        let mut warnings = Warnings::new();
+        assert!(check_equivalence("1kg", "1000g", 1e-9, &mut warnings)?.0);
+        assert!(check_equivalence("1st", "14lb", 1e-9, &mut warnings)?.0);
+
+        assert_eq!(categorize_unit("lb")?, "mass");
+        let output = OutputOptions {
+            rounding: Rounding::HalfEven,
+            fail_on_precision_loss: false,
+            unit_order: UnitOrder::Declaration,
+            color_temperature: false,
+            format: OutputFormat::Text,
+            csv_header: false,
+            precision: None,
+            sig_figs: None,
+            scientific: false,
+            fraction: None,
+            raw: false,
+            explain: false,
+            group_digits: false,
+            decimal_separator: DecimalSeparator::Dot,
+            unit_names: UnitNames::Short,
+            category: None,
+        };
+        assert!(detect_and_convert(
+            &mut std::io::sink(),
+            "kg",
+            "cm",
+            1.0,
+            false,
+            output,
+            &mut warnings
+        )
+        .unwrap_err()
+        .to_string()
+        .contains("(mass vs length)"));
+        Ok(())
+    }
+}
@@ -0,0 +1,790 @@
+use crate::conv::{
+    in_unit_order, AngleConverter, AreaConverter, DataConverter, EnergyConverter,
+    FrequencyConverter, FuelEconomyConverter, LengthConverter, MassConverter, PowerConverter,
+    PressureConverter, SpeedConverter, TemperatureConverter, TimeConverter, UnitOrder,
+    VolumeConverter,
+};
+use crate::errors::Warnings;
+use crate::format::{format_decimals, Rounding};
+use crate::report::{ConversionOutput, UnitListing};
+use crate::units::{recognize_unit, RecognizedUnit, UNIT_CATEGORIES};
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize, Serializer};
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::io::{ErrorKind, Write};
+
+/// Column names printed by `--csv-header`
+pub const CSV_HEADER: &str = "from_value,from_unit,to_value,to_unit";
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    /// `188 cm = 74 in`
+    Text,
+    /// `{"value":188.0,"from":"cm","to":"in","result":74.01574803149606,"warnings":[]}`
+    Json,
+    /// `188,cm,74.01574803149606,in`
+    Csv,
+    /// Every unit of the category, one aligned `unit | value` row each
+    Table,
+}
+
+/// The decimal mark in text results. JSON and CSV always use `.`.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum DecimalSeparator {
+    /// `3.107`, grouped as `1,000.5`
+    Dot,
+    /// `3,107`, grouped as `1.000,5`
+    Comma,
+}
+
+impl DecimalSeparator {
+    /// Rewrites a number formatted the Rust way (`.` decimals, `,` groups)
+    /// with this decimal mark, swapping the group separator to match
+    pub fn localize(self, number: &str) -> String {
+        match self {
+            DecimalSeparator::Dot => number.to_string(),
+            DecimalSeparator::Comma => number
+                .chars()
+                .map(|c| match c {
+                    '.' => ',',
+                    ',' => '.',
+                    other => other,
+                })
+                .collect(),
+        }
+    }
+
+    /// Undoes `localize` and drops the group separators, leaving a number
+    /// Rust can parse
+    pub fn delocalize(self, number: &str) -> String {
+        let (decimal, group) = match self {
+            DecimalSeparator::Dot => ('.', ','),
+            DecimalSeparator::Comma => (',', '.'),
+        };
+        number
+            .chars()
+            .filter(|c| *c != group)
+            .map(|c| if c == decimal { '.' } else { c })
+            .collect()
+    }
+}
+
+/// How units are written in text output
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum UnitNames {
+    /// `2 cm = 0.79 in`
+    Short,
+    /// `2 centimeters = 0.79 inches`
+    Long,
+}
+
+/// A unit that can be printed by its symbol (`Display`) or its full name
+pub trait UnitLabel: Display {
+    /// The full name, e.g. `centimeter`; the same name the commands accept
+    fn long_name(&self) -> String;
+}
+
+impl<T: ValueEnum + Display> UnitLabel for T {
+    fn long_name(&self) -> String {
+        self.to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_else(|| self.to_string())
+    }
+}
+
+impl UnitLabel for RecognizedUnit {
+    fn long_name(&self) -> String {
+        self.canonical_name()
+    }
+}
+
+/// `(singular, plural)` pairs the suffix rules get wrong. `miles` is listed
+/// because it is also the long name of `Length::Miles`.
+const IRREGULAR_PLURALS: &[(&str, &str)] =
+    &[("foot", "feet"), ("inch", "inches"), ("mile", "miles")];
+
+/// Names that read the same for any amount (`2 celsius`, `2 hertz`)
+const INVARIANT_NAMES: &[&str] = &[
+    "celsius",
+    "fahrenheit",
+    "delisle",
+    "newton",
+    "hertz",
+    "horsepower",
+    "psi",
+    "btu",
+    "rpm",
+    "mpg",
+    "hg",
+];
+
+/// Trailing words that qualify a unit rather than name it (`gallon-us`)
+const QUALIFIERS: &[&str] = &["us", "imperial", "mechanical", "metric"];
+
+/// The unit name for `value` of it: singular for exactly one, plural
+/// otherwise (`1 inch`, `0.5 inches`, `2 inches`). In compound names the
+/// counted word is pluralized: `cubic-meters`, `meters-per-second`,
+/// `gallons-us`.
+pub fn pluralize(name: &str, value: f64) -> String {
+    let mut words: Vec<&str> = name.split('-').collect();
+    let counted = match words.iter().position(|word| *word == "per") {
+        Some(per) => per.saturating_sub(1),
+        None => {
+            let last = words.len() - 1;
+            if last > 0 && QUALIFIERS.contains(&words[last]) {
+                last - 1
+            } else {
+                last
+            }
+        }
+    };
+
+    let word = singular(words[counted]);
+    let inflected = if value.abs() == 1.0 {
+        word.to_string()
+    } else {
+        plural(word)
+    };
+    words[counted] = &inflected;
+    words.join("-")
+}
+
+fn singular(word: &str) -> &str {
+    IRREGULAR_PLURALS
+        .iter()
+        .find(|(_, plural)| *plural == word)
+        .map_or(word, |(singular, _)| singular)
+}
+
+fn plural(word: &str) -> String {
+    if let Some((_, plural)) = IRREGULAR_PLURALS
+        .iter()
+        .find(|(singular, _)| *singular == word)
+    {
+        return plural.to_string();
+    }
+    if INVARIANT_NAMES.iter().any(|name| word.ends_with(name)) {
+        return word.to_string();
+    }
+    if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|end| word.ends_with(end))
+    {
+        format!("{}es", word)
+    } else {
+        format!("{}s", word)
+    }
+}
+
+/// One conversion in a machine-readable format, with the result unrounded.
+/// JSON carries the conversion's `warnings`; CSV has no room for them.
+/// Text output is `None`: it is rounded and checked by the caller.
+pub fn machine_line(
+    format: OutputFormat,
+    value: f64,
+    from: &dyn Display,
+    result: f64,
+    to: &dyn Display,
+    warnings: &Warnings,
+) -> Result<Option<String>> {
+    Ok(match format {
+        OutputFormat::Text | OutputFormat::Table => None,
+        OutputFormat::Json => Some(serde_json::to_string(&ConversionOutput {
+            value,
+            from: from.to_string(),
+            to: to.to_string(),
+            result,
+            warnings: warnings.clone(),
+        })?),
+        OutputFormat::Csv => Some(csv_record(value, from, result, to)),
+    })
+}
+
+/// The field of a results struct that holds `unit`: its name with
+/// underscores, `nautical-mile` → `nautical_mile`
+pub fn results_field(unit: &dyn UnitLabel) -> String {
+    unit.long_name().replace('-', "_")
+}
+
+/// `--all` as JSON: `results` (a category's results struct, or a map keyed
+/// the same way) serialized as one object, with its fields in the order of
+/// `rows`
+pub fn all_results_json<R: Serialize, U: UnitLabel>(
+    results: &R,
+    rows: &[(U, f64)],
+) -> Result<String> {
+    let serde_json::Value::Object(fields) = serde_json::to_value(results)? else {
+        return Err(anyhow!("results must serialize as a JSON object"));
+    };
+    let ordered = rows.iter().filter_map(|(unit, _)| {
+        let field = results_field(unit);
+        let value = fields.get(&field)?;
+        Some((field, value))
+    });
+
+    let mut json = Vec::new();
+    serde_json::Serializer::new(&mut json).collect_map(ordered)?;
+    Ok(String::from_utf8(json)?)
+}
+
+/// One row of `--format table`: unit name, value, unit symbol
+pub struct TableRow {
+    pub name: String,
+    pub value: f64,
+    pub symbol: String,
+}
+
+/// Renders rows under a `unit | value` header, names padded to one width and
+/// values right-aligned, each with `decimals` digits so their decimal points
+/// line up
+pub fn render_table(rows: &[TableRow], decimals: u32, rounding: Rounding) -> String {
+    let values: Vec<String> = rows
+        .iter()
+        .map(|row| format_decimals(row.value, decimals, rounding))
+        .collect();
+    let name_width = rows
+        .iter()
+        .map(|row| row.name.chars().count())
+        .chain(std::iter::once("unit".len()))
+        .max()
+        .unwrap_or_default();
+    let value_width = values
+        .iter()
+        .map(String::len)
+        .chain(std::iter::once("value".len()))
+        .max()
+        .unwrap_or_default();
+
+    let mut table = format!(
+        "{:<name_width$} | {:>value_width$}\n{}-+-{}",
+        "unit",
+        "value",
+        "-".repeat(name_width),
+        "-".repeat(value_width)
+    );
+    for (row, value) in rows.iter().zip(&values) {
+        table.push_str(&format!(
+            "\n{:<name_width$} | {:>value_width$} {}",
+            row.name, value, row.symbol
+        ));
+    }
+    table
+}
+
+/// `from_value,from_unit,to_value,to_unit`, with values printed at full
+/// precision so they parse back to the same `f64`
+pub fn csv_record(value: f64, from: &dyn Display, result: f64, to: &dyn Display) -> String {
+    format!(
+        "{},{},{},{}",
+        value,
+        csv_field(&from.to_string()),
+        result,
+        csv_field(&to.to_string())
+    )
+}
+
+/// Quotes a field unless it is plain ASCII, so symbols like `°C` survive
+/// spreadsheets that guess the encoding. Quotes inside are doubled.
+fn csv_field(field: &str) -> Cow<'_, str> {
+    let plain = !field.is_empty()
+        && field
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "./-_%".contains(c));
+
+    if plain {
+        Cow::Borrowed(field)
+    } else {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    }
+}
+
+/// Writes one line per item, returning `false` if the reader went away first
+/// (e.g. `uniconv run jobs.toml | head -1`). Rust ignores SIGPIPE, so a
+/// closed pipe shows up as a `BrokenPipe` write error rather than killing the
+/// process; `println!` would panic on it.
+/// Like `write_lines` for a single line, but leaves flushing to the caller
+/// so a buffered writer keeps its buffer
+pub fn write_line(out: &mut (impl Write + ?Sized), line: &str) -> std::io::Result<bool> {
+    match writeln!(out, "{}", line) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn write_lines<W: Write + ?Sized, T: Display>(
+    out: &mut W,
+    lines: impl IntoIterator<Item = T>,
+) -> std::io::Result<bool> {
+    let written = lines
+        .into_iter()
+        .try_for_each(|line| writeln!(out, "{}", line))
+        .and_then(|()| out.flush());
+
+    match written {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Every unit of `unit`'s category with `value` converted into it, taken
+/// from the category's `convert_to_all`
+pub fn table_rows(value: f64, unit: &RecognizedUnit, order: UnitOrder) -> Result<Vec<TableRow>> {
+    fn rows<U: ValueEnum + Display + PartialEq>(
+        results: Vec<(U, f64)>,
+        order: UnitOrder,
+    ) -> Vec<TableRow> {
+        in_unit_order(results, order)
+            .into_iter()
+            .map(|(unit, value)| TableRow {
+                name: unit
+                    .to_possible_value()
+                    .map(|name| name.get_name().to_string())
+                    .unwrap_or_default(),
+                value,
+                symbol: unit.to_string(),
+            })
+            .collect()
+    }
+
+    Ok(match unit {
+        RecognizedUnit::Temperature(unit) => rows(
+            TemperatureConverter::new(value, *unit)
+                .convert_to_all()?
+                .rows(),
+            order,
+        ),
+        RecognizedUnit::Length(unit) => rows(
+            LengthConverter::new(value, *unit).convert_to_all()?.rows(),
+            order,
+        ),
+        RecognizedUnit::Mass(unit) => rows(
+            MassConverter::new(value, *unit).convert_to_all()?.rows(),
+            order,
+        ),
+        RecognizedUnit::Volume(unit) => rows(
+            VolumeConverter::new(value, *unit).convert_to_all()?.rows(),
+            order,
+        ),
+        RecognizedUnit::Area(unit) => rows(
+            AreaConverter::new(value, *unit).convert_to_all()?.rows(),
+            order,
+        ),
+        RecognizedUnit::Speed(unit) => rows(
+            SpeedConverter::new(value, *unit).convert_to_all()?.rows(),
+            order,
+        ),
+        RecognizedUnit::Time(unit) => rows(
+            TimeConverter::new(value, *unit).convert_to_all()?.rows(),
+            order,
+        ),
+        RecognizedUnit::Data(unit) => rows(
+            DataConverter::new(value, *unit).convert_to_all()?.rows(),
+            order,
+        ),
+        RecognizedUnit::Pressure(unit) => rows(
+            PressureConverter::new(value, *unit)
+                .convert_to_all()?
+                .rows(),
+            order,
+        ),
+        RecognizedUnit::Energy(unit) => rows(
+            EnergyConverter::new(value, *unit).convert_to_all()?.rows(),
+            order,
+        ),
+        RecognizedUnit::Power(unit) => rows(
+            PowerConverter::new(value, *unit).convert_to_all()?.rows(),
+            order,
+        ),
+        RecognizedUnit::Angle(unit) => rows(
+            AngleConverter::new(value, *unit).convert_to_all()?.rows(),
+            order,
+        ),
+        RecognizedUnit::Frequency(unit) => rows(
+            FrequencyConverter::new(value, *unit)
+                .convert_to_all()?
+                .rows(),
+            order,
+        ),
+        RecognizedUnit::Fuel(unit) => rows(
+            FuelEconomyConverter::new(value, *unit)
+                .convert_to_all()?
+                .rows(),
+            order,
+        ),
+    })
+}
+
+/// The units of every category (or just those in `categories`, when it
+/// isn't empty), each with the spellings from `UNIT_CATEGORIES` that select
+/// it, in listing order
+pub fn list_units(categories: &[&str]) -> Vec<UnitListing> {
+    let mut listings: Vec<UnitListing> = Vec::new();
+
+    for (name, spellings) in UNIT_CATEGORIES {
+        if !categories.is_empty() && !categories.contains(name) {
+            continue;
+        }
+
+        let first = listings.len();
+        for spelling in *spellings {
+            let Some(unit) = recognize_unit(spelling) else {
+                continue;
+            };
+            let canonical = unit.canonical_name();
+            match listings[first..]
+                .iter_mut()
+                .find(|listing| listing.name == canonical)
+            {
+                Some(listing) => listing.aliases.push(spelling.to_string()),
+                None => listings.push(UnitListing {
+                    category: name.to_string(),
+                    name: canonical,
+                    symbol: unit.to_string(),
+                    aliases: vec![spelling.to_string()],
+                }),
+            }
+        }
+    }
+
+    listings
+}
+
+/// `list-units` as text: a heading per category, then one aligned
+/// `name  symbol  aliases` row per unit
+pub fn render_unit_list(listings: &[UnitListing]) -> Vec<String> {
+    let name_width = listings
+        .iter()
+        .map(|listing| listing.name.chars().count())
+        .max()
+        .unwrap_or_default();
+    let symbol_width = listings
+        .iter()
+        .map(|listing| listing.symbol.chars().count())
+        .max()
+        .unwrap_or_default();
+
+    let mut lines = Vec::new();
+    for (index, listing) in listings.iter().enumerate() {
+        if index == 0 || listings[index - 1].category != listing.category {
+            if index > 0 {
+                lines.push(String::new());
+            }
+            lines.push(listing.category.clone());
+        }
+        lines.push(format!(
+            "  {:<name_width$}  {:<symbol_width$}  {}",
+            listing.name,
+            listing.symbol,
+            listing.aliases.join(", ")
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conv::{
+        Angle, Area, Data, Degree, Energy, Frequency, FuelEconomy, Length, Mass, Power, Pressure,
+        Speed, Time, Volume,
+    };
+    use crate::units::{categorize_unit, require_unit};
+
+    #[test]
+    fn test_table_has_a_row_per_unit() -> Result<()> {
+        let count = |unit: &str| -> Result<usize> {
+            Ok(table_rows(1.0, &require_unit(unit)?, UnitOrder::Declaration)?.len())
+        };
+
+        // Gas mark only covers oven temperatures, so the table leaves it out
+        assert_eq!(count("c")?, Degree::value_variants().len() - 1);
+        assert_eq!(count("cm")?, Length::value_variants().len());
+        assert_eq!(count("kg")?, Mass::value_variants().len());
+        assert_eq!(count("l")?, Volume::value_variants().len());
+        assert_eq!(count("m2")?, Area::value_variants().len());
+        assert_eq!(count("mph")?, Speed::value_variants().len());
+        assert_eq!(count("h")?, Time::value_variants().len());
+        assert_eq!(count("gb")?, Data::value_variants().len());
+        assert_eq!(count("bar")?, Pressure::value_variants().len());
+        assert_eq!(count("kj")?, Energy::value_variants().len());
+        assert_eq!(count("kw")?, Power::value_variants().len());
+        assert_eq!(count("rad")?, Angle::value_variants().len());
+        assert_eq!(count("hz")?, Frequency::value_variants().len());
+        assert_eq!(count("mpg")?, FuelEconomy::value_variants().len());
+
+        let table = render_table(
+            &table_rows(25.0, &require_unit("c")?, UnitOrder::Declaration)?,
+            2,
+            Rounding::HalfEven,
+        );
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 2 + 5);
+        assert!(lines[3].starts_with("fahrenheit |"));
+        assert!(lines[3].ends_with(" 77.00 °F"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_listed_units_parse() {
+        let listings = list_units(&[]);
+        // Every alias selects the unit it is listed under, in its category
+        for listing in &listings {
+            for alias in &listing.aliases {
+                assert_eq!(
+                    categorize_unit(alias).ok(),
+                    Some(listing.category.as_str()),
+                    "'{}'",
+                    alias
+                );
+                let unit = recognize_unit(alias).expect("listed aliases parse");
+                assert_eq!(unit.canonical_name(), listing.name, "'{}'", alias);
+                assert_eq!(unit.to_string(), listing.symbol, "'{}'", alias);
+            }
+        }
+        // No spelling was dropped on the way
+        let listed: usize = listings.iter().map(|listing| listing.aliases.len()).sum();
+        let known: usize = UNIT_CATEGORIES.iter().map(|(_, units)| units.len()).sum();
+        assert_eq!(listed, known);
+
+        let temperatures = list_units(&["temperature"]);
+        assert!(temperatures
+            .iter()
+            .all(|listing| listing.category == "temperature"));
+        assert_eq!(temperatures[0].name, "celsius");
+        assert_eq!(temperatures[0].aliases, ["celsius", "c", "°c"]);
+        assert_eq!(
+            render_unit_list(&temperatures)[..2],
+            ["temperature", "  celsius     °C        celsius, c, °c"]
+        );
+
+        // Several categories keep the listing order, not the order asked for
+        let categories: Vec<String> = list_units(&["fuel", "mass"])
+            .into_iter()
+            .map(|listing| listing.category)
+            .collect();
+        assert!(categories.starts_with(&["mass".to_string()]));
+        assert!(categories.ends_with(&["fuel".to_string()]));
+        assert!(categories
+            .iter()
+            .all(|category| category == "mass" || category == "fuel"));
+    }
+
+    /// Accepts `capacity` bytes, then fails like a pipe whose reader exited
+    struct ClosedAfter {
+        written: Vec<u8>,
+        capacity: usize,
+    }
+
+    impl Write for ClosedAfter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.written.len() >= self.capacity {
+                return Err(ErrorKind::BrokenPipe.into());
+            }
+            let n = buf.len().min(self.capacity - self.written.len());
+            self.written.extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_write_lines_stops_quietly_on_closed_pipe() -> Result<()> {
+        let mut out = ClosedAfter {
+            written: Vec::new(),
+            capacity: 8,
+        };
+        let lines = (0..10_000).map(|i| format!("line {}", i));
+
+        assert!(!write_lines(&mut out, lines)?);
+        assert_eq!(out.written, b"line 0\nl");
+
+        let mut open = Vec::new();
+        assert!(write_lines(&mut open, ["a", "b"])?);
+        assert_eq!(open, b"a\nb\n");
+
+        // Other write errors still surface
+        struct Full;
+        impl Write for Full {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(ErrorKind::StorageFull.into())
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        assert!(write_lines(&mut Full, ["a"]).is_err());
+        Ok(())
+    }
+
+    /// Splits one CSV record, undoing the quoting `csv_field` applies
+    fn parse_record(line: &str) -> Vec<String> {
+        let mut fields = vec![String::new()];
+        let mut quoted = false;
+        let mut chars = line.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    fields.last_mut().unwrap().push('"');
+                }
+                '"' => quoted = !quoted,
+                ',' if !quoted => fields.push(String::new()),
+                _ => fields.last_mut().unwrap().push(c),
+            }
+        }
+        fields
+    }
+
+    #[test]
+    fn test_decimal_separator() {
+        assert_eq!(DecimalSeparator::Comma.localize("1.852"), "1,852");
+        assert_eq!(DecimalSeparator::Comma.localize("160,934.4"), "160.934,4");
+        assert_eq!(
+            DecimalSeparator::Comma.localize("-6.21371e-4"),
+            "-6,21371e-4"
+        );
+        assert_eq!(DecimalSeparator::Dot.localize("160,934.4"), "160,934.4");
+
+        assert_eq!(DecimalSeparator::Comma.delocalize("160.934,4"), "160934.4");
+        assert_eq!(DecimalSeparator::Dot.delocalize("160,934.4"), "160934.4");
+        assert_eq!(DecimalSeparator::Comma.delocalize("3 1/2"), "3 1/2");
+    }
+
+    #[test]
+    fn test_pluralize_singular_and_plural() {
+        assert_eq!(pluralize("centimeter", 1.0), "centimeter");
+        assert_eq!(pluralize("centimeter", 2.0), "centimeters");
+        assert_eq!(pluralize("centimeter", 0.5), "centimeters");
+        assert_eq!(pluralize("centimeter", 0.0), "centimeters");
+        assert_eq!(pluralize("centimeter", -1.0), "centimeter");
+        assert_eq!(pluralize("cubic-meter", 3.0), "cubic-meters");
+        assert_eq!(pluralize("meter-per-second", 3.0), "meters-per-second");
+        assert_eq!(pluralize("gallon-us", 3.0), "gallons-us");
+        assert_eq!(pluralize("celsius", 3.0), "celsius");
+        assert_eq!(pluralize("kilohertz", 3.0), "kilohertz");
+    }
+
+    #[test]
+    fn test_pluralize_irregulars() {
+        assert_eq!(pluralize("inch", 1.0), "inch");
+        assert_eq!(pluralize("inch", 2.0), "inches");
+        assert_eq!(pluralize("foot", 2.0), "feet");
+        assert_eq!(pluralize("square-foot", 2.0), "square-feet");
+        // `Length::Miles` is already plural
+        assert_eq!(pluralize("miles", 1.0), "mile");
+        assert_eq!(pluralize("miles", 26.2), "miles");
+    }
+
+    #[test]
+    fn test_csv_round_trips() {
+        let result = 188.0 / 2.54;
+        let line = csv_record(188.0, &"cm", result, &"in");
+        assert_eq!(line, "188,cm,74.01574803149606,in");
+
+        let fields = parse_record(&line);
+        assert_eq!(fields.len(), 4);
+        assert_eq!(fields[0].parse::<f64>().unwrap(), 188.0);
+        assert_eq!(fields[2].parse::<f64>().unwrap(), result);
+        assert_eq!(parse_record(CSV_HEADER).len(), 4);
+    }
+
+    #[test]
+    fn test_csv_quotes_special_units() {
+        let line = csv_record(100.0, &"°C", 212.0, &"°F");
+        assert_eq!(line, "100,\"°C\",212,\"°F\"");
+        assert_eq!(parse_record(&line), ["100", "°C", "212", "°F"]);
+
+        assert_eq!(csv_field("km/h"), "km/h");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("5\" pipe"), "\"5\"\" pipe\"");
+        assert_eq!(parse_record(&csv_field("5\" pipe")), ["5\" pipe"]);
+    }
+
+    #[test]
+    fn test_table_alignment() {
+        let row = |name: &str, value: f64, symbol: &str| TableRow {
+            name: name.to_string(),
+            value,
+            symbol: symbol.to_string(),
+        };
+        let rows = [
+            row("celsius", 25.0, "°C"),
+            row("fahrenheit", 77.0, "°F"),
+            row("kelvin", 298.15, "K"),
+        ];
+
+        assert_eq!(
+            render_table(&rows, 6, Rounding::HalfEven),
+            "unit       |      value\n\
+             -----------+-----------\n\
+             celsius    |  25.000000 °C\n\
+             fahrenheit |  77.000000 °F\n\
+             kelvin     | 298.150000 K"
+        );
+        assert_eq!(
+            render_table(&rows, 1, Rounding::HalfEven),
+            "unit       | value\n\
+             -----------+------\n\
+             celsius    |  25.0 °C\n\
+             fahrenheit |  77.0 °F\n\
+             kelvin     | 298.2 K"
+        );
+    }
+
+    #[test]
+    fn test_all_results_json_follows_the_rows() -> Result<()> {
+        let results = TemperatureConverter::new(100.0, Degree::Celsius).convert_to_all()?;
+        // In declaration order it is the struct's own serialization
+        let declaration = in_unit_order(results.rows(), UnitOrder::Declaration);
+        assert_eq!(
+            all_results_json(&results, &declaration)?,
+            serde_json::to_string(&results)?
+        );
+
+        let symbol = in_unit_order(results.rows(), UnitOrder::Symbol);
+        let json = all_results_json(&results, &symbol)?;
+        assert!(
+            json.starts_with("{\"kelvin\":373.15,\"celsius\":100.0,"),
+            "{}",
+            json
+        );
+
+        // Every length unit has its field, hyphenated names included
+        let results = LengthConverter::new(1.0, Length::Meter).convert_to_all()?;
+        let json = all_results_json(&results, &results.rows())?;
+        assert_eq!(json, serde_json::to_string(&results)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_machine_line_by_format() -> Result<()> {
+        let mut warnings = Warnings::new();
+        assert_eq!(
+            machine_line(OutputFormat::Text, 1.0, &"kg", 1000.0, &"g", &warnings)?,
+            None
+        );
+        assert_eq!(
+            machine_line(OutputFormat::Csv, 1.0, &"kg", 1000.0, &"g", &warnings)?.as_deref(),
+            Some("1,kg,1000,g")
+        );
+        let json = machine_line(OutputFormat::Json, 1.0, &"kg", 1000.0, &"g", &warnings)?.unwrap();
+        assert!(json.contains("\"result\":1000.0"));
+        assert!(json.ends_with("\"warnings\":[]}"));
+
+        warnings.push("approximate", "this result is approximate");
+        let json = machine_line(OutputFormat::Json, 90.0, &"d", 2.96, &"mo", &warnings)?.unwrap();
+        assert!(json.ends_with(
+            "\"warnings\":[{\"code\":\"approximate\",\"message\":\"this result is approximate\"}]}"
+        ));
+        Ok(())
+    }
+}
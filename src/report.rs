@@ -0,0 +1,356 @@
+use crate::errors::Warnings;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// One conversion as a JSON client sends it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConversionRequest {
+    pub from: String,
+    pub to: String,
+    pub value: f64,
+}
+
+/// Why a conversion failed. `code` is a stable machine-readable identifier;
+/// `message` is the same text the CLI prints.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ErrorObject {
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// The answer to one `ConversionRequest`: exactly one of `result` and `error`
+/// is non-null, and `warnings` is always present (possibly empty)
+#[derive(Debug, Serialize)]
+pub struct ConversionResult {
+    pub from: String,
+    pub to: String,
+    pub value: f64,
+    pub result: Option<f64>,
+    pub error: Option<ErrorObject>,
+    pub warnings: Warnings,
+}
+
+impl ConversionResult {
+    pub fn success(request: ConversionRequest, result: f64, warnings: Warnings) -> Self {
+        Self {
+            from: request.from,
+            to: request.to,
+            value: request.value,
+            result: Some(result),
+            error: None,
+            warnings,
+        }
+    }
+
+    pub fn failure(request: ConversionRequest, error: ErrorObject, warnings: Warnings) -> Self {
+        Self {
+            from: request.from,
+            to: request.to,
+            value: request.value,
+            result: None,
+            error: Some(error),
+            warnings,
+        }
+    }
+}
+
+/// What a conversion command prints under `--format json`: the bare answer,
+/// with `result` at full `f64` precision rather than rounded for display,
+/// and the conversion's warnings (possibly none) instead of stderr lines
+#[derive(Debug, Serialize)]
+pub struct ConversionOutput {
+    pub value: f64,
+    pub from: String,
+    pub to: String,
+    pub result: f64,
+    pub warnings: Warnings,
+}
+
+/// What a failing command prints to stderr under `--format json`
+#[derive(Debug, Serialize)]
+pub struct ErrorOutput {
+    pub error: String,
+}
+
+/// One answer in the array `uniconv batch` prints for a JSON array of
+/// requests: a result, or an error for an entry that isn't a valid request
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum BatchAnswer {
+    Answered(ConversionResult),
+    Malformed(ErrorOutput),
+}
+
+impl BatchAnswer {
+    pub fn failed(&self) -> bool {
+        match self {
+            BatchAnswer::Answered(result) => result.error.is_some(),
+            BatchAnswer::Malformed(_) => true,
+        }
+    }
+}
+
+/// One unit as `uniconv list-units --format json` prints it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnitListing {
+    pub category: String,
+    pub name: String,
+    pub symbol: String,
+    pub aliases: Vec<String>,
+}
+
+/// A unit and its category, as `uniconv category --format json` prints it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnitCategory {
+    pub unit: String,
+    pub category: String,
+}
+
+/// JSON Schema (draft 2020-12) for the structs above. Written by hand, so the
+/// tests below check it against what serde actually produces.
+pub fn schema() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "uniconv machine interface",
+        "$defs": {
+            "conversionRequest": {
+                "type": "object",
+                "properties": {
+                    "from": { "type": "string", "description": "Source unit, any accepted spelling" },
+                    "to": { "type": "string", "description": "Target unit, any accepted spelling" },
+                    "value": { "type": "number", "description": "Value in the source unit" }
+                },
+                "required": ["from", "to", "value"],
+                "additionalProperties": false
+            },
+            "conversionResult": {
+                "type": "object",
+                "properties": {
+                    "from": { "type": "string" },
+                    "to": { "type": "string" },
+                    "value": { "type": "number" },
+                    "result": {
+                        "type": ["number", "null"],
+                        "description": "Converted value, null when the conversion failed"
+                    },
+                    "error": {
+                        "oneOf": [{ "$ref": "#/$defs/error" }, { "type": "null" }],
+                        "description": "Why the conversion failed, null on success"
+                    },
+                    "warnings": {
+                        "type": "array",
+                        "items": { "$ref": "#/$defs/warning" }
+                    }
+                },
+                "required": ["from", "to", "value", "result", "error", "warnings"],
+                "additionalProperties": false
+            },
+            "conversionOutput": {
+                "type": "object",
+                "properties": {
+                    "value": { "type": "number" },
+                    "from": { "type": "string", "description": "Source unit symbol" },
+                    "to": { "type": "string", "description": "Target unit symbol" },
+                    "result": { "type": "number", "description": "Converted value, unrounded" },
+                    "warnings": {
+                        "type": "array",
+                        "items": { "$ref": "#/$defs/warning" }
+                    }
+                },
+                "required": ["value", "from", "to", "result", "warnings"],
+                "additionalProperties": false
+            },
+            "batchOutput": {
+                "type": "array",
+                "items": {
+                    "oneOf": [
+                        { "$ref": "#/$defs/conversionResult" },
+                        { "$ref": "#/$defs/errorOutput" }
+                    ]
+                },
+                "description": "What `batch` prints for a JSON array of conversionRequest"
+            },
+            "unitListing": {
+                "type": "object",
+                "properties": {
+                    "category": { "type": "string" },
+                    "name": { "type": "string", "description": "Canonical unit name" },
+                    "symbol": { "type": "string" },
+                    "aliases": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Every spelling that selects the unit"
+                    }
+                },
+                "required": ["category", "name", "symbol", "aliases"],
+                "additionalProperties": false
+            },
+            "unitCategory": {
+                "type": "object",
+                "properties": {
+                    "unit": { "type": "string", "description": "The unit as given" },
+                    "category": { "type": "string" }
+                },
+                "required": ["unit", "category"],
+                "additionalProperties": false
+            },
+            "errorOutput": {
+                "type": "object",
+                "properties": {
+                    "error": { "type": "string" }
+                },
+                "required": ["error"],
+                "additionalProperties": false
+            },
+            "error": {
+                "type": "object",
+                "properties": {
+                    "code": { "type": "string" },
+                    "message": { "type": "string" }
+                },
+                "required": ["code", "message"],
+                "additionalProperties": false
+            },
+            "warning": {
+                "type": "object",
+                "properties": {
+                    "code": { "type": "string" },
+                    "message": { "type": "string" }
+                },
+                "required": ["code", "message"],
+                "additionalProperties": false
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> ConversionRequest {
+        ConversionRequest {
+            from: "c".to_string(),
+            to: "f".to_string(),
+            value: 25.0,
+        }
+    }
+
+    /// Property names the schema declares for one of its `$defs`
+    fn schema_fields(definition: &str) -> Vec<String> {
+        let mut fields: Vec<String> = schema()["$defs"][definition]["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        fields.sort();
+        fields
+    }
+
+    fn serialized_fields(value: &Value) -> Vec<String> {
+        let mut fields: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+        fields.sort();
+        fields
+    }
+
+    #[test]
+    fn test_schema_has_key_fields() {
+        let result_fields = schema_fields("conversionResult");
+        for field in ["from", "to", "value", "error"] {
+            assert!(
+                result_fields.contains(&field.to_string()),
+                "missing {}",
+                field
+            );
+        }
+        assert_eq!(schema_fields("conversionRequest"), ["from", "to", "value"]);
+    }
+
+    #[test]
+    fn test_schema_matches_serde_structs() {
+        let mut warnings = Warnings::new();
+        warnings.push("clamped", "clamped to 0K");
+        let failed = ConversionResult::failure(
+            request(),
+            ErrorObject {
+                code: "invalid-unit",
+                message: "Unrecognized unit".to_string(),
+            },
+            warnings,
+        );
+        let failed = serde_json::to_value(&failed).unwrap();
+
+        assert_eq!(
+            serialized_fields(&failed),
+            schema_fields("conversionResult")
+        );
+        assert_eq!(serialized_fields(&failed["error"]), schema_fields("error"));
+        assert_eq!(
+            serialized_fields(&failed["warnings"][0]),
+            schema_fields("warning")
+        );
+        assert_eq!(
+            serialized_fields(&serde_json::to_value(request()).unwrap()),
+            schema_fields("conversionRequest")
+        );
+
+        let output = ConversionOutput {
+            value: 25.0,
+            from: "°C".to_string(),
+            to: "°F".to_string(),
+            result: 77.0,
+            warnings: Warnings::new(),
+        };
+        assert_eq!(
+            serialized_fields(&serde_json::to_value(output).unwrap()),
+            schema_fields("conversionOutput")
+        );
+        let listing = UnitListing {
+            category: "temperature".to_string(),
+            name: "celsius".to_string(),
+            symbol: "°C".to_string(),
+            aliases: vec!["celsius".to_string(), "c".to_string()],
+        };
+        assert_eq!(
+            serialized_fields(&serde_json::to_value(listing).unwrap()),
+            schema_fields("unitListing")
+        );
+        let category = UnitCategory {
+            unit: "cm".to_string(),
+            category: "length".to_string(),
+        };
+        assert_eq!(
+            serialized_fields(&serde_json::to_value(category).unwrap()),
+            schema_fields("unitCategory")
+        );
+        let malformed = BatchAnswer::Malformed(ErrorOutput {
+            error: "entry 1: missing field `value`".to_string(),
+        });
+        assert!(malformed.failed());
+        assert_eq!(
+            serialized_fields(&serde_json::to_value(malformed).unwrap()),
+            schema_fields("errorOutput")
+        );
+        let error = ErrorOutput {
+            error: "Unrecognized unit".to_string(),
+        };
+        assert_eq!(
+            serialized_fields(&serde_json::to_value(error).unwrap()),
+            schema_fields("errorOutput")
+        );
+
+        let succeeded = ConversionResult::success(request(), 77.0, Warnings::new());
+        let succeeded = serde_json::to_value(&succeeded).unwrap();
+        assert_eq!(succeeded["result"], json!(77.0));
+        assert!(succeeded["error"].is_null());
+    }
+
+    #[test]
+    fn test_request_round_trips() {
+        let parsed: ConversionRequest =
+            serde_json::from_str(r#"{"from": "c", "to": "f", "value": 25}"#).unwrap();
+        assert_eq!(parsed, request());
+    }
+}
@@ -0,0 +1,59 @@
+//! Assertions for test suites that check conversions, enabled by the
+//! `test-utils` feature
+
+use crate::convert::convert;
+
+/// Converts `value` from `from` to `to` and panics unless the result lies
+/// within `tolerance` of `expected`. The panic message names the conversion,
+/// the expected and actual results and how far apart they are.
+#[track_caller]
+pub fn assert_converts(from: &str, to: &str, value: f64, expected: f64, tolerance: f64) {
+    let result = match convert(from, to, value) {
+        Ok(result) => result,
+        Err(e) => panic!(
+            "converting {} {} to {} failed: {}",
+            value,
+            from,
+            to,
+            e.to_string().lines().next().unwrap_or_default()
+        ),
+    };
+
+    let difference = (result - expected).abs();
+    if difference > tolerance || difference.is_nan() {
+        panic!(
+            "converting {} {} to {}: expected {} ± {}, got {} (off by {})",
+            value, from, to, expected, tolerance, result, difference
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passing_assertions() {
+        assert_converts("c", "f", 100.0, 212.0, 1e-9);
+        assert_converts("km", "mi", 42.195, 26.2188, 1e-4);
+        assert_converts("kwh", "j", 1.0, 3_600_000.0, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "converting 100 c to f: expected 211 ± 0.5, got 212 (off by 1)")]
+    fn test_mismatch_panics_with_both_values() {
+        assert_converts("c", "f", 100.0, 211.0, 0.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "converting 1 kg to cm failed: Cannot compare")]
+    fn test_failed_conversion_panics() {
+        assert_converts("kg", "cm", 1.0, 100.0, 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "got 100 (off by NaN)")]
+    fn test_nan_tolerance_never_passes() {
+        assert_converts("m", "cm", 1.0, f64::NAN, 1.0);
+    }
+}
@@ -0,0 +1,1069 @@
+//! Unit names as typed: the spellings each category accepts, parsing them
+//! into unit enums, and recognizing which category a name belongs to
+
+use crate::conv::{
+    Angle, Area, Data, Degree, Energy, Frequency, FuelEconomy, Length, Mass, Power, Pressure,
+    Speed, Time, Volume,
+};
+use crate::util::find_closest_match;
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use std::fmt::Display;
+
+/// Declares a category's spellings once. `$list` holds every spelling in
+/// table order, for `list-units` and suggestions, and `$lookup` matches the
+/// same spellings, lowercased, to their units, so neither can drift.
+macro_rules! unit_spellings {
+    (
+        $list:ident,
+        $(#[$doc:meta])*
+        $vis:vis fn $lookup:ident -> $unit:ident {
+            $($variant:ident => [$($spelling:literal),+ $(,)?],)+
+        }
+    ) => {
+        pub const $list: &[&str] = &[$($($spelling,)+)+];
+
+        $(#[$doc])*
+        $vis fn $lookup(unit: &str) -> Option<$unit> {
+            match unit.to_lowercase().as_str() {
+                $($($spelling)|+ => Some($unit::$variant),)+
+                _ => None,
+            }
+        }
+    };
+}
+
+unit_spellings! {
+    TEMPERATURE_UNITS,
+    /// Resolves a unit name without building an error message, for callers
+    /// that probe several categories in turn
+    pub fn lookup_temperature_unit -> Degree {
+        Celsius => ["celsius", "c", "°c"],
+        Fahrenheit => ["fahrenheit", "f", "°f"],
+        Kelvin => ["kelvin", "k"],
+        Delisle => ["delisle", "de", "°de"],
+        Newton => ["newton", "n", "°n"],
+        GasMark => ["gasmark", "gas-mark", "gas mark", "gm"],
+    }
+}
+
+unit_spellings! {
+    LENGTH_UNITS,
+    pub fn lookup_length_unit -> Length {
+        Centimeter => ["centimeter", "cm"],
+        Inch => ["inch", "in"],
+        Kilometer => ["kilometer", "km"],
+        Miles => ["miles", "mile", "mi"],
+        Meter => ["meter", "metre", "m"],
+        Millimeter => ["millimeter", "millimetre", "mm"],
+        Micrometer => ["micrometer", "micrometre", "um", "µm", "μm"],
+        Nanometer => ["nanometer", "nanometre", "nm"],
+        Foot => ["foot", "feet", "ft"],
+        Yard => ["yard", "yd"],
+        NauticalMile => ["nautical-mile", "nauticalmile", "nmi"],
+        LightYear => ["light-year", "lightyear", "ly"],
+        AstronomicalUnit => ["astronomical-unit", "astronomicalunit", "au"],
+        Parsec => ["parsec", "pc"],
+        Angstrom => ["angstrom", "ang", "å"],
+        Thou => ["thou", "mil"],
+        Furlong => ["furlong", "fur"],
+        Fathom => ["fathom", "ftm"],
+        Rod => ["rod", "pole", "perch", "rd"],
+        Chain => ["chain", "ch"],
+    }
+}
+
+unit_spellings! {
+    MASS_UNITS,
+    pub fn lookup_mass_unit -> Mass {
+        Gram => ["gram", "gramme", "g"],
+        Kilogram => ["kilogram", "kilogramme", "kilo", "kg"],
+        Milligram => ["milligram", "milligramme", "mg"],
+        Tonne => ["tonne", "metric-ton", "t"],
+        Pound => ["pound", "lb", "lbs"],
+        Ounce => ["ounce", "oz"],
+        Stone => ["stone", "st"],
+    }
+}
+
+unit_spellings! {
+    VOLUME_UNITS,
+    pub fn lookup_volume_unit -> Volume {
+        Liter => ["liter", "litre", "l"],
+        Milliliter => ["milliliter", "millilitre", "ml"],
+        CubicMeter => ["cubic-meter", "cubicmeter", "cubic-metre", "m3", "m³"],
+        GallonUS => ["gallon", "gal", "us-gallon", "usgal", "gallon-us"],
+        GallonImperial => ["imperial-gallon", "impgal", "imp-gal", "gallon-imperial"],
+        Quart => ["quart", "qt"],
+        Pint => ["pint", "pt"],
+        FluidOunce => ["fluid-ounce", "fluidounce", "fl-oz", "floz"],
+    }
+}
+
+unit_spellings! {
+    AREA_UNITS,
+    pub fn lookup_area_unit -> Area {
+        SquareMeter => ["square-meter", "square-metre", "squaremeter", "sqm", "m2", "m²"],
+        SquareKilometer => [
+            "square-kilometer",
+            "square-kilometre",
+            "squarekilometer",
+            "sqkm",
+            "km2",
+            "km²",
+        ],
+        SquareCentimeter => [
+            "square-centimeter",
+            "square-centimetre",
+            "squarecentimeter",
+            "sqcm",
+            "cm2",
+            "cm²",
+        ],
+        Hectare => ["hectare", "ha"],
+        Acre => ["acre", "ac"],
+        SquareFoot => ["square-foot", "square-feet", "squarefoot", "sqft", "ft2", "ft²"],
+        SquareMile => ["square-mile", "squaremile", "sqmi", "mi2", "mi²"],
+    }
+}
+
+unit_spellings! {
+    SPEED_UNITS,
+    pub fn lookup_speed_unit -> Speed {
+        MeterPerSecond => ["meter-per-second", "metre-per-second", "meters-per-second", "m/s", "mps"],
+        KilometerPerHour => [
+            "kilometer-per-hour",
+            "kilometre-per-hour",
+            "kilometers-per-hour",
+            "km/h",
+            "kmh",
+            "kph",
+        ],
+        MilePerHour => ["mile-per-hour", "miles-per-hour", "mi/h", "mph"],
+        Knot => ["knot", "kn", "kt"],
+        FootPerSecond => ["foot-per-second", "feet-per-second", "ft/s", "fps"],
+    }
+}
+
+unit_spellings! {
+    TIME_UNITS,
+    pub fn lookup_time_unit -> Time {
+        Second => ["second", "sec", "s"],
+        Minute => ["minute", "min"],
+        Hour => ["hour", "hr", "h"],
+        Day => ["day", "d"],
+        Week => ["week", "wk", "w"],
+        Millisecond => ["millisecond", "msec", "ms"],
+        Month => ["month", "mo"],
+        Year => ["year", "yr", "y"],
+    }
+}
+
+unit_spellings! {
+    DATA_UNITS,
+    pub fn lookup_data_unit -> Data {
+        Bit => ["bit"],
+        Byte => ["byte", "octet"],
+        Kilobyte => ["kilobyte", "kb"],
+        Megabyte => ["megabyte", "mb"],
+        Gigabyte => ["gigabyte", "gb"],
+        Terabyte => ["terabyte", "tb"],
+        Kibibyte => ["kibibyte", "kib"],
+        Mebibyte => ["mebibyte", "mib"],
+        Gibibyte => ["gibibyte", "gib"],
+        Tebibyte => ["tebibyte", "tib"],
+    }
+}
+
+unit_spellings! {
+    PRESSURE_UNITS,
+    pub fn lookup_pressure_unit -> Pressure {
+        Pascal => ["pascal", "pa"],
+        Kilopascal => ["kilopascal", "kpa"],
+        Bar => ["bar"],
+        Atmosphere => ["atmosphere", "atm"],
+        Psi => ["psi", "pound-per-square-inch"],
+        MmHg => ["millimeter-of-mercury", "mm-hg", "mmhg"],
+    }
+}
+
+unit_spellings! {
+    ENERGY_UNITS,
+    fn lookup_lowercase_energy_unit -> Energy {
+        Joule => ["joule", "j"],
+        Kilojoule => ["kilojoule", "kj"],
+        Calorie => ["calorie", "cal"],
+        Kilocalorie => ["kilocalorie", "kcal"],
+        WattHour => ["watt-hour", "wh"],
+        KilowattHour => ["kilowatt-hour", "kwh"],
+        Btu => ["btu", "british-thermal-unit"],
+    }
+}
+
+unit_spellings! {
+    POWER_UNITS,
+    pub fn lookup_power_unit -> Power {
+        Watt => ["watt"],
+        Kilowatt => ["kilowatt", "kw"],
+        Megawatt => ["megawatt", "mw"],
+        HorsepowerMechanical => [
+            "horsepower",
+            "hp",
+            "mechanical-horsepower",
+            "horsepower-mechanical",
+        ],
+        HorsepowerMetric => ["metric-horsepower", "horsepower-metric", "metric-hp", "ps"],
+    }
+}
+
+unit_spellings! {
+    ANGLE_UNITS,
+    pub fn lookup_angle_unit -> Angle {
+        Degree => ["degree", "deg", "°"],
+        Radian => ["radian", "rad"],
+        Gradian => ["gradian", "grad", "gon"],
+        Turn => ["turn", "rev", "revolution"],
+        Arcminute => ["arcminute", "arcmin"],
+        Arcsecond => ["arcsecond", "arcsec"],
+    }
+}
+
+unit_spellings! {
+    FREQUENCY_UNITS,
+    pub fn lookup_frequency_unit -> Frequency {
+        Hertz => ["hertz", "hz"],
+        Kilohertz => ["kilohertz", "khz"],
+        Megahertz => ["megahertz", "mhz"],
+        Gigahertz => ["gigahertz", "ghz"],
+        Rpm => ["rpm", "revolutions-per-minute"],
+    }
+}
+
+unit_spellings! {
+    FUEL_UNITS,
+    pub fn lookup_fuel_unit -> FuelEconomy {
+        MpgUS => ["mpg", "mpg-us"],
+        MpgImperial => ["mpg-imperial", "mpg-imp", "mpg-uk"],
+        L100km => ["l/100km", "l100km", "liters-per-100km"],
+        KmPerLiter => ["km/l", "kmpl", "km-per-liter"],
+    }
+}
+
+/// Every spelling each category accepts, in detection order. `list-units`
+/// and `suggest_unit` read these; each list comes from the same
+/// `unit_spellings!` table as its `lookup_*_unit` parser.
+pub const UNIT_CATEGORIES: &[(&str, &[&str])] = &[
+    ("temperature", TEMPERATURE_UNITS),
+    ("length", LENGTH_UNITS),
+    ("mass", MASS_UNITS),
+    ("volume", VOLUME_UNITS),
+    ("area", AREA_UNITS),
+    ("speed", SPEED_UNITS),
+    ("time", TIME_UNITS),
+    ("data", DATA_UNITS),
+    ("pressure", PRESSURE_UNITS),
+    ("energy", ENERGY_UNITS),
+    ("power", POWER_UNITS),
+    ("angle", ANGLE_UNITS),
+    ("frequency", FREQUENCY_UNITS),
+    ("fuel", FUEL_UNITS),
+];
+
+pub const SUPPORTED_UNITS_HELP: &str = "
+Supported units:
+Temperature: celsius (c), fahrenheit (f), kelvin (k), delisle (de), newton (n), gasmark (gm)
+Length: centimeter (cm), inch (in), kilometer (km), miles (mi), meter (m), millimeter (mm),
+        micrometer (um), nanometer (nm), foot (ft), yard (yd), nautical-mile (nmi),
+        light-year (ly), astronomical-unit (au), parsec (pc), angstrom (ang),
+        thou (mil), furlong (fur), fathom (ftm), rod (rd), chain (ch)
+Mass: gram (g), kilogram (kg), milligram (mg), tonne (t), pound (lb), ounce (oz), stone (st)
+Volume: liter (l), milliliter (ml), cubic-meter (m3), gallon (gal, US), imperial-gallon (impgal),
+        quart (qt), pint (pt), fluid-ounce (floz)
+Area: square-meter (m2), square-kilometer (km2), square-centimeter (cm2), hectare (ha),
+      acre (ac), square-foot (ft2), square-mile (mi2)
+Speed: meter-per-second (m/s), kilometer-per-hour (km/h, kph), mile-per-hour (mph), knot (kn),
+       foot-per-second (ft/s)
+Time: second (s, sec), minute (min), hour (h, hr), day (d), week (w), millisecond (ms),
+      month (mo, ≈30.44 d), year (yr, ≈365.25 d)
+Data: bit, byte, kilobyte (kb), megabyte (mb), gigabyte (gb), terabyte (tb) are SI (1 kB = 1000 B);
+      kibibyte (kib), mebibyte (mib), gibibyte (gib), tebibyte (tib) are binary (1 KiB = 1024 B)
+Pressure: pascal (pa), kilopascal (kpa), bar, atmosphere (atm), psi, millimeter-of-mercury (mmhg)
+Energy: joule (j), kilojoule (kj), calorie (cal, 4.184 J), kilocalorie (kcal, Cal), watt-hour (wh),
+        kilowatt-hour (kwh), btu
+Power: watt, kilowatt (kw), megawatt (mw), horsepower (hp, mechanical), metric-horsepower (ps)
+Angle: degree (deg), radian (rad), gradian (grad, gon), turn (rev), arcminute (arcmin),
+       arcsecond (arcsec)
+Frequency: hertz (hz), kilohertz (khz), megahertz (mhz), gigahertz (ghz), rpm
+Fuel: mpg (US), mpg-imperial (mpg-uk), l/100km (l100km), km/l (kmpl)
+";
+
+/// `unit` as a temperature unit. The `UnitParseError` stays the root cause,
+/// with the list of valid units and a suggestion attached as context.
+pub fn parse_temperature_unit(unit: &str) -> Result<Degree> {
+    unit.parse::<Degree>().with_context(|| {
+        let mut error_msg = format!("Invalid temperature unit: '{}'.\n", unit);
+        error_msg.push_str("Valid temperature units are:\n");
+        error_msg.push_str("  • celsius (or 'c')\n");
+        error_msg.push_str("  • fahrenheit (or 'f')\n");
+        error_msg.push_str("  • kelvin (or 'k')\n");
+        error_msg.push_str("  • delisle (or 'de')\n");
+        error_msg.push_str("  • newton (or 'n')\n");
+        error_msg.push_str("  • gasmark (or 'gas-mark', 'gm')\n");
+
+        if let Some(suggestion) = find_closest_match(unit, TEMPERATURE_UNITS) {
+            error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
+        }
+
+        error_msg
+    })
+}
+
+/// `unit` as a length unit, with the same context as
+/// `parse_temperature_unit`
+pub fn parse_length_unit(unit: &str) -> Result<Length> {
+    unit.parse::<Length>().with_context(|| {
+        let mut error_msg = format!("Invalid length unit: '{}'.\n", unit);
+        error_msg.push_str("Valid length units are:\n");
+        error_msg.push_str("  • centimeter (or 'cm')\n");
+        error_msg.push_str("  • inch (or 'in')\n");
+        error_msg.push_str("  • kilometer (or 'km')\n");
+        error_msg.push_str("  • miles (or 'mile', 'mi')\n");
+        error_msg.push_str("  • meter (or 'metre', 'm')\n");
+        error_msg.push_str("  • millimeter (or 'millimetre', 'mm')\n");
+        error_msg.push_str("  • micrometer (or 'micrometre', 'um', 'µm', 'μm')\n");
+        error_msg.push_str("  • nanometer (or 'nanometre', 'nm')\n");
+        error_msg.push_str("  • foot (or 'feet', 'ft')\n");
+        error_msg.push_str("  • yard (or 'yd')\n");
+        error_msg.push_str("  • nautical-mile (or 'nauticalmile', 'nmi')\n");
+        error_msg.push_str("  • light-year (or 'lightyear', 'ly')\n");
+        error_msg.push_str("  • astronomical-unit (or 'au')\n");
+        error_msg.push_str("  • parsec (or 'pc')\n");
+        error_msg.push_str("  • angstrom (or 'ang', 'Å')\n");
+        error_msg.push_str("  • thou (or 'mil')\n");
+        error_msg.push_str("  • furlong (or 'fur')\n");
+        error_msg.push_str("  • fathom (or 'ftm')\n");
+        error_msg.push_str("  • rod (or 'pole', 'perch', 'rd')\n");
+        error_msg.push_str("  • chain (or 'ch')\n");
+
+        if let Some(suggestion) = find_closest_match(unit, LENGTH_UNITS) {
+            error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
+        }
+
+        error_msg
+    })
+}
+
+pub fn parse_mass_unit(unit: &str) -> Result<Mass> {
+    lookup_mass_unit(unit).ok_or_else(|| {
+        let mut error_msg = format!("Invalid mass unit: '{}'.\n", unit);
+        error_msg.push_str("Valid mass units are:\n");
+        error_msg.push_str("  • gram (or 'gramme', 'g')\n");
+        error_msg.push_str("  • kilogram (or 'kilogramme', 'kilo', 'kg')\n");
+        error_msg.push_str("  • milligram (or 'milligramme', 'mg')\n");
+        error_msg.push_str("  • tonne (or 'metric-ton', 't')\n");
+        error_msg.push_str("  • pound (or 'lb', 'lbs')\n");
+        error_msg.push_str("  • ounce (or 'oz')\n");
+        error_msg.push_str("  • stone (or 'st')\n");
+
+        if let Some(suggestion) = find_closest_match(unit, MASS_UNITS) {
+            error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
+        }
+
+        anyhow!(error_msg)
+    })
+}
+
+pub fn parse_volume_unit(unit: &str) -> Result<Volume> {
+    lookup_volume_unit(unit).ok_or_else(|| {
+        let mut error_msg = format!("Invalid volume unit: '{}'.\n", unit);
+        error_msg.push_str("Valid volume units are:\n");
+        error_msg.push_str("  • liter (or 'litre', 'l')\n");
+        error_msg.push_str("  • milliliter (or 'millilitre', 'ml')\n");
+        error_msg.push_str("  • cubic-meter (or 'cubic-metre', 'm3', 'm³')\n");
+        error_msg.push_str(
+            "  • gallon (or 'gal', 'us-gallon', 'gallon-us'; US gallon, 3.785411784 L)\n",
+        );
+        error_msg.push_str("  • imperial-gallon (or 'impgal', 'gallon-imperial'; 4.54609 L)\n");
+        error_msg.push_str("  • quart (or 'qt'; US liquid quart)\n");
+        error_msg.push_str("  • pint (or 'pt'; US liquid pint)\n");
+        error_msg.push_str("  • fluid-ounce (or 'fl-oz', 'floz'; US)\n");
+
+        if let Some(suggestion) = find_closest_match(unit, VOLUME_UNITS) {
+            error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
+        }
+
+        anyhow!(error_msg)
+    })
+}
+
+pub fn parse_area_unit(unit: &str) -> Result<Area> {
+    lookup_area_unit(unit).ok_or_else(|| {
+        let mut error_msg = format!("Invalid area unit: '{}'.\n", unit);
+        error_msg.push_str("Valid area units are:\n");
+        error_msg.push_str("  • square-meter (or 'square-metre', 'sqm', 'm2', 'm²')\n");
+        error_msg.push_str("  • square-kilometer (or 'sqkm', 'km2', 'km²')\n");
+        error_msg.push_str("  • square-centimeter (or 'sqcm', 'cm2', 'cm²')\n");
+        error_msg.push_str("  • hectare (or 'ha')\n");
+        error_msg.push_str("  • acre (or 'ac')\n");
+        error_msg.push_str("  • square-foot (or 'square-feet', 'sqft', 'ft2', 'ft²')\n");
+        error_msg.push_str("  • square-mile (or 'sqmi', 'mi2', 'mi²')\n");
+
+        if let Some(suggestion) = find_closest_match(unit, AREA_UNITS) {
+            error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
+        }
+
+        anyhow!(error_msg)
+    })
+}
+
+pub fn parse_speed_unit(unit: &str) -> Result<Speed> {
+    lookup_speed_unit(unit).ok_or_else(|| {
+        let mut error_msg = format!("Invalid speed unit: '{}'.\n", unit);
+        error_msg.push_str("Valid speed units are:\n");
+        error_msg.push_str("  • meter-per-second (or 'metre-per-second', 'm/s', 'mps')\n");
+        error_msg.push_str("  • kilometer-per-hour (or 'km/h', 'kmh', 'kph')\n");
+        error_msg.push_str("  • mile-per-hour (or 'miles-per-hour', 'mi/h', 'mph')\n");
+        error_msg.push_str("  • knot (or 'kn', 'kt')\n");
+        error_msg.push_str("  • foot-per-second (or 'feet-per-second', 'ft/s', 'fps')\n");
+
+        if let Some(suggestion) = find_closest_match(unit, SPEED_UNITS) {
+            error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
+        }
+
+        anyhow!(error_msg)
+    })
+}
+
+pub fn parse_time_unit(unit: &str) -> Result<Time> {
+    lookup_time_unit(unit).ok_or_else(|| {
+        let mut error_msg = format!("Invalid time unit: '{}'.\n", unit);
+        error_msg.push_str("Valid time units are:\n");
+        error_msg.push_str("  • second (or 'sec', 's')\n");
+        error_msg.push_str("  • minute (or 'min')\n");
+        error_msg.push_str("  • hour (or 'hr', 'h')\n");
+        error_msg.push_str("  • day (or 'd')\n");
+        error_msg.push_str("  • week (or 'wk', 'w')\n");
+        error_msg.push_str("  • millisecond (or 'msec', 'ms')\n");
+        error_msg.push_str("  • month (or 'mo', an average 30.44 days)\n");
+        error_msg.push_str("  • year (or 'yr', 'y', 365.25 days)\n");
+
+        if let Some(suggestion) = find_closest_match(unit, TIME_UNITS) {
+            error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
+        }
+
+        anyhow!(error_msg)
+    })
+}
+
+pub fn parse_data_unit(unit: &str) -> Result<Data> {
+    lookup_data_unit(unit).ok_or_else(|| {
+        let mut error_msg = format!("Invalid data unit: '{}'.\n", unit);
+        error_msg.push_str("Valid data units are:\n");
+        error_msg.push_str("  • bit\n");
+        error_msg.push_str("  • byte (or 'octet')\n");
+        error_msg.push_str("  • kilobyte (or 'kb'; SI, 1000 B)\n");
+        error_msg.push_str("  • megabyte (or 'mb'; SI, 1000 kB)\n");
+        error_msg.push_str("  • gigabyte (or 'gb'; SI, 1000 MB)\n");
+        error_msg.push_str("  • terabyte (or 'tb'; SI, 1000 GB)\n");
+        error_msg.push_str("  • kibibyte (or 'kib'; binary, 1024 B)\n");
+        error_msg.push_str("  • mebibyte (or 'mib'; binary, 1024 KiB)\n");
+        error_msg.push_str("  • gibibyte (or 'gib'; binary, 1024 MiB)\n");
+        error_msg.push_str("  • tebibyte (or 'tib'; binary, 1024 GiB)\n");
+        error_msg.push_str(
+            "Units are case-insensitive: 'kb' is always a kilobyte (1000 B, SI); use 'kib' for 1024 B.\n",
+        );
+
+        if let Some(suggestion) = find_closest_match(unit, DATA_UNITS) {
+            error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
+        }
+
+        anyhow!(error_msg)
+    })
+}
+
+pub fn parse_pressure_unit(unit: &str) -> Result<Pressure> {
+    lookup_pressure_unit(unit).ok_or_else(|| {
+        let mut error_msg = format!("Invalid pressure unit: '{}'.\n", unit);
+        error_msg.push_str("Valid pressure units are:\n");
+        error_msg.push_str("  • pascal (or 'pa')\n");
+        error_msg.push_str("  • kilopascal (or 'kpa')\n");
+        error_msg.push_str("  • bar\n");
+        error_msg.push_str("  • atmosphere (or 'atm')\n");
+        error_msg.push_str("  • psi (or 'pound-per-square-inch')\n");
+        error_msg.push_str("  • millimeter-of-mercury (or 'mmhg', 'mm-hg')\n");
+
+        if let Some(suggestion) = find_closest_match(unit, PRESSURE_UNITS) {
+            error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
+        }
+
+        anyhow!(error_msg)
+    })
+}
+
+pub fn lookup_energy_unit(unit: &str) -> Option<Energy> {
+    // Nutrition labels write the kilocalorie as a capitalized "Cal"
+    if unit == "Cal" {
+        return Some(Energy::Kilocalorie);
+    }
+
+    lookup_lowercase_energy_unit(unit)
+}
+
+pub fn parse_energy_unit(unit: &str) -> Result<Energy> {
+    lookup_energy_unit(unit).ok_or_else(|| {
+        let mut error_msg = format!("Invalid energy unit: '{}'.\n", unit);
+        error_msg.push_str("Valid energy units are:\n");
+        error_msg.push_str("  • joule (or 'j')\n");
+        error_msg.push_str("  • kilojoule (or 'kj')\n");
+        error_msg.push_str("  • calorie (or 'cal'; the small calorie, 4.184 J)\n");
+        error_msg.push_str("  • kilocalorie (or 'kcal', 'Cal'; the food calorie, 4184 J)\n");
+        error_msg.push_str("  • watt-hour (or 'wh')\n");
+        error_msg.push_str("  • kilowatt-hour (or 'kwh')\n");
+        error_msg.push_str("  • btu (or 'british-thermal-unit')\n");
+
+        if let Some(suggestion) = find_closest_match(unit, ENERGY_UNITS) {
+            error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
+        }
+
+        anyhow!(error_msg)
+    })
+}
+
+pub fn parse_power_unit(unit: &str) -> Result<Power> {
+    lookup_power_unit(unit).ok_or_else(|| {
+        let mut error_msg = format!("Invalid power unit: '{}'.\n", unit);
+        error_msg.push_str("Valid power units are:\n");
+        error_msg.push_str("  • watt ('w' is a week; spell out 'watt')\n");
+        error_msg.push_str("  • kilowatt (or 'kw')\n");
+        error_msg.push_str("  • megawatt (or 'mw')\n");
+        error_msg.push_str("  • horsepower (or 'hp'; mechanical, 745.7 W)\n");
+        error_msg.push_str("  • metric-horsepower (or 'ps', 'metric-hp'; 735.5 W)\n");
+
+        if let Some(suggestion) = find_closest_match(unit, POWER_UNITS) {
+            error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
+        }
+
+        anyhow!(error_msg)
+    })
+}
+
+pub fn parse_angle_unit(unit: &str) -> Result<Angle> {
+    lookup_angle_unit(unit).ok_or_else(|| {
+        let mut error_msg = format!("Invalid angle unit: '{}'.\n", unit);
+        error_msg.push_str("Valid angle units are:\n");
+        error_msg.push_str("  • degree (or 'deg', '°')\n");
+        error_msg.push_str("  • radian (or 'rad')\n");
+        error_msg.push_str("  • gradian (or 'grad', 'gon')\n");
+        error_msg.push_str("  • turn (or 'rev', 'revolution')\n");
+        error_msg.push_str("  • arcminute (or 'arcmin')\n");
+        error_msg.push_str("  • arcsecond (or 'arcsec')\n");
+
+        if let Some(suggestion) = find_closest_match(unit, ANGLE_UNITS) {
+            error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
+        }
+
+        anyhow!(error_msg)
+    })
+}
+
+pub fn parse_frequency_unit(unit: &str) -> Result<Frequency> {
+    lookup_frequency_unit(unit).ok_or_else(|| {
+        let mut error_msg = format!("Invalid frequency unit: '{}'.\n", unit);
+        error_msg.push_str("Valid frequency units are:\n");
+        error_msg.push_str("  • hertz (or 'hz')\n");
+        error_msg.push_str("  • kilohertz (or 'khz')\n");
+        error_msg.push_str("  • megahertz (or 'mhz')\n");
+        error_msg.push_str("  • gigahertz (or 'ghz')\n");
+        error_msg.push_str("  • rpm (or 'revolutions-per-minute'; 60 rpm = 1 Hz)\n");
+
+        if let Some(suggestion) = find_closest_match(unit, FREQUENCY_UNITS) {
+            error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
+        }
+
+        anyhow!(error_msg)
+    })
+}
+
+pub fn parse_fuel_unit(unit: &str) -> Result<FuelEconomy> {
+    lookup_fuel_unit(unit).ok_or_else(|| {
+        let mut error_msg = format!("Invalid fuel economy unit: '{}'.\n", unit);
+        error_msg.push_str("Valid fuel economy units are:\n");
+        error_msg.push_str("  • mpg (or 'mpg-us'; miles per US gallon)\n");
+        error_msg
+            .push_str("  • mpg-imperial (or 'mpg-imp', 'mpg-uk'; miles per imperial gallon)\n");
+        error_msg.push_str("  • l/100km (or 'l100km', 'liters-per-100km')\n");
+        error_msg.push_str("  • km/l (or 'kmpl', 'km-per-liter')\n");
+
+        if let Some(suggestion) = find_closest_match(unit, FUEL_UNITS) {
+            error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
+        }
+
+        anyhow!(error_msg)
+    })
+}
+
+/// A unit string resolved to its category
+pub enum RecognizedUnit {
+    Temperature(Degree),
+    Length(Length),
+    Mass(Mass),
+    Volume(Volume),
+    Area(Area),
+    Speed(Speed),
+    Time(Time),
+    Data(Data),
+    Pressure(Pressure),
+    Energy(Energy),
+    Power(Power),
+    Angle(Angle),
+    Frequency(Frequency),
+    Fuel(FuelEconomy),
+}
+
+/// Wraps each unit enum in its category's variant, so code generic over
+/// `Unit` can reach the checks in `convert_recognized`
+macro_rules! impl_from_unit {
+    ($($unit:ty => $variant:ident;)*) => {
+        $(
+            impl From<$unit> for RecognizedUnit {
+                fn from(unit: $unit) -> Self {
+                    RecognizedUnit::$variant(unit)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_unit! {
+    Degree => Temperature;
+    Length => Length;
+    Mass => Mass;
+    Volume => Volume;
+    Area => Area;
+    Speed => Speed;
+    Time => Time;
+    Data => Data;
+    Pressure => Pressure;
+    Energy => Energy;
+    Power => Power;
+    Angle => Angle;
+    Frequency => Frequency;
+    FuelEconomy => Fuel;
+}
+
+impl Display for RecognizedUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecognizedUnit::Temperature(unit) => write!(f, "{}", unit),
+            RecognizedUnit::Length(unit) => write!(f, "{}", unit),
+            RecognizedUnit::Mass(unit) => write!(f, "{}", unit),
+            RecognizedUnit::Volume(unit) => write!(f, "{}", unit),
+            RecognizedUnit::Area(unit) => write!(f, "{}", unit),
+            RecognizedUnit::Speed(unit) => write!(f, "{}", unit),
+            RecognizedUnit::Time(unit) => write!(f, "{}", unit),
+            RecognizedUnit::Data(unit) => write!(f, "{}", unit),
+            RecognizedUnit::Pressure(unit) => write!(f, "{}", unit),
+            RecognizedUnit::Energy(unit) => write!(f, "{}", unit),
+            RecognizedUnit::Power(unit) => write!(f, "{}", unit),
+            RecognizedUnit::Angle(unit) => write!(f, "{}", unit),
+            RecognizedUnit::Frequency(unit) => write!(f, "{}", unit),
+            RecognizedUnit::Fuel(unit) => write!(f, "{}", unit),
+        }
+    }
+}
+
+impl RecognizedUnit {
+    /// The canonical (clap value) name of the unit, e.g. `centimeter`
+    pub fn canonical_name(&self) -> String {
+        let value = match self {
+            RecognizedUnit::Temperature(unit) => unit.to_possible_value(),
+            RecognizedUnit::Length(unit) => unit.to_possible_value(),
+            RecognizedUnit::Mass(unit) => unit.to_possible_value(),
+            RecognizedUnit::Volume(unit) => unit.to_possible_value(),
+            RecognizedUnit::Area(unit) => unit.to_possible_value(),
+            RecognizedUnit::Speed(unit) => unit.to_possible_value(),
+            RecognizedUnit::Time(unit) => unit.to_possible_value(),
+            RecognizedUnit::Data(unit) => unit.to_possible_value(),
+            RecognizedUnit::Pressure(unit) => unit.to_possible_value(),
+            RecognizedUnit::Energy(unit) => unit.to_possible_value(),
+            RecognizedUnit::Power(unit) => unit.to_possible_value(),
+            RecognizedUnit::Angle(unit) => unit.to_possible_value(),
+            RecognizedUnit::Frequency(unit) => unit.to_possible_value(),
+            RecognizedUnit::Fuel(unit) => unit.to_possible_value(),
+        };
+        value.map(|v| v.get_name().to_string()).unwrap_or_default()
+    }
+
+    /// The category name `list-units` files the unit under, e.g. `length`
+    pub fn category(&self) -> &'static str {
+        match self {
+            RecognizedUnit::Temperature(_) => "temperature",
+            RecognizedUnit::Length(_) => "length",
+            RecognizedUnit::Mass(_) => "mass",
+            RecognizedUnit::Volume(_) => "volume",
+            RecognizedUnit::Area(_) => "area",
+            RecognizedUnit::Speed(_) => "speed",
+            RecognizedUnit::Time(_) => "time",
+            RecognizedUnit::Data(_) => "data",
+            RecognizedUnit::Pressure(_) => "pressure",
+            RecognizedUnit::Energy(_) => "energy",
+            RecognizedUnit::Power(_) => "power",
+            RecognizedUnit::Angle(_) => "angle",
+            RecognizedUnit::Frequency(_) => "frequency",
+            RecognizedUnit::Fuel(_) => "fuel",
+        }
+    }
+}
+
+pub fn recognize_unit(unit: &str) -> Option<RecognizedUnit> {
+    let trimmed = unit.trim();
+    let cleaned = trimmed.to_lowercase();
+
+    // Try the unit as written first (a few symbols are case-sensitive, like
+    // "Cal"), then lowercased, then with common plural suffixes stripped
+    let mut candidates = vec![trimmed, cleaned.as_str()];
+    if let Some(singular) = cleaned.strip_suffix("es") {
+        candidates.push(singular);
+    }
+    if let Some(singular) = cleaned.strip_suffix('s') {
+        candidates.push(singular);
+    }
+
+    for candidate in candidates {
+        if let Some(degree) = lookup_temperature_unit(candidate) {
+            return Some(RecognizedUnit::Temperature(degree));
+        }
+        if let Some(length) = lookup_length_unit(candidate) {
+            return Some(RecognizedUnit::Length(length));
+        }
+        if let Some(mass) = lookup_mass_unit(candidate) {
+            return Some(RecognizedUnit::Mass(mass));
+        }
+        if let Some(unit) = lookup_volume_unit(candidate) {
+            return Some(RecognizedUnit::Volume(unit));
+        }
+        if let Some(unit) = lookup_area_unit(candidate) {
+            return Some(RecognizedUnit::Area(unit));
+        }
+        if let Some(unit) = lookup_speed_unit(candidate) {
+            return Some(RecognizedUnit::Speed(unit));
+        }
+        if let Some(unit) = lookup_time_unit(candidate) {
+            return Some(RecognizedUnit::Time(unit));
+        }
+        if let Some(unit) = lookup_data_unit(candidate) {
+            return Some(RecognizedUnit::Data(unit));
+        }
+        if let Some(unit) = lookup_pressure_unit(candidate) {
+            return Some(RecognizedUnit::Pressure(unit));
+        }
+        if let Some(unit) = lookup_energy_unit(candidate) {
+            return Some(RecognizedUnit::Energy(unit));
+        }
+        if let Some(unit) = lookup_power_unit(candidate) {
+            return Some(RecognizedUnit::Power(unit));
+        }
+        if let Some(unit) = lookup_angle_unit(candidate) {
+            return Some(RecognizedUnit::Angle(unit));
+        }
+        if let Some(unit) = lookup_frequency_unit(candidate) {
+            return Some(RecognizedUnit::Frequency(unit));
+        }
+        if let Some(unit) = lookup_fuel_unit(candidate) {
+            return Some(RecognizedUnit::Fuel(unit));
+        }
+    }
+
+    None
+}
+
+/// Like `recognize_unit`, but an unknown unit is an error with suggestions
+pub fn require_unit(unit: &str) -> Result<RecognizedUnit> {
+    if let Some(recognized) = recognize_unit(unit) {
+        return Ok(recognized);
+    }
+
+    let mut error_msg = format!("Unrecognized unit: '{}'\n", unit.trim());
+    error_msg.push_str(SUPPORTED_UNITS_HELP);
+
+    if let Some(suggestion) = suggest_unit(unit.trim()) {
+        error_msg.push_str(&format!("\nDid you mean '{}'?", suggestion));
+    }
+
+    Err(anyhow!(error_msg))
+}
+
+pub fn normalize_unit(unit: &str) -> Result<String> {
+    Ok(require_unit(unit)?.canonical_name())
+}
+
+/// The category of a unit, accepting the same spellings as `normalize`
+pub fn categorize_unit(unit: &str) -> Result<&'static str> {
+    Ok(require_unit(unit)?.category())
+}
+
+/// Picks the single numeric token as the value and keeps the two unit
+/// tokens in order, so `25 c f`, `c 25 f` and `c f 25` are all accepted.
+pub fn parse_positional(tokens: &[String]) -> Result<(String, String, f64)> {
+    if tokens.len() != 3 {
+        return Err(anyhow!(
+            "Expected 3 positional arguments (<VALUE> <FROM> <TO>), got {}.\n\
+             Example: uniconv convert 25 c f",
+            tokens.len()
+        ));
+    }
+
+    let numeric: Vec<(usize, f64)> = tokens
+        .iter()
+        .enumerate()
+        .filter_map(|(i, token)| token.parse::<f64>().ok().map(|v| (i, v)))
+        .collect();
+
+    let (value_index, value) = match numeric.as_slice() {
+        [single] => *single,
+        [] => {
+            return Err(anyhow!(
+                "No numeric value found in '{}'. Example: uniconv convert 25 c f",
+                tokens.join(" ")
+            ))
+        }
+        _ => {
+            return Err(anyhow!(
+                "Ambiguous input '{}': expected exactly one numeric value but found {}.",
+                tokens.join(" "),
+                numeric.len()
+            ))
+        }
+    };
+
+    let mut units = tokens
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != value_index)
+        .map(|(_, token)| token.clone());
+    let from = units.next().unwrap_or_default();
+    let to = units.next().unwrap_or_default();
+
+    Ok((from, to, value))
+}
+
+/// Splits a fused quantity like `0c`, `32f` or `1.5e3m` into value and unit
+pub fn parse_quantity(input: &str) -> Result<(f64, String)> {
+    let input = input.trim();
+
+    // The longest numeric prefix is the value; the rest is the unit
+    let split = input
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(input.len()))
+        .rev()
+        .find(|&i| i > 0 && input[..i].parse::<f64>().is_ok());
+
+    match split {
+        Some(i) if !input[i..].trim().is_empty() => {
+            let value = input[..i].parse::<f64>()?;
+            Ok((value, input[i..].trim().to_string()))
+        }
+        _ => Err(anyhow!(
+            "Invalid quantity: '{}'. Expected a number followed by a unit (e.g., '0c', '32f').",
+            input
+        )),
+    }
+}
+
+/// Closest known unit name across all categories, temperature first
+pub fn suggest_unit(unit: &str) -> Option<String> {
+    UNIT_CATEGORIES
+        .iter()
+        .find_map(|(_, spellings)| find_closest_match(unit, spellings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::UnitParseError;
+
+    #[test]
+    fn test_normalize_whitespace_and_case() -> Result<()> {
+        assert_eq!(normalize_unit("CentiMeters ")?, "centimeter");
+        assert_eq!(normalize_unit("  KELVIN\t")?, "kelvin");
+        assert_eq!(normalize_unit("c")?, "celsius");
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_plurals() -> Result<()> {
+        assert_eq!(normalize_unit("inches")?, "inch");
+        assert_eq!(normalize_unit("Kilometers")?, "kilometer");
+        assert_eq!(normalize_unit("mile")?, "miles");
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_is_idempotent() -> Result<()> {
+        for messy in ["CentiMeters ", "Inches", "F", "mi"] {
+            let once = normalize_unit(messy)?;
+            assert_eq!(normalize_unit(&once)?, once);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_categorize_unit() -> Result<()> {
+        assert_eq!(categorize_unit("Fahrenheit")?, "temperature");
+        assert_eq!(categorize_unit("cm")?, "length");
+        assert_eq!(categorize_unit(" Inches ")?, "length");
+
+        let error = categorize_unit("fahrenhiet").unwrap_err().to_string();
+        assert!(
+            error.starts_with("Unrecognized unit: 'fahrenhiet'"),
+            "{}",
+            error
+        );
+        assert!(error.ends_with("Did you mean 'f'?"), "{}", error);
+
+        // Agrees with the categories `list-units` prints
+        for (category, spellings) in UNIT_CATEGORIES {
+            for spelling in *spellings {
+                assert_eq!(categorize_unit(spelling)?, *category, "'{}'", spelling);
+            }
+        }
+        Ok(())
+    }
+
+    fn tokens(input: &str) -> Vec<String> {
+        input.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_positional_reordered() -> Result<()> {
+        let expected = ("c".to_string(), "f".to_string(), 25.0);
+        assert_eq!(parse_positional(&tokens("25 c f"))?, expected);
+        assert_eq!(parse_positional(&tokens("c 25 f"))?, expected);
+        assert_eq!(parse_positional(&tokens("c f 25"))?, expected);
+
+        let (from, to, value) = parse_positional(&tokens("c -40 f"))?;
+        assert_eq!((from.as_str(), to.as_str(), value), ("c", "f", -40.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_positional_ambiguity_errors() {
+        let err = parse_positional(&tokens("25 30 f"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Ambiguous"));
+
+        let err = parse_positional(&tokens("c f k")).unwrap_err().to_string();
+        assert!(err.contains("No numeric value"));
+    }
+
+    #[test]
+    fn test_positional_needs_three_arguments() {
+        for input in ["25 c", "25", "188 cm inch km"] {
+            let err = parse_positional(&tokens(input)).unwrap_err().to_string();
+            assert!(
+                err.starts_with("Expected 3 positional arguments"),
+                "{}",
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_unit_parse_errors() {
+        let cause = |error: anyhow::Error| match error.downcast::<UnitParseError>() {
+            Ok(cause) => cause,
+            Err(error) => panic!("not a UnitParseError: {:#}", error),
+        };
+
+        let error = parse_temperature_unit("celcius").unwrap_err();
+        let message = error.to_string();
+        assert!(message.starts_with("Invalid temperature unit: 'celcius'."));
+        assert!(message.contains("Did you mean"));
+        assert!(matches!(cause(error), UnitParseError::UnknownUnit(unit) if unit == "celcius"));
+
+        let error = parse_length_unit("kilometr").unwrap_err();
+        assert!(error.to_string().contains("Did you mean"));
+        assert!(matches!(cause(error), UnitParseError::UnknownUnit(_)));
+
+        for unit in ["", "   "] {
+            assert!(matches!(
+                cause(parse_temperature_unit(unit).unwrap_err()),
+                UnitParseError::EmptyInput
+            ));
+            assert!(matches!(
+                cause(parse_length_unit(unit).unwrap_err()),
+                UnitParseError::EmptyInput
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_quantity() -> Result<()> {
+        assert_eq!(parse_quantity("0c")?, (0.0, "c".to_string()));
+        assert_eq!(parse_quantity("-40 F")?, (-40.0, "F".to_string()));
+        assert_eq!(parse_quantity("1.5e3m")?, (1500.0, "m".to_string()));
+        assert!(parse_quantity("32").is_err());
+        assert!(parse_quantity("cm").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_very_long_unit_gets_no_suggestion() {
+        let huge = "c".repeat(10_000);
+        let start = std::time::Instant::now();
+
+        assert_eq!(find_closest_match(&huge, TEMPERATURE_UNITS), None);
+        let err = normalize_unit(&huge).unwrap_err().to_string();
+        assert!(err.starts_with("Unrecognized unit"));
+        assert!(!err.contains("Did you mean"));
+
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_unit_aliases_belong_to_one_category() {
+        // Detection takes the first category that accepts a name, so an alias
+        // shared by two categories would silently shadow one of them
+        for (category, units) in UNIT_CATEGORIES {
+            for unit in *units {
+                let detected = recognize_unit(unit).map(|unit| unit.category());
+                assert_eq!(detected, Some(*category), "'{}'", unit);
+            }
+        }
+    }
+
+    #[test]
+    fn test_accepted_spellings_are_listed() {
+        // Spellings the parsers took that `list-units` once left out
+        for spelling in [
+            "pole",
+            "perch",
+            "å",
+            "μm",
+            "mile",
+            "gramme",
+            "kilogramme",
+            "metric-ton",
+            "imp-gal",
+            "gallon-us",
+            "gallon-imperial",
+        ] {
+            let category = recognize_unit(spelling).map(|unit| unit.category());
+            let listed = UNIT_CATEGORIES
+                .iter()
+                .find(|(_, spellings)| spellings.contains(&spelling))
+                .map(|(category, _)| *category);
+            assert!(category.is_some(), "'{}'", spelling);
+            assert_eq!(listed, category, "'{}'", spelling);
+        }
+    }
+
+    #[test]
+    fn test_normalize_unknown_suggests() {
+        let err = normalize_unit("celcius").unwrap_err().to_string();
+        assert!(err.contains("Unrecognized unit"));
+        assert!(err.contains("Did you mean"));
+    }
+}
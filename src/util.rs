@@ -0,0 +1,105 @@
+//! "Did you mean ...?" suggestions for unrecognized unit names
+
+/// Inputs longer than this get no suggestion: no unit name is anywhere near
+/// that long, and edit distances against a pasted blob only waste time
+pub const MAX_SUGGESTION_INPUT_LEN: usize = 64;
+
+/// The unit in `valid_units` that `input` most likely meant: one containing
+/// it (or contained in it), else the nearest within an edit distance of 3.
+/// Case is ignored.
+pub fn find_closest_match(input: &str, valid_units: &[&str]) -> Option<String> {
+    if input.chars().count() > MAX_SUGGESTION_INPUT_LEN {
+        return None;
+    }
+
+    let input_lower = input.to_lowercase();
+
+    // First, try exact matches or partial matches
+    for unit in valid_units {
+        if unit.to_lowercase().contains(&input_lower) || input_lower.contains(&unit.to_lowercase())
+        {
+            return Some(unit.to_string());
+        }
+    }
+
+    // If no partial match, find the unit with minimum edit distance
+    let mut best_match = None;
+    let mut min_distance = usize::MAX;
+
+    for unit in valid_units {
+        let distance = levenshtein_distance(&input_lower, &unit.to_lowercase());
+        if distance < min_distance && distance <= 3 {
+            // Only suggest if distance is reasonable
+            min_distance = distance;
+            best_match = Some(unit.to_string());
+        }
+    }
+
+    best_match
+}
+
+/// Edits (insertions, deletions, substitutions) between `s1` and `s2`,
+/// counted in characters rather than bytes
+pub fn levenshtein_distance(s1: &str, s2: &str) -> usize {
+    // Keep a single row of the edit-distance matrix: this runs against every
+    // known unit name for each unrecognized input
+    let s2: Vec<char> = s2.chars().collect();
+    let mut row: Vec<usize> = (0..=s2.len()).collect();
+
+    for (i, c1) in s1.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &c2) in s2.iter().enumerate() {
+            let cost = if c1 == c2 { 0 } else { 1 };
+            let substitution = diagonal + cost;
+            diagonal = row[j + 1];
+            row[j + 1] = (diagonal + 1).min(row[j] + 1).min(substitution);
+        }
+    }
+
+    row[s2.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein_distance() {
+        assert_eq!(levenshtein_distance("", ""), 0);
+        assert_eq!(levenshtein_distance("meter", ""), 5);
+        assert_eq!(levenshtein_distance("", "meter"), 5);
+        assert_eq!(levenshtein_distance("meter", "meter"), 0);
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(levenshtein_distance("celsuis", "celsius"), 2);
+
+        // Characters, not bytes: each of these is a single edit
+        assert_eq!(levenshtein_distance("µm", "um"), 1);
+        assert_eq!(levenshtein_distance("ångström", "angström"), 1);
+        assert_eq!(levenshtein_distance("日本", "日本語"), 1);
+    }
+
+    #[test]
+    fn test_find_closest_match() {
+        let units = &["celsius", "fahrenheit", "ångström", "parsec"];
+
+        // Partial matches win, in either direction
+        assert_eq!(
+            find_closest_match("fahren", units),
+            Some("fahrenheit".into())
+        );
+        assert_eq!(find_closest_match("parsecs", units), Some("parsec".into()));
+        assert_eq!(find_closest_match("CELSIUS", units), Some("celsius".into()));
+
+        // Then the nearest spelling, up to three edits away
+        assert_eq!(find_closest_match("celsuis", units), Some("celsius".into()));
+        assert_eq!(
+            find_closest_match("ÅNGSTROM", units),
+            Some("ångström".into())
+        );
+        assert_eq!(find_closest_match("xyzzy", units), None);
+
+        let huge = "c".repeat(MAX_SUGGESTION_INPUT_LEN + 1);
+        assert_eq!(find_closest_match(&huge, units), None);
+    }
+}
@@ -0,0 +1,101 @@
+//! Runs the built binary with `--all` and checks every unit is listed
+
+mod common;
+
+use common::uniconv;
+
+#[test]
+fn test_temperature_all_lists_every_unit() {
+    let output = uniconv("temperature --from celsius --value 100 --all");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    for expected in [
+        "100 °C = 100 °C\n",
+        "100 °C = 212 °F\n",
+        "100 °C = 373.15 K\n",
+    ] {
+        assert!(stdout.contains(expected), "{}", stdout);
+    }
+}
+
+#[test]
+fn test_all_follows_precision() {
+    let output = uniconv("temperature --from celsius --value 36.5 --all --precision 3");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("36.500 °C = 97.700 °F\n"), "{}", stdout);
+
+    let output = uniconv("length --from kilometer --value 1609.344 --all --precision 2");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("1609.34 km = 1000.00 mi\n"), "{}", stdout);
+}
+
+#[test]
+fn test_temperature_needs_to_or_all() {
+    let output = uniconv("temperature --from celsius --value 100");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("--to <UNIT>, or --all"), "{}", stderr);
+
+    assert!(
+        !uniconv("temperature --from celsius --to kelvin --value 100 --all")
+            .status
+            .success()
+    );
+}
+
+#[test]
+fn test_length_all_lists_every_unit() {
+    let output = uniconv("length --from kilometer --value 1 --all");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    for expected in [
+        "1 km = 100000 cm\n",
+        "1 km = 0.621371 mi\n",
+        "1 km = 49.709695 ch\n",
+    ] {
+        assert!(stdout.contains(expected), "{}", stdout);
+    }
+}
+
+#[test]
+fn test_length_all_skips_units_out_of_range() {
+    let output = uniconv("length --from parsec --value 1000000000000 --all");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("1000000000000 pc = 3261563777167.433594 ly"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains(" nm\n"), "{}", stdout);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Parsec to Nanometer"), "{}", stderr);
+}
+
+/// The unit each `--all` line ends with, in order
+fn units(stdout: &[u8]) -> Vec<String> {
+    String::from_utf8(stdout.to_vec())
+        .unwrap()
+        .lines()
+        .map(|line| line.rsplit([' ', ',']).next().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_all_follows_deterministic_order() {
+    let declaration = uniconv("temperature --from celsius --value 100 --all");
+    assert_eq!(units(&declaration.stdout), ["°C", "°F", "K", "°De", "°N"]);
+
+    let symbol =
+        uniconv("--deterministic-order symbol temperature --from celsius --value 100 --all");
+    assert_eq!(units(&symbol.stdout), ["K", "°C", "°De", "°F", "°N"]);
+
+    let csv =
+        uniconv("--deterministic-order symbol --format csv length --from meter --value 1 --all");
+    let csv_units = units(&csv.stdout);
+    assert_eq!(csv_units[..4], ["AU", "ch", "cm", "ft"]);
+
+    let mut sorted = csv_units.clone();
+    sorted.sort_by_key(|unit| unit.trim_matches('"').to_lowercase());
+    assert_eq!(csv_units, sorted);
+}
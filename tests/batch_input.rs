@@ -0,0 +1,128 @@
+//! Pipes request lines into `uniconv batch` and checks answers and summary
+
+mod common;
+
+use common::{command, uniconv, uniconv_with_stdin};
+use std::process::Output;
+
+fn batch(input: &str) -> Output {
+    uniconv_with_stdin("batch", input)
+}
+
+#[test]
+fn test_batch_answers_every_line() {
+    let output = batch("cm,inch,254\nkg,lb,0\n");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "254 cm = 100 in\n0 kg = 0 lb\n"
+    );
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("2 of 2 requests converted"));
+}
+
+#[test]
+fn test_batch_marks_bad_lines_and_fails_at_the_end() {
+    let output = batch("cm,inch,254\ncm,kg,1\nc,f,100\n");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "254 cm = 100 in");
+    assert!(lines[1].starts_with("error: line 2: "), "{}", lines[1]);
+    assert_eq!(lines[2], "100 °C = 212 °F");
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("2 of 3 requests converted, 1 failed"));
+}
+
+#[test]
+fn test_batch_reads_and_writes_files() {
+    let dir = std::env::temp_dir();
+    let requests = dir.join(format!("uniconv-batch-{}.txt", std::process::id()));
+    let answers = dir.join(format!("uniconv-batch-{}.out", std::process::id()));
+    std::fs::write(
+        &requests,
+        "# whitespace-separated\n100 c f\n0 c k\n254 cm in\n5 km mi\n",
+    )
+    .unwrap();
+
+    let output = command()
+        .args(["batch", "--file"])
+        .arg(&requests)
+        .arg("--output")
+        .arg(&answers)
+        .output()
+        .expect("failed to run uniconv");
+    assert!(output.status.success(), "{:?}", output);
+    assert!(output.stdout.is_empty());
+
+    assert_eq!(
+        std::fs::read_to_string(&answers).unwrap(),
+        "100 °C = 212 °F\n0 °C = 273.15 K\n254 cm = 100 in\n5 km = 3.106856 mi\n"
+    );
+    std::fs::remove_file(&requests).unwrap();
+    std::fs::remove_file(&answers).unwrap();
+}
+
+#[test]
+fn test_batch_round_trips_a_json_array() {
+    let output = batch(
+        r#"[{"from": "cm", "to": "in", "value": 254},
+            {"from": "cm", "to": "in", "value": "lots"},
+            {"from": "k", "to": "c", "value": 0}]"#,
+    );
+    assert!(!output.status.success());
+
+    let answers: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(answers.len(), 3);
+    assert_eq!(answers[0]["result"], 100.0);
+    assert_eq!(answers[0]["from"], "cm");
+    assert!(answers[1]["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("entry 2: "));
+    assert_eq!(answers[2]["result"], -273.15);
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("2 of 3 requests converted, 1 failed"));
+}
+
+#[test]
+fn test_batch_max_errors_aborts() {
+    let input = "c,kg,100\nkg,g,1\nfurlongs,psi,1\nbogus,g,1\nm,cm,1\n";
+    let output = uniconv_with_stdin("batch --max-errors 2", input);
+
+    // Three bad lines with a limit of two: the run stops at the second one
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap().lines().count(), 3);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains(
+            "Aborted: --max-errors reached after 2 failed request(s); 2 of 5 requests were not run"
+        ),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_batch_from_column_reads_each_rows_unit() {
+    let output = uniconv_with_stdin(
+        "batch --from-column 2 --to cm",
+        "10,in\n2,m\n1,cubits\n3,ft\n",
+    );
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "10 in = 25.4 cm");
+    assert_eq!(lines[1], "2 m = 200 cm");
+    assert!(lines[2].starts_with("error: line 3: "), "{}", lines[2]);
+    assert_eq!(lines[3], "3 ft = 91.44 cm");
+
+    // The target unit is required
+    let output = uniconv("batch --from-column 2");
+    assert!(!output.status.success());
+}
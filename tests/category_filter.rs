@@ -0,0 +1,60 @@
+//! Runs the built binary with `--only`, which restricts `list-units` and
+//! `--format table` to the given categories
+
+mod common;
+
+use common::uniconv;
+use std::process::Output;
+
+/// The category headers of a `list-units` listing, in order
+fn listed_categories(output: &Output) -> Vec<String> {
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with(' '))
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn test_list_units_only_lists_the_requested_categories() {
+    let output = uniconv("list-units --only length,temperature");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(listed_categories(&output), ["temperature", "length"]);
+
+    let output = uniconv("list-units --category mass,fuel");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(listed_categories(&output), ["mass", "fuel"]);
+
+    let output = uniconv("--format json list-units --only time,data");
+    assert!(output.status.success(), "{:?}", output);
+    let listings: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let listings = listings.as_array().unwrap();
+    assert!(!listings.is_empty());
+    assert!(listings
+        .iter()
+        .all(|listing| listing["category"] == "time" || listing["category"] == "data"));
+}
+
+#[test]
+fn test_list_units_rejects_unknown_only_categories() {
+    let output = uniconv("list-units --only length,colour");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_table_only_prints_the_requested_categories() {
+    let output = uniconv("--format table --only temperature,length convert 25 c f");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("fahrenheit |"), "{}", stdout);
+
+    let output = uniconv("--format table --only temperature,length convert 1 kg lb");
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("mass is not among --only temperature,length"),
+        "{}",
+        stderr
+    );
+}
@@ -0,0 +1,36 @@
+//! Runs the built binary with `NO_COLOR` set and checks for ANSI escapes
+
+mod common;
+
+fn uniconv_stdout(args: &str, no_color: Option<&str>) -> String {
+    let mut command = common::command();
+    command.args(args.split_whitespace()).env_remove("NO_COLOR");
+    if let Some(value) = no_color {
+        command.env("NO_COLOR", value);
+    }
+    let output = command.output().expect("failed to run uniconv");
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_no_color_suppresses_escapes() {
+    for value in ["1", ""] {
+        let stdout = uniconv_stdout("--color-temp --color auto convert 35 c f", Some(value));
+        assert_eq!(stdout, "35 °C = 95 °F\n");
+        assert!(!stdout.contains('\x1b'));
+    }
+}
+
+#[test]
+fn test_color_always_overrides_no_color() {
+    let stdout = uniconv_stdout("--color-temp --color always convert 35 c f", Some("1"));
+    assert!(stdout.contains("\x1b["), "{:?}", stdout);
+    assert!(stdout.contains("95 °F"));
+
+    let stdout = uniconv_stdout(
+        "--color-temp --color always --no-color convert 35 c f",
+        None,
+    );
+    assert!(!stdout.contains('\x1b'));
+}
@@ -0,0 +1,47 @@
+//! Spawns the built binary for the integration tests. Every test file
+//! compiles its own copy and uses only some of these helpers.
+
+#![allow(dead_code)]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// The built binary, for tests that add files or environment variables
+pub fn command() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_uniconv"))
+}
+
+/// Runs the binary with whitespace-separated `args`
+pub fn uniconv(args: &str) -> Output {
+    command()
+        .args(args.split_whitespace())
+        .output()
+        .expect("failed to run uniconv")
+}
+
+/// Stdout of a run that has to succeed
+pub fn uniconv_stdout(args: &str) -> String {
+    let output = uniconv(args);
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Runs the binary with `stdin` piped in
+pub fn uniconv_with_stdin(args: &str, stdin: &str) -> Output {
+    let mut child = command()
+        .args(args.split_whitespace())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run uniconv");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child
+        .wait_with_output()
+        .expect("failed to wait for uniconv")
+}
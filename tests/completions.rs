@@ -0,0 +1,28 @@
+//! Runs the built binary to generate shell completion scripts
+
+mod common;
+
+use common::uniconv;
+
+#[test]
+fn test_bash_completions_list_subcommands_and_units() {
+    let output = uniconv("completions bash");
+    assert!(output.status.success(), "{:?}", output);
+    let script = String::from_utf8(output.stdout).unwrap();
+    for subcommand in [
+        "temperature",
+        "length",
+        "convert",
+        "list-units",
+        "completions",
+    ] {
+        assert!(script.contains(subcommand), "missing {}", subcommand);
+    }
+    assert!(script.contains("celsius fahrenheit kelvin"));
+}
+
+#[test]
+fn test_unknown_shell_fails() {
+    let output = uniconv("completions tcsh");
+    assert!(!output.status.success());
+}
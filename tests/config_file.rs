@@ -0,0 +1,71 @@
+//! Runs the built binary with a config file of defaults
+
+mod common;
+
+use std::path::PathBuf;
+use std::process::Output;
+
+fn write_config(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "uniconv-config-{}-{}.toml",
+        name,
+        std::process::id()
+    ));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+fn uniconv(args: &[&str], config: Option<&PathBuf>) -> Output {
+    let mut command = common::command();
+    command.args(args).env_remove("UNICONV_CONFIG");
+    if let Some(config) = config {
+        command.env("UNICONV_CONFIG", config);
+    }
+    command.output().expect("failed to run uniconv")
+}
+
+#[test]
+fn test_config_precision_applies_unless_flag_given() {
+    let config = write_config("precision", "precision = 3\n");
+    let flag = format!("--config={}", config.display());
+
+    let output = uniconv(&[&flag, "convert", "1", "km", "mi"], None);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(output.stdout, "1.000 km = 0.621 mi\n".as_bytes());
+
+    // The flag wins over the file, wherever it is given
+    let output = uniconv(
+        &["convert", "1", "km", "mi", &flag, "--precision", "1"],
+        None,
+    );
+    assert_eq!(output.stdout, "1.0 km = 0.6 mi\n".as_bytes());
+
+    // UNICONV_CONFIG names the file when --config doesn't
+    let output = uniconv(&["convert", "1", "km", "mi"], Some(&config));
+    assert_eq!(output.stdout, "1.000 km = 0.621 mi\n".as_bytes());
+
+    std::fs::remove_file(config).unwrap();
+}
+
+#[test]
+fn test_bad_config_fails() {
+    let config = write_config("bad", "precision = \"two\"\n");
+    let output = uniconv(&["convert", "1", "km", "mi"], Some(&config));
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Invalid config file"), "{}", stderr);
+    std::fs::remove_file(config).unwrap();
+
+    let output = uniconv(
+        &[
+            "--config",
+            "/nonexistent/uniconv.toml",
+            "convert",
+            "1",
+            "km",
+            "mi",
+        ],
+        None,
+    );
+    assert!(!output.status.success());
+}
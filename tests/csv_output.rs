@@ -0,0 +1,26 @@
+//! Runs the built binary with `--format csv` and checks the records
+
+mod common;
+
+use common::uniconv_stdout;
+
+#[test]
+fn test_one_record_without_header() {
+    let stdout = uniconv_stdout("--format csv convert --from cm --to in --value 188");
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(lines, ["188,cm,74.01574803149606,in"]);
+    let fields: Vec<&str> = lines[0].split(',').collect();
+    assert_eq!(fields.len(), 4);
+    assert_eq!(fields[2].parse::<f64>().unwrap(), 188.0 / 2.54);
+}
+
+#[test]
+fn test_header_and_quoted_symbols() {
+    let stdout = uniconv_stdout("--format csv --csv-header convert 100 c f");
+
+    assert_eq!(
+        stdout,
+        "from_value,from_unit,to_value,to_unit\n100,\"°C\",212,\"°F\"\n"
+    );
+}
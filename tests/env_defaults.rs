@@ -0,0 +1,60 @@
+//! Runs the built binary with `UNICONV_*` environment variables as defaults
+
+mod common;
+
+use common::command;
+use std::process::Output;
+
+fn uniconv(args: &str, env: &[(&str, &str)]) -> Output {
+    command()
+        .args(args.split_whitespace())
+        .env_remove("UNICONV_CONFIG")
+        .envs(env.iter().copied())
+        .output()
+        .expect("failed to run uniconv")
+}
+
+#[test]
+fn test_precision_from_env() {
+    let output = uniconv("convert 37 c f", &[("UNICONV_PRECISION", "3")]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(output.stdout, "37.000 °C = 98.600 °F\n".as_bytes());
+
+    // A flag wins over the variable, even a different one that replaces it
+    let output = uniconv(
+        "convert 37 c f --precision 1",
+        &[("UNICONV_PRECISION", "3")],
+    );
+    assert_eq!(output.stdout, "37.0 °C = 98.6 °F\n".as_bytes());
+    let output = uniconv("convert 37 c f --sig-figs 2", &[("UNICONV_PRECISION", "3")]);
+    assert_eq!(output.stdout, "37 °C = 99 °F\n".as_bytes());
+
+    let output = uniconv("convert 37 c f", &[("UNICONV_PRECISION", "lots")]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_env_wins_over_config_file() {
+    let config = std::env::temp_dir().join(format!("uniconv-env-{}.toml", std::process::id()));
+    std::fs::write(&config, "precision = 5\nformat = \"csv\"\n").unwrap();
+    let config_path = config.to_str().unwrap();
+
+    let output = uniconv(
+        "convert 37 c f",
+        &[
+            ("UNICONV_CONFIG", config_path),
+            ("UNICONV_PRECISION", "3"),
+            ("UNICONV_FORMAT", "json"),
+        ],
+    );
+    assert_eq!(
+        output.stdout,
+        "{\"value\":37.0,\"from\":\"°C\",\"to\":\"°F\",\"result\":98.6,\"warnings\":[]}\n"
+            .as_bytes()
+    );
+
+    let output = uniconv("convert 0.125 c c", &[("UNICONV_ROUNDING", "half-up")]);
+    assert_eq!(output.stdout, "0.13 °C = 0.13 °C\n".as_bytes());
+
+    std::fs::remove_file(config).unwrap();
+}
@@ -0,0 +1,24 @@
+//! Runs the built binary's `gas-volume` calculator and checks the printed
+//! volume
+
+mod common;
+
+use common::uniconv;
+
+#[test]
+fn test_molar_volume_at_stp() {
+    let output =
+        uniconv("gas-volume --moles 1 --temp 0 --temp-unit c --pressure 1 --pressure-unit atm");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "1 mol at 0 °C and 1 atm = 22.41397 L\n"
+    );
+
+    let output = uniconv(
+        "gas-volume --moles 1 --temp 273.15 --pressure 101325 --pressure-unit pa --precision 3",
+    );
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.ends_with("= 22.414 L\n"), "{}", stdout);
+}
@@ -0,0 +1,114 @@
+//! Runs the built binary with `--format json` and checks what scripts see
+
+mod common;
+
+use common::uniconv;
+use serde_json::Value;
+use std::process::Output;
+
+fn stdout_json(output: &Output) -> Value {
+    assert!(output.status.success(), "{:?}", output);
+    serde_json::from_slice(&output.stdout).expect("stdout is not one JSON object")
+}
+
+#[test]
+fn test_convert_prints_one_object() {
+    let output = uniconv("convert --from cm --to in --value 188 --format json");
+    let json = stdout_json(&output);
+
+    let mut keys: Vec<&str> = json
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    keys.sort_unstable();
+    assert_eq!(keys, ["from", "result", "to", "value", "warnings"]);
+    assert_eq!(json["warnings"], serde_json::json!([]));
+    assert_eq!(json["value"], 188.0);
+    assert_eq!(json["from"], "cm");
+    assert_eq!(json["to"], "in");
+}
+
+#[test]
+fn test_warnings_go_into_the_object() {
+    let output = uniconv("--format json convert 90 d mo");
+    let json = stdout_json(&output);
+
+    let warnings = json["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0]["code"], "approximate");
+    assert!(warnings[0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("1 month = 30.44 days"));
+    // Carried in the object, so not repeated on stderr
+    assert!(output.stderr.is_empty(), "{:?}", output);
+
+    let output = uniconv("--format json convert 1 gal l");
+    let json = stdout_json(&output);
+    assert_eq!(json["warnings"][0]["code"], "assumed");
+}
+
+#[test]
+fn test_result_keeps_full_precision() {
+    let output = uniconv("--format json length --from centimeter --to inch --value 188");
+    let json = stdout_json(&output);
+
+    // The text output rounds this to 74; JSON must carry every bit
+    assert_eq!(json["result"].as_f64(), Some(188.0 / 2.54));
+}
+
+#[test]
+fn test_temperature_uses_unit_symbols() {
+    let output = uniconv("--format json temperature --from celsius --to fahrenheit --value 100");
+    let json = stdout_json(&output);
+
+    assert_eq!(json["from"], "°C");
+    assert_eq!(json["to"], "°F");
+    assert_eq!(json["result"], 212.0);
+}
+
+#[test]
+fn test_errors_go_to_stderr_as_json() {
+    let output = uniconv("--format json convert --from c --to kg --value 1");
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let json: Value =
+        serde_json::from_slice(&output.stderr).expect("stderr is not one JSON object");
+    assert_eq!(json.as_object().unwrap().len(), 1);
+    assert!(json["error"]
+        .as_str()
+        .unwrap()
+        .contains("different categories"));
+}
+
+#[test]
+fn test_decimal_comma_leaves_json_alone() {
+    let output = uniconv("--format json --decimal-separator comma --group-digits convert 1 nmi m");
+    let json = stdout_json(&output);
+
+    assert_eq!(json["result"], 1852.0);
+    assert!(String::from_utf8_lossy(&output.stdout).contains(r#""result":1852.0"#));
+}
+
+#[test]
+fn test_all_prints_the_results_object() {
+    let output = uniconv("--format json temperature --from celsius --value 100 --all");
+    let json = stdout_json(&output);
+    assert_eq!(json["fahrenheit"], 212.0);
+    assert_eq!(json["kelvin"], 373.15);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "{\"celsius\":100.0,\"fahrenheit\":212.0,\"kelvin\":373.15,\"delisle\":0.0,\"newton\":33.0}\n"
+    );
+
+    // --deterministic-order rearranges the fields too
+    let output =
+        uniconv("--format json --deterministic-order symbol length --from meter --value 1 --all");
+    let json = stdout_json(&output);
+    assert_eq!(json["nautical_mile"], 1.0 / 1852.0);
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(text.starts_with("{\"astronomical_unit\":"), "{}", text);
+}
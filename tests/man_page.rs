@@ -0,0 +1,17 @@
+//! Runs the built binary to render its man page
+
+mod common;
+
+use common::uniconv;
+
+#[test]
+fn test_man_page_renders() {
+    let output = uniconv("man");
+    assert!(output.status.success(), "{:?}", output);
+    let page = String::from_utf8(output.stdout).unwrap();
+    assert!(page.starts_with(".ie"), "{}", page);
+    assert!(page.contains(".TH uniconv 1"));
+    assert!(page.contains("temperature"));
+    // The long_about examples make up the DESCRIPTION
+    assert!(page.contains("Smart conversion"));
+}
@@ -0,0 +1,34 @@
+//! Runs the built binary with several `--value`s in one invocation
+
+mod common;
+
+use common::uniconv;
+
+#[test]
+fn test_one_line_per_value() {
+    let output = uniconv(
+        "temperature --from celsius --to fahrenheit --value 36.5 --value 37.0 --value 38.2",
+    );
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 3, "{}", stdout);
+
+    let output = uniconv("convert --from c --to f --value 0,100");
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "0 °C = 32 °F\n100 °C = 212 °F\n"
+    );
+}
+
+#[test]
+fn test_bad_value_does_not_stop_the_rest() {
+    let output = uniconv("mass --from kilogram --to gram --value 1 --value -2 --value 3");
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "1 kg = 1000 g\n3 kg = 3000 g\n"
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("cannot be negative"), "{}", stderr);
+    assert!(stderr.contains("1 of 3 values failed"), "{}", stderr);
+}
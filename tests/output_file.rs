@@ -0,0 +1,43 @@
+//! Runs the built binary with `--output` and reads the file back
+
+use std::path::Path;
+mod common;
+
+use common::command;
+
+fn uniconv(args: &[&str], output: &Path) {
+    let result = command()
+        .args(args)
+        .arg("--output")
+        .arg(output)
+        .output()
+        .expect("failed to run uniconv");
+    assert!(result.status.success(), "{:?}", result);
+    assert!(result.stdout.is_empty(), "{:?}", result);
+}
+
+#[test]
+fn test_output_truncates_and_append_keeps_prior_lines() {
+    let path = std::env::temp_dir().join(format!("uniconv-output-{}.txt", std::process::id()));
+    std::fs::write(&path, "stale line\n").unwrap();
+
+    uniconv(&["convert", "100", "c", "f"], &path);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "100 °C = 212 °F\n");
+
+    uniconv(&["--append", "convert", "254", "cm", "in"], &path);
+    uniconv(
+        &["--append", "--format", "csv", "convert", "1", "kg", "g"],
+        &path,
+    );
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "100 °C = 212 °F\n254 cm = 100 in\n1,kg,1000,g\n"
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_append_needs_output() {
+    let result = common::uniconv("--append convert 100 c f");
+    assert!(!result.status.success());
+}
@@ -0,0 +1,30 @@
+//! Runs the built binary with the quick `convert <VALUE> <FROM> <TO>` form
+
+mod common;
+
+use common::uniconv;
+
+#[test]
+fn test_positional_matches_named_flags() {
+    let positional = uniconv("convert 188 cm inch");
+    assert!(positional.status.success(), "{:?}", positional);
+    assert_eq!(
+        String::from_utf8(positional.stdout).unwrap(),
+        "188 cm = 74.015748 in\n"
+    );
+
+    let named = uniconv("convert --from cm --to inch --value 188");
+    assert_eq!(named.stdout, b"188 cm = 74.015748 in\n");
+}
+
+#[test]
+fn test_too_few_positionals_fail() {
+    let output = uniconv("convert 188 cm");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Expected 3 positional arguments"),
+        "{}",
+        stderr
+    );
+}
@@ -0,0 +1,25 @@
+//! Runs the built binary with `--range` sweeping a span of values
+
+mod common;
+
+use common::uniconv;
+
+#[test]
+fn test_celsius_to_fahrenheit_sweep() {
+    let output = uniconv("temperature --from celsius --to fahrenheit --range 0..100:10");
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 11);
+    assert_eq!(lines.first(), Some(&"0 °C = 32 °F"));
+    assert_eq!(lines.last(), Some(&"100 °C = 212 °F"));
+}
+
+#[test]
+fn test_oversized_range_fails() {
+    let output = uniconv("temperature --from celsius --to fahrenheit --range 0..1000000:1");
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("at most 10000"), "{}", stderr);
+}
@@ -0,0 +1,26 @@
+//! Runs the built binary with `--raw` and checks that only the number is printed
+
+mod common;
+
+use common::uniconv_stdout;
+
+#[test]
+fn test_raw_prints_the_number_alone() {
+    assert_eq!(
+        uniconv_stdout("temperature --from celsius --to fahrenheit --value 100 --raw"),
+        "212\n"
+    );
+    assert_eq!(uniconv_stdout("--quiet convert 5 km mi"), "3.106856\n");
+}
+
+#[test]
+fn test_raw_respects_precision() {
+    assert_eq!(
+        uniconv_stdout("--raw --precision 3 length --from kilometer --to miles --value 5"),
+        "3.107\n"
+    );
+    assert_eq!(
+        uniconv_stdout("--raw --precision 1 convert 100 c f"),
+        "212.0\n"
+    );
+}
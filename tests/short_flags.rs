@@ -0,0 +1,35 @@
+//! Runs the built binary with `-f`/`-t`/`-v` and the long flags side by side
+
+mod common;
+
+use common::uniconv_stdout;
+
+#[test]
+fn test_short_flags_match_long_flags() {
+    let pairs = [
+        (
+            "convert -f c -t f -v 25",
+            "convert --from c --to f --value 25",
+        ),
+        (
+            "convert -f c -t f -v -40",
+            "convert --from c --to f --value -40",
+        ),
+        (
+            "temperature -f celsius -t kelvin -v 100",
+            "temperature --from celsius --to kelvin --value 100",
+        ),
+        (
+            "length -f kilometer -t miles -v 5",
+            "length --from kilometer --to miles --value 5",
+        ),
+    ];
+    for (short, long) in pairs {
+        assert_eq!(uniconv_stdout(short), uniconv_stdout(long), "{}", short);
+    }
+}
+
+#[test]
+fn test_version_keeps_capital_v() {
+    assert!(uniconv_stdout("-V").starts_with("uniconv "));
+}
@@ -0,0 +1,32 @@
+//! Runs the built binary with the value piped in instead of passed as `--value`
+
+mod common;
+
+use common::uniconv_with_stdin;
+
+#[test]
+fn test_value_piped_in() {
+    let output = uniconv_with_stdin("convert --from c --to f", "25\n");
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "25 °C = 77 °F\n");
+
+    let output = uniconv_with_stdin("mass --from kilogram --to gram", "2");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "2 kg = 2000 g\n");
+}
+
+#[test]
+fn test_empty_or_bad_stdin_fails() {
+    let output = uniconv_with_stdin("convert --from c --to f", "");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("No value on stdin"), "{}", stderr);
+
+    let output = uniconv_with_stdin("convert --from c --to f", "warm\n");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("'warm' on stdin is not a number"),
+        "{}",
+        stderr
+    );
+}
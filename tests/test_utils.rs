@@ -0,0 +1,22 @@
+//! Calls `assert_converts` the way another crate's test suite would, through
+//! the `test-utils` feature
+
+use uniconv::assert_converts;
+
+#[test]
+fn test_assert_converts_from_another_crate() {
+    assert_converts("c", "f", 100.0, 212.0, 1e-9);
+    assert_converts("Kilos", "lb", 1.0, 2.204_62, 1e-5);
+    assert_converts("gib", "mb", 1.0, 1_073.741_824, 1e-9);
+}
+
+#[test]
+#[should_panic(expected = "converting 1 m to cm: expected 99 ± 0.5, got 100 (off by 1)")]
+fn test_assert_converts_panics_on_mismatch() {
+    assert_converts("m", "cm", 1.0, 99.0, 0.5);
+}
+
+#[test]
+fn test_convert_is_reexported() {
+    assert_eq!(uniconv::convert("km", "m", 1.5).unwrap(), 1500.0);
+}